@@ -1,12 +1,14 @@
 // Build script to validate JSON database files at compile time
 // This prevents runtime panics from corrupted database files
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
 fn main() {
     println!("cargo:rerun-if-changed=data/cccs_algorithms.json");
     println!("cargo:rerun-if-changed=data/cmvp_certificates.json");
+    println!("cargo:rerun-if-changed=data/rules");
 
     // Validate CCCS algorithms database
     let cccs_path = Path::new("data/cccs_algorithms.json");
@@ -37,5 +39,92 @@ fn main() {
         Err(e) => panic!("CMVP certificates database contains invalid JSON: {}", e),
     }
 
+    // Validate embedded rule packs
+    let rules_dir = Path::new("data/rules");
+    if !rules_dir.join("core.json").exists() {
+        panic!("Core rule pack not found at data/rules/core.json");
+    }
+
+    let entries = fs::read_dir(rules_dir).expect("Failed to read data/rules directory");
+    for entry in entries {
+        let path = entry.expect("Failed to read data/rules entry").path();
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            println!("cargo:rerun-if-changed={}", path.display());
+            validate_rule_pack(&path);
+        }
+    }
+
     println!("cargo:warning=All database files validated at build time");
 }
+
+/// Check a rule pack's structure and compile its regexes so the embedded
+/// packs can be loaded at runtime without panicking
+fn validate_rule_pack(path: &Path) {
+    let name = path.display();
+    let data =
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("Failed to read {}: {}", name, e));
+    let pack: serde_json::Value = serde_json::from_str(&data)
+        .unwrap_or_else(|e| panic!("Rule pack {} contains invalid JSON: {}", name, e));
+
+    let rules = pack["rules"]
+        .as_array()
+        .unwrap_or_else(|| panic!("Rule pack {} is missing a 'rules' array", name));
+    if rules.is_empty() {
+        panic!("Rule pack {} contains no rules", name);
+    }
+
+    let mut ids = HashSet::new();
+    for rule in rules {
+        let id = rule["id"]
+            .as_str()
+            .unwrap_or_else(|| panic!("Rule pack {} has a rule without an 'id'", name));
+        if !ids.insert(id) {
+            panic!("Rule pack {} has duplicate rule id '{}'", name, id);
+        }
+
+        for field in ["crypto_type", "severity", "message", "recommendation"] {
+            if !rule[field].is_string() {
+                panic!("Rule '{}' in {} is missing '{}'", id, name, field);
+            }
+        }
+        match rule["risk_score"].as_u64() {
+            Some(score) if score <= 100 => {}
+            _ => panic!("Rule '{}' in {} needs a risk_score of 0-100", id, name),
+        }
+
+        let pattern = rule["pattern"].as_str();
+        let has_tokens = rule["tokens"].as_array().is_some_and(|t| !t.is_empty());
+        match (pattern, has_tokens) {
+            (Some(pattern), false) => {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    panic!("Rule '{}' in {} has an invalid pattern: {}", id, name, e);
+                }
+            }
+            (None, true) => {}
+            _ => panic!(
+                "Rule '{}' in {} needs exactly one of 'pattern' or 'tokens'",
+                id, name
+            ),
+        }
+
+        if let Some(key_pattern) = rule["key_size"]["pattern"].as_str() {
+            match regex::Regex::new(key_pattern) {
+                Ok(re) if re.captures_len() >= 2 => {}
+                Ok(_) => panic!(
+                    "Rule '{}' in {} key_size pattern needs a capture group",
+                    id, name
+                ),
+                Err(e) => panic!(
+                    "Rule '{}' in {} has an invalid key_size pattern: {}",
+                    id, name, e
+                ),
+            }
+        }
+    }
+
+    println!(
+        "cargo:warning=Rule pack {} validated successfully ({} rules)",
+        name,
+        rules.len()
+    );
+}
@@ -73,6 +73,7 @@ fn main() {
 
     // Analyze all files
     let mut all_vulnerabilities = Vec::new();
+    let mut all_quantum_safe = Vec::new();
     let mut total_lines = 0;
     let mut total_files = 0;
 
@@ -87,6 +88,7 @@ fn main() {
                 for vuln in result.vulnerabilities {
                     all_vulnerabilities.push(vuln);
                 }
+                all_quantum_safe.extend(result.quantum_safe);
             }
             Err(e) => {
                 eprintln!("  Error scanning {}: {}", _file_path, e);
@@ -97,7 +99,8 @@ fn main() {
     println!("\n=== Scan Results ===");
     println!("Files scanned: {}", total_files);
     println!("Lines scanned: {}", total_lines);
-    println!("Vulnerabilities found: {}\n", all_vulnerabilities.len());
+    println!("Vulnerabilities found: {}", all_vulnerabilities.len());
+    println!("Post-quantum algorithm uses: {}\n", all_quantum_safe.len());
 
     // Create a synthetic audit result for report generation
     use pqc_scanner::Language;
@@ -123,7 +126,9 @@ fn main() {
                 .iter()
                 .filter(|v| matches!(v.severity, pqc_scanner::Severity::Low))
                 .count(),
+            quantum_safe_count: all_quantum_safe.len(),
         },
+        quantum_safe: all_quantum_safe,
         risk_score: calculate_risk_score(&all_vulnerabilities),
         language: Language::JavaScript, // Default, doesn't matter for report
         recommendations: Vec::new(),
@@ -122,6 +122,7 @@ pub fn get_cccs_status(crypto_type: &CryptoType) -> CCCSApprovalStatus {
     let algorithm_name = match crypto_type {
         CryptoType::Rsa => "RSA",
         CryptoType::Ecdsa => "ECDSA",
+        CryptoType::Eddsa => "EdDSA",
         CryptoType::Ecdh => "ECDH",
         CryptoType::Dsa => "DSA",
         CryptoType::DiffieHellman => "DH",
@@ -130,6 +131,21 @@ pub fn get_cccs_status(crypto_type: &CryptoType) -> CCCSApprovalStatus {
         CryptoType::Des => "DES",
         CryptoType::TripleDes => "3DES",
         CryptoType::Rc4 => "RC4",
+        CryptoType::Aes => "AES",
+        CryptoType::Blowfish => "Blowfish",
+        CryptoType::Rc2 => "RC2",
+        CryptoType::Sha224 => "SHA-2",
+        CryptoType::EcbMode => "ECB",
+        CryptoType::StaticIv => "IV",
+        CryptoType::Sike => "SIKE",
+        CryptoType::Rainbow => "Rainbow",
+        CryptoType::Kyber => "CRYSTALS-Kyber",
+        CryptoType::Dilithium => "CRYSTALS-Dilithium",
+        CryptoType::SphincsPlus => "SPHINCS+",
+        CryptoType::NonHybridKem => "ML-KEM",
+        CryptoType::WeakRandom => "RNG",
+        CryptoType::UnsignedJwt => "JWT none",
+        CryptoType::WeakJwtSecret => "HMAC",
     };
 
     get_algorithm_validation(algorithm_name)
@@ -179,7 +195,10 @@ pub fn validate_key_size(
 
     match crypto_type {
         CryptoType::Rsa => key_size >= requirements.minimum_rsa_key_size,
-        CryptoType::Ecdsa | CryptoType::Ecdh => key_size >= requirements.minimum_ecc_key_size,
+        CryptoType::Ecdsa | CryptoType::Eddsa | CryptoType::Ecdh => {
+            key_size >= requirements.minimum_ecc_key_size
+        }
+        CryptoType::Aes => key_size >= requirements.minimum_aes_key_size,
         _ => true, // Other types don't have size requirements in this context
     }
 }
@@ -309,6 +328,7 @@ pub fn get_itsp_reference(crypto_type: &CryptoType) -> String {
     let algorithm_name = match crypto_type {
         CryptoType::Rsa => "RSA",
         CryptoType::Ecdsa => "ECDSA",
+        CryptoType::Eddsa => "EdDSA",
         CryptoType::Ecdh => "ECDH",
         CryptoType::Dsa => "DSA",
         CryptoType::DiffieHellman => "DH",
@@ -317,6 +337,21 @@ pub fn get_itsp_reference(crypto_type: &CryptoType) -> String {
         CryptoType::Des => "DES",
         CryptoType::TripleDes => "3DES",
         CryptoType::Rc4 => "RC4",
+        CryptoType::Aes => "AES",
+        CryptoType::Blowfish => "Blowfish",
+        CryptoType::Rc2 => "RC2",
+        CryptoType::Sha224 => "SHA-2",
+        CryptoType::EcbMode => "ECB",
+        CryptoType::StaticIv => "IV",
+        CryptoType::Sike => "SIKE",
+        CryptoType::Rainbow => "Rainbow",
+        CryptoType::Kyber => "CRYSTALS-Kyber",
+        CryptoType::Dilithium => "CRYSTALS-Dilithium",
+        CryptoType::SphincsPlus => "SPHINCS+",
+        CryptoType::NonHybridKem => "ML-KEM",
+        CryptoType::WeakRandom => "RNG",
+        CryptoType::UnsignedJwt => "JWT none",
+        CryptoType::WeakJwtSecret => "HMAC",
     };
 
     get_algorithm_validation(algorithm_name)
@@ -1,7 +1,25 @@
+use crate::commands;
+use crate::confidence;
+use crate::detector::{self, CryptoPattern, Prefilter};
+use crate::jca;
+use crate::jose;
+use crate::lexer::{LexedSource, Span};
+use crate::parser;
+use crate::pqc;
+use crate::randomness;
+use crate::rules::{RulePack, RulePackError};
+use crate::scope::ScopeIndex;
+use crate::strength;
+use crate::symbols::{ProjectIndex, SymbolTable};
 use crate::types::*;
 use lazy_static::lazy_static;
+use once_cell::sync::Lazy;
 use regex::Regex;
-use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,6 +27,9 @@ pub enum AuditError {
     #[error("Unsupported language: {0}")]
     UnsupportedLanguage(String),
 
+    #[error("Could not detect the source language")]
+    LanguageNotDetected,
+
     #[error("Invalid source code")]
     InvalidSource,
 
@@ -25,418 +46,762 @@ pub enum AuditError {
 // Input validation constants
 const MAX_SOURCE_SIZE: usize = 10 * 1024 * 1024; // 10MB
 const MAX_LINES: usize = 500_000;
+const MAX_STATEMENT_LINES: usize = 32; // longest multi-line statement scanned as one
 
-// Lazy-compiled regex patterns for crypto detection
+// Lazy-compiled regex patterns for string-literal classification
 lazy_static! {
-    // RSA patterns with key size detection
-    static ref RSA_PATTERN: Regex = Regex::new(
-        r"(?i)(RSA|Rsa|rsa)[^a-zA-Z]*([\d]{3,4})?|(generate.*rsa.*key|rsa.*keygen)"
-    ).expect("RSA_PATTERN: Invalid regex - this is a compile-time bug");
-
-    static ref RSA_KEYGEN: Regex = Regex::new(
-        r"(?i)(RSA\.generate|generateKeyPair.*RSA|KeyPairGenerator\.getInstance.*RSA|rsa\.GenerateKey)"
-    ).expect("RSA_KEYGEN: Invalid regex - this is a compile-time bug");
-
-    static ref RSA_KEY_SIZE: Regex = Regex::new(
-        r"(?i)(?:rsa|RSA)[^0-9]*(512|1024|2048|3072|4096|8192)"
-    ).expect("RSA_KEY_SIZE: Invalid regex - this is a compile-time bug");
-
-    // ECDSA/ECDH patterns
-    static ref ECDSA_PATTERN: Regex = Regex::new(
-        r"(?i)(ECDSA|ECC|elliptic.*curve|secp256k1|secp384r1|prime256v1|P-256|P-384|P-521)"
-    ).expect("ECDSA_PATTERN: Invalid regex - this is a compile-time bug");
-
-    static ref ECDH_PATTERN: Regex = Regex::new(
-        r"(?i)(ECDH|ecdh|elliptic.*diffie|curve25519)"
-    ).expect("ECDH_PATTERN: Invalid regex - this is a compile-time bug");
-
-    // DSA and Diffie-Hellman
-    static ref DSA_PATTERN: Regex = Regex::new(
-        r"(?i)(DSA|dsa)[^a-zA-Z]|(Digital.*Signature.*Algorithm)"
-    ).expect("DSA_PATTERN: Invalid regex - this is a compile-time bug");
-
-    static ref DH_PATTERN: Regex = Regex::new(
-        r"(?i)(diffie.*hellman|DH_|DHE|DHE_)"
-    ).expect("DH_PATTERN: Invalid regex - this is a compile-time bug");
-
-    // Deprecated hash functions
-    static ref SHA1_PATTERN: Regex = Regex::new(
-        r"(?i)(SHA1|sha1|SHA-1|sha-1)[^0-9]"
-    ).expect("SHA1_PATTERN: Invalid regex - this is a compile-time bug");
-
-    static ref MD5_PATTERN: Regex = Regex::new(
-        r"(?i)(MD5|md5)"
-    ).expect("MD5_PATTERN: Invalid regex - this is a compile-time bug");
-
-    // Deprecated ciphers - simpler pattern without lookahead
-    static ref DES_PATTERN: Regex = Regex::new(
-        r"(?i)(DES_|_DES|\.DES|DES\.|\bDES\b)"
-    ).expect("DES_PATTERN: Invalid regex - this is a compile-time bug");
-
-    static ref TRIPLE_DES_PATTERN: Regex = Regex::new(
-        r"(?i)(3DES|TripleDES|DESede)"
-    ).expect("TRIPLE_DES_PATTERN: Invalid regex - this is a compile-time bug");
-
-    static ref RC4_PATTERN: Regex = Regex::new(
-        r"(?i)(RC4|rc4|ARCFOUR)"
-    ).expect("RC4_PATTERN: Invalid regex - this is a compile-time bug");
+    // String literals that look like algorithm identifiers ('md5', "RSA/ECB/PKCS1Padding")
+    static ref ALGORITHM_IDENTIFIER: Regex = Regex::new(
+        r"^[A-Za-z0-9][A-Za-z0-9_\-/.:+]{0,63}$"
+    ).expect("ALGORITHM_IDENTIFIER: Invalid regex - this is a compile-time bug");
+
+    // Callee names that take an algorithm identifier argument
+    static ref CRYPTO_API_CALLEE: Regex = Regex::new(
+        r"(?i)(hash|digest|cipher|crypt|sign|verif|key|instance|hmac|mac|curve|ecdh|ecdsa|rsa|dsa|algorithm|create|generate|derive|pbkdf|tls|ssl|(^|[.:])new$)"
+    ).expect("CRYPTO_API_CALLEE: Invalid regex - this is a compile-time bug");
+
+    // Trailing callee name immediately before an argument list
+    static ref CALLEE_BEFORE_PAREN: Regex = Regex::new(
+        r"([A-Za-z_][\w.:]*)\s*\($"
+    ).expect("CALLEE_BEFORE_PAREN: Invalid regex - this is a compile-time bug");
+
+    // libgcrypt key-generation S-expressions, e.g. "(genkey (rsa (nbits 4:1024)))"
+    static ref GCRYPT_GENKEY_SEXP: Regex = Regex::new(
+        r"^\(\s*genkey\s*\("
+    ).expect("GCRYPT_GENKEY_SEXP: Invalid regex - this is a compile-time bug");
+
+    // Identifiers that may name a constant, including dotted ones (`Config.ALG`)
+    static ref IDENTIFIER: Regex = Regex::new(
+        r"[A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)*"
+    ).expect("IDENTIFIER: Invalid regex - this is a compile-time bug");
 }
 
-/// Main audit function - analyzes source code for quantum-vulnerable cryptography
-pub fn analyze(source: &str, language: &str) -> Result<AuditResult, AuditError> {
-    // Parse language
-    let lang = parse_language(language)?;
+/// Which rule packs a `Scanner` loads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannerConfig {
+    /// Load the embedded core rule pack
+    pub include_core_rules: bool,
+    /// Additional rule pack files, loaded after the core pack
+    pub rule_pack_paths: Vec<PathBuf>,
+    /// Drop findings with a confidence below this value (0.0-1.0)
+    #[serde(default)]
+    pub min_confidence: f32,
+}
 
-    // Validate source is not empty
-    let trimmed = source.trim();
-    if trimmed.is_empty() {
-        return Err(AuditError::InvalidSource);
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            include_core_rules: true,
+            rule_pack_paths: Vec::new(),
+            min_confidence: 0.0,
+        }
     }
+}
 
-    // Validate source size
-    let source_size = source.len();
-    if source_size > MAX_SOURCE_SIZE {
-        return Err(AuditError::SourceTooLarge(source_size, MAX_SOURCE_SIZE));
-    }
+/// Analyzes source code against a configured set of detection rules
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    patterns: Vec<CryptoPattern>,
+    prefilter: Prefilter,
+    min_confidence: f32,
+    project: Option<Arc<ProjectIndex>>,
+}
 
-    let lines: Vec<&str> = source.lines().collect();
-    let line_count = lines.len();
+static DEFAULT_SCANNER: Lazy<Scanner> = Lazy::new(Scanner::default);
 
-    // Validate line count
-    if line_count > MAX_LINES {
-        return Err(AuditError::TooManyLines(line_count, MAX_LINES));
+impl Default for Scanner {
+    /// Scanner with only the core rule pack
+    fn default() -> Self {
+        Self {
+            patterns: detector::builtin_patterns().to_vec(),
+            prefilter: detector::builtin_prefilter().clone(),
+            min_confidence: 0.0,
+            project: None,
+        }
     }
+}
 
-    let mut result = AuditResult::new(lang, line_count);
+impl Scanner {
+    /// Build a scanner from `config`, loading and validating every rule pack
+    pub fn new(config: &ScannerConfig) -> Result<Self, RulePackError> {
+        let mut scanner = if config.include_core_rules {
+            Self::default()
+        } else {
+            Self {
+                patterns: Vec::new(),
+                prefilter: Prefilter::new(&[]),
+                min_confidence: 0.0,
+                project: None,
+            }
+        };
+        scanner.min_confidence = config.min_confidence;
+
+        for path in &config.rule_pack_paths {
+            scanner.add_rule_pack(&RulePack::from_file(path)?)?;
+        }
 
-    // Scan each line for crypto patterns
-    for (line_idx, line) in lines.iter().enumerate() {
-        let line_num = line_idx + 1;
+        Ok(scanner)
+    }
 
-        // Detect RSA
-        if let Some(vuln) = detect_rsa(line, line_num) {
-            result.add_vulnerability(vuln);
-        }
+    /// Add the rules from `pack`; rule ids must not clash with loaded rules,
+    /// and `supersedes` must name rules in the pack or already loaded
+    pub fn add_rule_pack(&mut self, pack: &RulePack) -> Result<(), RulePackError> {
+        let existing: HashSet<&str> = self.patterns.iter().map(|p| p.id.as_str()).collect();
+        let compiled = pack.compile_with(&existing)?;
 
-        // Detect ECDSA
-        if let Some(vuln) = detect_ecdsa(line, line_num) {
-            result.add_vulnerability(vuln);
+        if let Some(dup) = compiled.iter().find(|p| existing.contains(p.id.as_str())) {
+            return Err(RulePackError::DuplicateRuleId(dup.id.clone()));
         }
 
-        // Detect ECDH
-        if let Some(vuln) = detect_ecdh(line, line_num) {
-            result.add_vulnerability(vuln);
-        }
+        self.patterns.extend(compiled);
+        self.prefilter = Prefilter::new(&self.patterns);
+        Ok(())
+    }
 
-        // Detect DSA
-        if let Some(vuln) = detect_dsa(line, line_num) {
-            result.add_vulnerability(vuln);
-        }
+    /// Only report findings with at least this confidence (0.0-1.0)
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
 
-        // Detect Diffie-Hellman
-        if let Some(vuln) = detect_diffie_hellman(line, line_num) {
-            result.add_vulnerability(vuln);
-        }
+    /// Resolve constants that a file does not define through `index`, built
+    /// from the rest of the project before analysis
+    pub fn with_project_index(mut self, index: ProjectIndex) -> Self {
+        self.project = Some(Arc::new(index));
+        self
+    }
 
-        // Detect deprecated hash functions
-        if let Some(vuln) = detect_sha1(line, line_num) {
-            result.add_vulnerability(vuln);
+    /// Detection patterns in evaluation order
+    pub fn patterns(&self) -> &[CryptoPattern] {
+        &self.patterns
+    }
+
+    /// Analyze source code for quantum-vulnerable cryptography; a `language`
+    /// of `"auto"` detects it from the source
+    pub fn analyze(&self, source: &str, language: &str) -> Result<AuditResult, AuditError> {
+        // Parse language
+        let lang = parse_language(language, source)?;
+
+        // Validate source is not empty
+        let trimmed = source.trim();
+        if trimmed.is_empty() {
+            return Err(AuditError::InvalidSource);
         }
 
-        if let Some(vuln) = detect_md5(line, line_num) {
-            result.add_vulnerability(vuln);
+        // Validate source size
+        let source_size = source.len();
+        if source_size > MAX_SOURCE_SIZE {
+            return Err(AuditError::SourceTooLarge(source_size, MAX_SOURCE_SIZE));
         }
 
-        // Detect deprecated ciphers
-        if let Some(vuln) = detect_des(line, line_num) {
-            result.add_vulnerability(vuln);
+        let lines: Vec<&str> = source.lines().collect();
+        let line_count = lines.len();
+
+        // Validate line count
+        if line_count > MAX_LINES {
+            return Err(AuditError::TooManyLines(line_count, MAX_LINES));
         }
 
-        if let Some(vuln) = detect_triple_des(line, line_num) {
-            result.add_vulnerability(vuln);
+        let mut result = AuditResult::new(lang, line_count);
+
+        // Command-line arguments are often quoted, and JOSE algorithm names and
+        // JWK members are string literals, so tool invocations, JWKs and rules
+        // that set `match_string_literals` see the source with only comments blanked
+        let lexed = LexedSource::new(source, lang);
+        let literal_source = lexed.scan_text(|span| span.kind == SpanKind::StringLiteral);
+        let parsed = parser::parse_code(source, &literal_source, lang).ok();
+
+        // Blank out comments, docstrings and string literals that are not algorithm
+        // identifiers passed to a crypto API, so prose mentioning "RSA" is ignored
+        let api_arguments = parsed.as_ref().map(ApiArguments::new);
+        let scan_source = lexed.scan_text(|span| {
+            span.kind == SpanKind::StringLiteral
+                && is_crypto_api_argument(source, span, api_arguments.as_ref())
+        });
+        let line_starts = line_start_offsets(source);
+        let crypto_import = confidence::has_crypto_import(source, &lexed, lang);
+        let mut symbols = parsed
+            .as_ref()
+            .map(SymbolTable::from_parsed)
+            .unwrap_or_default();
+        let scopes = parsed
+            .as_ref()
+            .map(|parsed| ScopeIndex::new(&lexed, parsed))
+            .unwrap_or_default();
+        if let Some(project) = &self.project {
+            symbols = symbols.with_project(Arc::clone(project));
         }
 
-        if let Some(vuln) = detect_rc4(line, line_num) {
-            result.add_vulnerability(vuln);
+        let scan_lines: Vec<&str> = scan_source.lines().collect();
+        let literal_lines: Vec<&str> = literal_source.lines().collect();
+
+        let web_keys = jose::scan(&literal_source);
+
+        // Scan each logical statement for crypto patterns
+        for statement in statement_ranges(&scan_lines, &literal_lines, lang) {
+            let (first, last) = (statement.start, statement.end - 1);
+            let statement_start = line_starts[first].0;
+            let statement_end = line_starts[last].0 + scan_lines[last].len();
+            let scan_statement = &scan_source[statement_start..statement_end];
+            let literal_statement = &literal_source[statement_start..statement_end];
+
+            if literal_statement.trim().is_empty() {
+                continue;
+            }
+
+            let line_num = first + 1;
+            let mut candidates = detector::detect_statement(
+                &self.patterns,
+                &self.prefilter,
+                lang,
+                scan_statement,
+                literal_statement,
+                line_num,
+            );
+            if !symbols.is_empty() {
+                candidates.extend(self.resolved_algorithms(
+                    &symbols,
+                    lang,
+                    scan_statement,
+                    line_num,
+                ));
+            }
+            if lang.is_jvm()
+                && let Some(found) = self.aes_key_generator(
+                    &symbols,
+                    scan_statement,
+                    &scan_lines,
+                    scopes.function_lines(line_num),
+                    line_num,
+                )
+            {
+                candidates.push(found);
+            }
+            if lang == Language::Shell {
+                candidates.retain(|vuln| !commands::is_command_rule(&vuln.rule_id));
+                candidates.extend(commands::detect(
+                    &self.patterns,
+                    literal_statement,
+                    line_num,
+                    &|name| symbols.resolve(name, line_num),
+                ));
+            }
+
+            // A JWK's `alg` member names the algorithm already reported for its
+            // key, unless it is a worse choice (`RSA1_5` on an RSA key)
+            let statement_bytes = statement_start..statement_end;
+            for key in web_keys
+                .iter()
+                .filter(|key| statement_bytes.contains(&key.start))
+            {
+                let Some(found) =
+                    key.finding(&self.patterns, literal_statement, statement_start, line_num)
+                else {
+                    continue;
+                };
+                candidates.retain(|vuln| {
+                    vuln.crypto_type != found.crypto_type
+                        || vuln.severity > found.severity
+                        || !key
+                            .object
+                            .contains(&(statement_start + vuln.span.byte_start))
+                });
+                candidates.push(found);
+            }
+
+            // Post-quantum names contain classical ones ("ML-DSA", "X25519MLKEM768"),
+            // so those matches belong to the inventory rather than vulnerabilities.
+            // Findings about how the algorithm is configured still stand. Group
+            // lists are usually strings (`{"groups": ["X25519MLKEM768"]}`), so
+            // the inventory reads them from the text with only comments blanked,
+            // skipping package paths such as Go's `"crypto/mlkem"` import.
+            let mut quantum_safe = pqc::detect(literal_statement, line_num, lang);
+            quantum_safe.retain(|qs| !literal_statement[..qs.span.byte_start].ends_with('/'));
+            candidates.retain(|vuln| {
+                !vuln.crypto_type.is_algorithm()
+                    || !quantum_safe.iter().any(|qs| {
+                        qs.span.byte_start < vuln.span.byte_end
+                            && vuln.span.byte_start < qs.span.byte_end
+                    })
+            });
+
+            // The physical line a statement-relative offset falls on, and its column
+            let locate = |relative: usize| {
+                let offset = statement_start + relative;
+                let line_idx = statement
+                    .clone()
+                    .rev()
+                    .find(|&idx| line_starts[idx].0 <= offset)
+                    .unwrap_or(first);
+                (line_idx, offset - line_starts[line_idx].0)
+            };
+            // A statement-relative span as offsets into the file. Blanked regions
+            // keep their byte length but not their characters, so the span is
+            // recounted against the original line.
+            let absolute = |span: SourceSpan| {
+                let (line_idx, column) = locate(span.byte_start);
+                let line = lines[line_idx];
+                let (byte_start, char_start) = line_starts[line_idx];
+                let len = span.byte_end - span.byte_start;
+                SourceSpan::from_byte_range(line, column, (column + len).min(line.len()))
+                    .shifted(byte_start, char_start)
+            };
+
+            for mut found in quantum_safe {
+                let (line_idx, column) = locate(found.span.byte_start);
+                let line = lines[line_idx];
+                found.line = line_idx + 1;
+                found.column = column;
+                found.span = absolute(found.span);
+                found.context = line.trim().to_string();
+                found.scope = scopes.enclosing(found.line);
+                result.add_quantum_safe(found);
+            }
+
+            for mut vuln in detector::resolve_overlaps(&self.patterns, candidates) {
+                // Report the physical line and column the match starts on
+                let (line_idx, column) = locate(vuln.column);
+                let line = lines[line_idx];
+                vuln.line = line_idx + 1;
+                vuln.scope = scopes.enclosing(vuln.line);
+
+                if vuln.key_size.is_none() && !symbols.is_empty() {
+                    self.resolve_key_size(&symbols, &mut vuln, scan_statement);
+                }
+                if vuln.key_size.is_none() && lang.is_jvm() {
+                    self.initialized_key_size(
+                        &symbols,
+                        &mut vuln,
+                        scan_statement,
+                        &scan_lines,
+                        scopes.function_lines(line_num),
+                        line_num,
+                    );
+                }
+                vuln.security_level = strength::security_level(&vuln.crypto_type, vuln.key_size);
+
+                vuln.span = absolute(vuln.span);
+                for suppressed in &mut vuln.suppressed {
+                    suppressed.span = absolute(suppressed.span);
+                }
+                vuln.context = line.trim().to_string();
+                vuln.span_kind = lexed.kind_at(vuln.span.byte_start);
+                if lang.is_jvm() {
+                    vuln.explanation.transformation = jca_transformation(&vuln, &lexed, source);
+                }
+                confidence::assess(&mut vuln, scan_statement, lang, crypto_import);
+                vuln.column = column;
+                if vuln.crypto_type == CryptoType::WeakRandom {
+                    let function = scopes.function_lines(vuln.line);
+                    randomness::escalate(&mut vuln, &scan_lines, function, lang);
+                }
+                if vuln.confidence < self.min_confidence {
+                    continue;
+                }
+                result.add_vulnerability(vuln);
+            }
         }
-    }
 
-    // Calculate overall risk score
-    result.calculate_risk_score();
+        // Calculate overall risk score
+        result.calculate_risk_score();
 
-    // Generate recommendations
-    result.generate_recommendations();
+        // Generate recommendations
+        result.generate_recommendations();
 
-    Ok(result)
-}
+        Ok(result)
+    }
 
-/// Parse language string to enum
-fn parse_language(lang: &str) -> Result<Language, AuditError> {
-    Language::from_string(lang).ok_or_else(|| AuditError::UnsupportedLanguage(lang.to_string()))
-}
+    /// Findings for identifiers passed to a crypto API that resolve to an
+    /// algorithm name, e.g. `hashlib.new(alg)` after `alg = "MD5"`. Spans cover
+    /// the identifier; the rules matched the resolved value in its place.
+    fn resolved_algorithms(
+        &self,
+        symbols: &SymbolTable,
+        language: Language,
+        scan_line: &str,
+        line_num: usize,
+    ) -> Vec<Vulnerability> {
+        let mut findings = Vec::new();
+
+        for ident in IDENTIFIER.find_iter(scan_line) {
+            let before = scan_line[..ident.start()].trim_end();
+            let after = scan_line[ident.end()..].trim_start();
+            let whole_argument = (before.ends_with('(') || before.ends_with(','))
+                && (after.starts_with(')') || after.starts_with(','));
+            if !whole_argument || !in_crypto_api_call(before) {
+                continue;
+            }
+
+            let Some(resolution) = symbols.resolve(ident.as_str(), line_num) else {
+                continue;
+            };
+            let ConstantValue::String(value) = &resolution.value else {
+                continue;
+            };
+            if !ALGORITHM_IDENTIFIER.is_match(value) {
+                continue;
+            }
+
+            // Put the value in place as a string literal, so rules that match the
+            // call around it (`Cipher.getInstance("...")`) see it too, and keep the
+            // matches that fall inside it
+            let literal = format!("\"{}\"", value);
+            let substituted = format!(
+                "{}{}{}",
+                &scan_line[..ident.start()],
+                literal,
+                &scan_line[ident.end()..]
+            );
+            let inside = ident.start()..ident.start() + literal.len();
+            let matches = detector::detect_line(&self.patterns, language, &substituted, line_num)
+                .into_iter()
+                .filter(|v| inside.start <= v.span.byte_start && v.span.byte_end <= inside.end)
+                .collect();
+            for mut vuln in detector::resolve_overlaps(&self.patterns, matches) {
+                vuln.column = ident.start();
+                vuln.span = SourceSpan::from_byte_range(scan_line, ident.start(), ident.end());
+                // Matches inside the substituted value stand for the identifier too
+                for suppressed in &mut vuln.suppressed {
+                    suppressed.span = vuln.span;
+                }
+                vuln.explanation.algorithm_resolution = Some(resolution.clone());
+                findings.push(vuln);
+            }
+        }
 
-/// Detect RSA usage and determine risk
-fn detect_rsa(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !RSA_PATTERN.is_match(line) && !RSA_KEYGEN.is_match(line) {
-        return None;
+        findings
     }
 
-    // Try to extract key size
-    let key_size = RSA_KEY_SIZE
-        .captures(line)
-        .and_then(|cap| cap.get(1))
-        .and_then(|m| u32::from_str(m.as_str()).ok());
-
-    let (severity, risk_score, message) = match key_size {
-        Some(size) if size < 2048 => (
-            Severity::Critical,
-            100,
-            format!(
-                "RSA with {}-bit key is critically vulnerable to quantum attacks",
-                size
-            ),
-        ),
-        Some(size) if size < 4096 => (
-            Severity::High,
-            85,
-            format!(
-                "RSA with {}-bit key will be vulnerable to quantum computers",
-                size
-            ),
-        ),
-        Some(size) => (
-            Severity::High,
-            80,
-            format!(
-                "RSA with {}-bit key is quantum-vulnerable (Shor's algorithm)",
-                size
-            ),
-        ),
-        None => (
-            Severity::High,
-            85,
-            "RSA detected - vulnerable to quantum attacks via Shor's algorithm".to_string(),
-        ),
-    };
+    /// Finding for `KeyGenerator.getInstance("AES")` in `scan_statement`, whose
+    /// key size only shows in the `init(...)` call on the generator later in
+    /// `function`; AES-256 generators are not reported
+    fn aes_key_generator(
+        &self,
+        symbols: &SymbolTable,
+        scan_statement: &str,
+        scan_lines: &[&str],
+        function: Option<(usize, usize)>,
+        line_num: usize,
+    ) -> Option<Vulnerability> {
+        let name = jca::aes_key_generator(scan_statement)?;
+        let pattern = self
+            .patterns
+            .iter()
+            .find(|p| p.crypto_type == CryptoType::Aes && p.languages.is_empty())?;
+        let (bits, resolution) =
+            generator_key_size(symbols, scan_statement, scan_lines, function, line_num)?;
+        let graded = pattern
+            .key_size_tiers
+            .iter()
+            .any(|t| t.below.is_none_or(|below| bits < below));
+        if !graded {
+            return None;
+        }
 
-    let column = line
-        .find(|c: char| c.to_lowercase().any(|c| c == 'r'))
-        .unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::Rsa,
-        severity,
-        risk_score,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message,
-        recommendation:
-            "Replace with CRYSTALS-Dilithium (signatures) or CRYSTALS-Kyber (encryption)"
-                .to_string(),
-        key_size,
-    })
-}
+        let mut vuln = pattern.finding(scan_statement, line_num, name.start, name.end, Some(bits));
+        vuln.explanation.key_size_resolution = resolution;
+        Some(vuln)
+    }
 
-/// Detect ECDSA usage
-fn detect_ecdsa(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !ECDSA_PATTERN.is_match(line) {
-        return None;
+    /// Fill in a key size from a constant that follows the match, e.g.
+    /// `generateKeyPair('rsa', { modulusLength: BITS })` after `const BITS = 1024`
+    fn resolve_key_size(&self, symbols: &SymbolTable, vuln: &mut Vulnerability, scan_line: &str) {
+        let Some(pattern) = self
+            .patterns
+            .iter()
+            .find(|p| p.id == vuln.rule_id && p.key_size.is_some())
+        else {
+            return;
+        };
+
+        let tail = &scan_line[vuln.column..];
+        for ident in IDENTIFIER.find_iter(tail) {
+            let Some(resolution) = symbols.resolve(ident.as_str(), vuln.line) else {
+                continue;
+            };
+            let ConstantValue::Integer(value) = resolution.value else {
+                continue;
+            };
+            let Ok(bits) = u32::try_from(value) else {
+                continue;
+            };
+
+            // Substitute the value and let the rule's own key-size pattern decide
+            let substituted = format!("{}{}{}", &tail[..ident.start()], bits, &tail[ident.end()..]);
+            if pattern.key_size_in(&substituted) == Some(bits) {
+                pattern.apply_key_size(vuln, bits);
+                vuln.explanation.key_size_resolution = Some(resolution);
+                return;
+            }
+        }
     }
 
-    let column = line
-        .find(|c: char| c.to_uppercase().any(|c| "ECDSA".contains(c)))
-        .unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::Ecdsa,
-        severity: Severity::High,
-        risk_score: 85,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "ECDSA (Elliptic Curve Digital Signature Algorithm) is quantum-vulnerable"
-            .to_string(),
-        recommendation: "Replace with CRYSTALS-Dilithium or SPHINCS+ for post-quantum signatures"
-            .to_string(),
-        key_size: None,
-    })
+    /// Fill in the key size of a JCA generator created in the finding's
+    /// statement from its `initialize(...)` call, e.g.
+    /// `kpg.initialize(CryptoConstants.KEY_SIZE)` after
+    /// `KeyPairGenerator.getInstance(CryptoConstants.ALGORITHM)`
+    fn initialized_key_size(
+        &self,
+        symbols: &SymbolTable,
+        vuln: &mut Vulnerability,
+        scan_statement: &str,
+        scan_lines: &[&str],
+        function: Option<(usize, usize)>,
+        line_num: usize,
+    ) {
+        let Some(pattern) = self
+            .patterns
+            .iter()
+            .find(|p| p.id == vuln.rule_id && p.key_size.is_some())
+        else {
+            return;
+        };
+        let Some((bits, resolution)) =
+            generator_key_size(symbols, scan_statement, scan_lines, function, line_num)
+        else {
+            return;
+        };
+        pattern.apply_key_size(vuln, bits);
+        vuln.explanation.key_size_resolution = resolution;
+    }
 }
 
-/// Detect ECDH usage
-fn detect_ecdh(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !ECDH_PATTERN.is_match(line) {
-        return None;
+/// Key size a JCA generator created in `scan_statement` is initialized with,
+/// from its `initialize(...)` or `init(...)` call in the rest of `function`
+/// (the rest of the file outside functions): a literal, or a constant resolved
+/// through `symbols`
+fn generator_key_size(
+    symbols: &SymbolTable,
+    scan_statement: &str,
+    scan_lines: &[&str],
+    function: Option<(usize, usize)>,
+    line_num: usize,
+) -> Option<(u32, Option<Resolution>)> {
+    let variable = jca::generator_variable(scan_statement)?;
+    let last = function.map_or(scan_lines.len(), |(_, last)| last);
+    let (init_line, argument) = jca::generator_init(scan_lines, variable, line_num, last)?;
+
+    if let Ok(bits) = argument.parse() {
+        return Some((bits, None));
     }
+    let resolution = symbols.resolve(argument, init_line)?;
+    let ConstantValue::Integer(value) = resolution.value else {
+        return None;
+    };
+    Some((u32::try_from(value).ok()?, Some(resolution)))
+}
 
-    let column = line
-        .find(|c: char| c.to_uppercase().any(|c| "ECDH".contains(c)))
-        .unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::Ecdh,
-        severity: Severity::High,
-        risk_score: 85,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "ECDH (Elliptic Curve Diffie-Hellman) is quantum-vulnerable".to_string(),
-        recommendation: "Replace with CRYSTALS-Kyber or NTRU for quantum-safe key exchange"
-            .to_string(),
-        key_size: None,
-    })
+/// Main audit function - analyzes source code for quantum-vulnerable cryptography
+/// using the core rule pack
+pub fn analyze(source: &str, language: &str) -> Result<AuditResult, AuditError> {
+    DEFAULT_SCANNER.analyze(source, language)
 }
 
-/// Detect DSA usage
-fn detect_dsa(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !DSA_PATTERN.is_match(line) {
-        return None;
+/// Parse language string to enum; `"auto"` detects it from the source
+fn parse_language(lang: &str, source: &str) -> Result<Language, AuditError> {
+    if lang.eq_ignore_ascii_case("auto") {
+        return Language::detect(Path::new(""), source)
+            .map(|detection| detection.language)
+            .ok_or(AuditError::LanguageNotDetected);
     }
-
-    let column = line.find("DSA").or_else(|| line.find("dsa")).unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::Dsa,
-        severity: Severity::High,
-        risk_score: 90,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "DSA (Digital Signature Algorithm) is quantum-vulnerable".to_string(),
-        recommendation: "Replace with CRYSTALS-Dilithium for post-quantum digital signatures"
-            .to_string(),
-        key_size: None,
-    })
+    Language::from_string(lang).ok_or_else(|| AuditError::UnsupportedLanguage(lang.to_string()))
 }
 
-/// Detect Diffie-Hellman usage
-fn detect_diffie_hellman(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !DH_PATTERN.is_match(line) {
-        return None;
+/// Byte and character offsets at which each line of `source` starts
+fn line_start_offsets(source: &str) -> Vec<(usize, usize)> {
+    let mut offsets = vec![(0, 0)];
+    let mut chars = 0;
+    for (byte, ch) in source.char_indices() {
+        chars += 1;
+        if ch == '\n' {
+            offsets.push((byte + 1, chars));
+        }
     }
-
-    let column = line.find("diffie").or_else(|| line.find("DH")).unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::DiffieHellman,
-        severity: Severity::High,
-        risk_score: 85,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "Diffie-Hellman key exchange is quantum-vulnerable".to_string(),
-        recommendation:
-            "Replace with CRYSTALS-Kyber or FrodoKEM for quantum-safe key encapsulation".to_string(),
-        key_size: None,
-    })
+    offsets
 }
 
-/// Detect SHA-1 usage
-fn detect_sha1(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !SHA1_PATTERN.is_match(line) {
-        return None;
-    }
+/// Group lines into logical statements. A line is joined with the ones after
+/// it while its brackets are still open, it ends in a Python or shell `\\`, it
+/// ends in an assignment or binary operator, or the next line continues a
+/// method chain, so calls formatted over several lines are scanned as one
+/// statement. `literal_lines` are the same lines with only comments blanked,
+/// so a trailing string literal is not mistaken for a dangling `=`.
+fn statement_ranges(
+    scan_lines: &[&str],
+    literal_lines: &[&str],
+    language: Language,
+) -> Vec<Range<usize>> {
+    // Braces delimit blocks everywhere except Python, where they are literals,
+    // and Objective-C `@{...}` dictionary literals
+    let braces_nest = language == Language::Python;
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut literal_braces = 0usize;
+
+    for (idx, line) in scan_lines.iter().enumerate() {
+        let mut prev = None;
+        for ch in line.chars() {
+            match ch {
+                '(' | '[' => depth += 1,
+                '{' if braces_nest => depth += 1,
+                '{' if language == Language::ObjectiveC && prev == Some('@') => {
+                    depth += 1;
+                    literal_braces += 1;
+                }
+                ')' | ']' => depth = depth.saturating_sub(1),
+                '}' if braces_nest => depth = depth.saturating_sub(1),
+                '}' if literal_braces > 0 => {
+                    depth = depth.saturating_sub(1);
+                    literal_braces -= 1;
+                }
+                _ => {}
+            }
+            prev = Some(ch);
+        }
 
-    let column = line.find("SHA1").or_else(|| line.find("sha1")).unwrap_or(0);
+        let line_continuation = matches!(language, Language::Python | Language::Shell);
+        let continues = depth > 0
+            || (line_continuation && line.trim_end().ends_with('\\'))
+            || (!line_continuation
+                && (literal_lines
+                    .get(idx)
+                    .is_some_and(|l| ends_with_operator(l))
+                    || scan_lines
+                        .get(idx + 1)
+                        .is_some_and(|next| next.trim_start().starts_with('.'))));
+        if !continues || idx + 1 - start >= MAX_STATEMENT_LINES {
+            ranges.push(start..idx + 1);
+            start = idx + 1;
+            depth = 0;
+            literal_braces = 0;
+        }
+    }
 
-    Some(Vulnerability {
-        crypto_type: CryptoType::Sha1,
-        severity: Severity::Critical,
-        risk_score: 95,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "SHA-1 is cryptographically broken and should not be used".to_string(),
-        recommendation: "Replace with SHA-256, SHA-384, or SHA-512".to_string(),
-        key_size: None,
-    })
+    if start < scan_lines.len() {
+        ranges.push(start..scan_lines.len());
+    }
+    ranges
 }
 
-/// Detect MD5 usage
-fn detect_md5(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !MD5_PATTERN.is_match(line) {
-        return None;
+/// Whether `line` ends in an assignment or binary operator, leaving the
+/// expression to the next line: `KeyPairGenerator kpg =`, `a &&`. Increments,
+/// `.*` imports and `/`, which may close a regex literal, do not count.
+fn ends_with_operator(line: &str) -> bool {
+    let line = line.trim_end();
+    if line.ends_with("++") || line.ends_with("--") || line.ends_with(".*") {
+        return false;
     }
+    line.ends_with(['=', '+', '-', '*', '%', '^'])
+        || ["=>", "&&", "||"].iter().any(|op| line.ends_with(op))
+}
 
-    let column = line.find("MD5").or_else(|| line.find("md5")).unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::Md5,
-        severity: Severity::Critical,
-        risk_score: 100,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "MD5 is cryptographically broken and must not be used".to_string(),
-        recommendation: "Replace with SHA-256 or SHA-3".to_string(),
-        key_size: None,
-    })
+/// String literals the parser saw passed to a crypto API call, e.g. `'md5'` in
+/// `crypto.createHash('md5')`, and array or object literals passed to one, e.g.
+/// `openssl_pkey_new(['private_key_bits' => 1024])`
+#[derive(Default)]
+struct ApiArguments {
+    literals: HashSet<usize>,
+    containers: Vec<Range<usize>>,
 }
 
-/// Detect DES usage
-fn detect_des(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !DES_PATTERN.is_match(line) {
-        return None;
+impl ApiArguments {
+    fn new(parsed: &ParsedSource) -> Self {
+        let mut arguments = ApiArguments::default();
+        let args = parsed
+            .function_calls
+            .iter()
+            .filter(|call| CRYPTO_API_CALLEE.is_match(&call.name))
+            .flat_map(|call| &call.args);
+        for arg in args {
+            match arg.value {
+                ArgumentValue::Literal(ConstantValue::String(_)) => {
+                    arguments.literals.insert(arg.offset);
+                }
+                ArgumentValue::Expression if arg.text.starts_with(['[', '{']) => {
+                    arguments
+                        .containers
+                        .push(arg.offset..arg.offset + arg.text.len());
+                }
+                _ => {}
+            }
+        }
+        arguments
     }
 
-    let column = line.find("DES").or_else(|| line.find("des")).unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::Des,
-        severity: Severity::Critical,
-        risk_score: 95,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "DES is obsolete and cryptographically weak".to_string(),
-        recommendation: "Replace with AES-256 or ChaCha20".to_string(),
-        key_size: None,
-    })
+    /// Whether the string literal starting at `start` is one of the arguments
+    fn contains(&self, start: usize) -> bool {
+        self.literals.contains(&start) || self.containers.iter().any(|range| range.contains(&start))
+    }
 }
 
-/// Detect 3DES usage
-fn detect_triple_des(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !TRIPLE_DES_PATTERN.is_match(line) {
-        return None;
+/// Whether a string literal is an algorithm identifier passed directly to a
+/// crypto API call, e.g. `createHash('md5')` or `getInstance("RSA", provider)`, or a
+/// libgcrypt `(genkey ...)` S-expression. The calls come from `api_arguments`;
+/// sources the parser rejects fall back to reading the text before the literal.
+fn is_crypto_api_argument(source: &str, span: &Span, api_arguments: Option<&ApiArguments>) -> bool {
+    let literal = &source[span.start..span.end];
+    let content = literal
+        .trim_start_matches(['@', '`', '"', '\''])
+        .trim_end_matches(['`', '"', '\'']);
+    if GCRYPT_GENKEY_SEXP.is_match(content) {
+        return true;
+    }
+    if !ALGORITHM_IDENTIFIER.is_match(content) {
+        return false;
     }
 
-    let column = line
-        .find("3DES")
-        .or_else(|| line.find("TripleDES"))
-        .unwrap_or(0);
-
-    Some(Vulnerability {
-        crypto_type: CryptoType::TripleDes,
-        severity: Severity::High,
-        risk_score: 80,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "3DES (Triple DES) is deprecated and should be replaced".to_string(),
-        recommendation: "Replace with AES-256 or ChaCha20-Poly1305".to_string(),
-        key_size: None,
-    })
-}
-
-/// Detect RC4 usage
-fn detect_rc4(line: &str, line_num: usize) -> Option<Vulnerability> {
-    if !RC4_PATTERN.is_match(line) {
-        return None;
+    match api_arguments {
+        Some(arguments) => arguments.contains(span.start),
+        None => in_crypto_api_call(&source[..span.start]),
     }
+}
 
-    let column = line.find("RC4").or_else(|| line.find("rc4")).unwrap_or(0);
+/// The JCA transformation a finding's algorithm name came from: the string
+/// literal it matched in, or the constant it was resolved from
+fn jca_transformation(
+    vuln: &Vulnerability,
+    lexed: &LexedSource,
+    source: &str,
+) -> Option<Transformation> {
+    let name = match &vuln.explanation.algorithm_resolution {
+        Some(resolution) => match &resolution.value {
+            ConstantValue::String(value) => value.as_str(),
+            _ => return None,
+        },
+        None if vuln.span_kind == SpanKind::StringLiteral => {
+            let span = lexed.span_at(vuln.span.byte_start)?;
+            source[span.start..span.end].trim_matches(['"', '\''])
+        }
+        None => return None,
+    };
+    jca::parse_transformation(name)
+}
 
-    Some(Vulnerability {
-        crypto_type: CryptoType::Rc4,
-        severity: Severity::Critical,
-        risk_score: 95,
-        line: line_num,
-        column,
-        context: line.trim().to_string(),
-        message: "RC4 is cryptographically broken and must not be used".to_string(),
-        recommendation: "Replace with AES-GCM or ChaCha20-Poly1305".to_string(),
-        key_size: None,
-    })
+/// Whether the text ending at an argument is inside the argument list of a
+/// crypto API call
+fn in_crypto_api_call(before: &str) -> bool {
+    // Walk back over earlier arguments to the opening paren of the call
+    let mut depth = 0usize;
+    for (idx, ch) in before.char_indices().rev() {
+        match ch {
+            ')' | ']' | '}' => depth += 1,
+            '(' if depth == 0 => {
+                return CALLEE_BEFORE_PAREN
+                    .captures(&before[..=idx])
+                    .and_then(|caps| caps.get(1))
+                    .is_some_and(|callee| CRYPTO_API_CALLEE.is_match(callee.as_str()));
+            }
+            '(' | '[' | '{' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => return false,
+            // Arguments may start on a new line after `(` or `,`
+            '\n' if depth == 0 && !before[..idx].trim_end().ends_with(['(', ',', '[', '{']) => {
+                return false;
+            }
+            _ => {}
+        }
+    }
+    false
 }
 
 /// Calculate risk score for a crypto type and key size
@@ -448,14 +813,32 @@ pub fn score_vulnerability(crypto_type: &CryptoType, key_size: Option<u32>) -> u
             Some(size) if size < 4096 => 85,  // High
             _ => 80,                          // High (any RSA is quantum-vulnerable)
         },
-        CryptoType::Ecdsa | CryptoType::Ecdh => 85, // High
-        CryptoType::Dsa => 90,                      // High
-        CryptoType::DiffieHellman => 85,            // High
-        CryptoType::Sha1 => 95,                     // Critical (broken)
-        CryptoType::Md5 => 100,                     // Critical (broken)
-        CryptoType::Des => 95,                      // Critical (weak)
-        CryptoType::TripleDes => 80,                // High (deprecated)
-        CryptoType::Rc4 => 95,                      // Critical (broken)
+        CryptoType::Ecdsa | CryptoType::Eddsa | CryptoType::Ecdh => 85, // High
+        CryptoType::Dsa => 90,                                          // High
+        CryptoType::DiffieHellman => 85,                                // High
+        CryptoType::Sha1 => 95,                                         // Critical (broken)
+        CryptoType::Md5 => 100,                                         // Critical (broken)
+        CryptoType::Des => 95,                                          // Critical (weak)
+        CryptoType::TripleDes => 80,                                    // High (deprecated)
+        CryptoType::Rc4 => 95,                                          // Critical (broken)
+        CryptoType::Aes => match key_size {
+            Some(size) if size < 192 => 50, // Medium (64-bit post-quantum)
+            Some(size) if size < 256 => 30, // Low (96-bit post-quantum)
+            _ => 10,
+        },
+        CryptoType::Blowfish => 80, // High (64-bit block)
+        CryptoType::Rc2 => 90,      // Critical (weak)
+        CryptoType::Sha224 => 50,   // Medium (74-bit post-quantum collisions)
+        CryptoType::EcbMode => 75,  // High (leaks plaintext patterns)
+        CryptoType::StaticIv => 75, // High (repeated keystream or ciphertext)
+        CryptoType::Sike => 100,    // Critical (key recovery in hours)
+        CryptoType::Rainbow => 95,  // Critical (key recovery in days)
+        // Medium (pre-standard drafts, not interoperable with FIPS 203-205)
+        CryptoType::Kyber | CryptoType::Dilithium | CryptoType::SphincsPlus => 45,
+        CryptoType::NonHybridKem => 50, // Medium (no classical fallback)
+        CryptoType::WeakRandom => 45,   // Medium (High once it reaches key material)
+        CryptoType::UnsignedJwt => 100, // Critical (tokens can be forged)
+        CryptoType::WeakJwtSecret => 80, // High (secret can be brute-forced from a token)
     }
 }
 
@@ -465,45 +848,18 @@ mod tests {
 
     #[test]
     fn test_parse_language() {
-        assert!(parse_language("rust").is_ok());
-        assert!(parse_language("javascript").is_ok());
-        assert!(parse_language("python").is_ok());
-        assert!(parse_language("unknown").is_err());
-    }
-
-    #[test]
-    fn test_detect_rsa_1024() {
-        let line = "RSA.generate(1024)";
-        let vuln = detect_rsa(line, 1).unwrap();
-        assert_eq!(vuln.crypto_type, CryptoType::Rsa);
-        assert_eq!(vuln.severity, Severity::Critical);
-        assert_eq!(vuln.key_size, Some(1024));
-    }
-
-    #[test]
-    fn test_detect_rsa_2048() {
-        let line = "generateKeyPair('rsa', { modulusLength: 2048 })";
-        let vuln = detect_rsa(line, 1).unwrap();
-        assert_eq!(vuln.crypto_type, CryptoType::Rsa);
-        assert_eq!(vuln.severity, Severity::High);
-        assert_eq!(vuln.key_size, Some(2048));
-    }
-
-    #[test]
-    fn test_detect_ecdsa() {
-        let line = "crypto.createSign('ecdsa-with-SHA256')";
-        let vuln = detect_ecdsa(line, 1).unwrap();
-        assert_eq!(vuln.crypto_type, CryptoType::Ecdsa);
-        assert_eq!(vuln.severity, Severity::High);
-    }
-
-    #[test]
-    fn test_detect_md5() {
-        let line = "hashlib.md5(data).hexdigest()";
-        let vuln = detect_md5(line, 1).unwrap();
-        assert_eq!(vuln.crypto_type, CryptoType::Md5);
-        assert_eq!(vuln.severity, Severity::Critical);
-        assert_eq!(vuln.risk_score, 100);
+        assert!(parse_language("rust", "").is_ok());
+        assert!(parse_language("javascript", "").is_ok());
+        assert!(parse_language("python", "").is_ok());
+        assert!(parse_language("unknown", "").is_err());
+        assert_eq!(
+            parse_language("auto", "<?php\necho 1;\n").unwrap(),
+            Language::Php
+        );
+        assert!(matches!(
+            parse_language("auto", "plain text"),
+            Err(AuditError::LanguageNotDetected)
+        ));
     }
 
     #[test]
@@ -536,6 +892,551 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_comment_mentions_are_suppressed() {
+        let source = r#"
+            // We used to use RSA but migrated to ML-KEM
+            /* MD5 was removed in v2 */
+            let x = 1;
+        "#;
+
+        let result = analyze(source, "rust").unwrap();
+        assert!(result.vulnerabilities.is_empty());
+    }
+
+    #[test]
+    fn test_python_docstring_is_suppressed() {
+        let source = "def digest(data):\n    \"\"\"Replaces the old MD5 helper.\"\"\"\n    return sha256(data)\n";
+
+        let result = analyze(source, "python").unwrap();
+        assert!(result.vulnerabilities.is_empty());
+    }
+
+    #[test]
+    fn test_string_literal_outside_crypto_call_is_suppressed() {
+        let source = r#"
+            println!("MD5 is mentioned here but not used");
+            let label = "ECDSA";
+        "#;
+
+        let result = analyze(source, "rust").unwrap();
+        assert!(result.vulnerabilities.is_empty());
+    }
+
+    #[test]
+    fn test_string_literal_algorithm_argument_is_kept() {
+        let source = "const h = crypto.createHash('md5');";
+
+        let result = analyze(source, "javascript").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.crypto_type, CryptoType::Md5);
+        assert_eq!(vuln.span_kind, SpanKind::StringLiteral);
+        assert_eq!(vuln.context, source);
+    }
+
+    #[test]
+    fn test_code_match_records_code_span() {
+        let result = analyze("digest = hashlib.md5(data)  # legacy", "python").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].span_kind, SpanKind::Code);
+    }
+
+    #[test]
+    fn test_spans_are_absolute_byte_and_char_offsets() {
+        let source = "# résumé\nh = hashlib.md5(a); g = hashlib.md5(b)\n";
+
+        let result = analyze(source, "python").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 2);
+
+        let second_line = source.find('h').unwrap();
+        for (vuln, column) in result.vulnerabilities.iter().zip([12, 32]) {
+            assert_eq!(vuln.line, 2);
+            assert_eq!(vuln.column, column);
+            let span = vuln.span;
+            assert_eq!(span.byte_start, second_line + column);
+            assert_eq!(&source[span.byte_start..span.byte_end], "md5");
+            // "é" is two bytes but one character
+            assert_eq!(span.char_start, span.byte_start - 2);
+            assert_eq!(span.char_end - span.char_start, 3);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_matches_count_once() {
+        // The multi-byte comment shifts byte and character offsets differently
+        let source = "// clé ü\nvar key = ECDsa.Create(ECCurve.NamedCurves.nistP256);";
+        let result = analyze(source, "csharp").unwrap();
+        assert!(
+            result
+                .vulnerabilities
+                .iter()
+                .all(|v| v.crypto_type == CryptoType::Ecdsa)
+        );
+        let winner = result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.suppressed.iter().any(|s| s.rule_id == "dsa"))
+            .expect("expected a finding that suppressed 'dsa'");
+        assert_eq!(winner.line, 2);
+        let dsa = winner
+            .suppressed
+            .iter()
+            .find(|s| s.rule_id == "dsa")
+            .unwrap();
+        let line_start = source.find("var").unwrap();
+        assert_eq!(line_start, 11);
+        assert_eq!(&source[dsa.span.byte_start..dsa.span.byte_end], "Dsa");
+        assert_eq!(
+            (
+                dsa.span.byte_start,
+                dsa.span.byte_end,
+                dsa.span.char_start,
+                dsa.span.char_end
+            ),
+            (23, 26, 21, 24)
+        );
+        assert!(winner.span.byte_start <= dsa.span.byte_start);
+        assert!(dsa.span.byte_end <= winner.span.byte_end);
+        assert_eq!(
+            result.stats.total_vulnerabilities,
+            result.vulnerabilities.len()
+        );
+    }
+
+    #[test]
+    fn test_key_size_resolved_from_constant() {
+        let source =
+            "const BITS = 1024;\ncrypto.generateKeyPair('rsa', { modulusLength: BITS });\n";
+
+        let result = analyze(source, "javascript").unwrap();
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.crypto_type, CryptoType::Rsa);
+        assert_eq!(vuln.key_size, Some(1024));
+        assert_eq!(vuln.severity, Severity::Critical);
+        let resolution = vuln.explanation.key_size_resolution.as_ref().unwrap();
+        assert_eq!(resolution.symbol, "BITS");
+        assert_eq!(resolution.chain[0].line, 1);
+    }
+
+    #[test]
+    fn test_python_key_size_keyword() {
+        let source = "from cryptography.hazmat.primitives.asymmetric import rsa
+KEY_SIZE = 1024
+a = rsa.generate_private_key(public_exponent=65537, key_size=1024)
+b = rsa.generate_private_key(public_exponent=65537, key_size=KEY_SIZE)
+c = rsa.generate_private_key(
+    public_exponent=65537,
+    key_size=2048,
+)
+";
+
+        let result = analyze(source, "python").unwrap();
+        let key_size = |line: usize| {
+            result
+                .vulnerabilities
+                .iter()
+                .find(|v| v.line == line && v.crypto_type == CryptoType::Rsa)
+                .and_then(|v| v.key_size)
+        };
+        assert_eq!(key_size(3), Some(1024));
+        assert_eq!(key_size(4), Some(1024));
+        assert_eq!(key_size(5), Some(2048));
+
+        let constant = result.vulnerabilities.iter().find(|v| v.line == 4).unwrap();
+        assert_eq!(constant.severity, Severity::Critical);
+        let resolution = constant.explanation.key_size_resolution.as_ref().unwrap();
+        assert_eq!(resolution.symbol, "KEY_SIZE");
+    }
+
+    #[test]
+    fn test_algorithm_resolved_from_assignment() {
+        let source = "import hashlib\nalg = \"MD5\"\nh = hashlib.new(alg)\n";
+
+        let result = analyze(source, "python").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.crypto_type, CryptoType::Md5);
+        assert_eq!(vuln.line, 3);
+        assert_eq!(&source[vuln.span.byte_start..vuln.span.byte_end], "alg");
+        assert_eq!(vuln.explanation.matched_text, "MD5");
+        assert_eq!(
+            vuln.explanation
+                .algorithm_resolution
+                .as_ref()
+                .unwrap()
+                .to_string(),
+            "alg (line 2) = \"MD5\""
+        );
+        assert!(
+            vuln.explanation
+                .signals
+                .iter()
+                .any(|s| s.kind == SignalKind::AlgorithmArgument)
+        );
+
+        // The same identifier outside a crypto API call is not a finding
+        let result = analyze("alg = \"MD5\"\nprint(alg)\n", "python").unwrap();
+        assert!(result.vulnerabilities.is_empty());
+    }
+
+    #[test]
+    fn test_resolved_identifier_replaces_fragment_match() {
+        let source = "String RSA_ALG = \"RSA\";\nCipher c = Cipher.getInstance(RSA_ALG);\n";
+
+        let result = analyze(source, "java").unwrap();
+        let on_call: Vec<_> = result
+            .vulnerabilities
+            .iter()
+            .filter(|v| v.line == 2)
+            .collect();
+        assert_eq!(on_call.len(), 1);
+        assert!(on_call[0].explanation.algorithm_resolution.is_some());
+        let rsa = &on_call[0].suppressed[0];
+        assert_eq!(rsa.rule_id, "rsa");
+        let offset = source.find("(RSA_ALG)").unwrap() + 1;
+        assert_eq!((rsa.span.byte_start, rsa.span.char_start), (offset, offset));
+    }
+
+    #[test]
+    fn test_multi_line_call_keeps_algorithm_and_key_size() {
+        let source = "const { privateKey } = crypto.generateKeyPairSync(\n  'rsa',\n  { modulusLength: 1024 }\n);\n";
+
+        let result = analyze(source, "javascript").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.crypto_type, CryptoType::Rsa);
+        assert_eq!(vuln.key_size, Some(1024));
+        assert_eq!((vuln.line, vuln.column), (2, 3));
+        assert_eq!(&source[vuln.span.byte_start..vuln.span.byte_end], "rsa");
+        assert_eq!(vuln.context, "'rsa',");
+        assert_eq!(vuln.span_kind, SpanKind::StringLiteral);
+    }
+
+    #[test]
+    fn test_multi_line_java_call() {
+        let source = "Cipher cipher = Cipher\n    .getInstance(\n        \"DES/ECB/PKCS5Padding\");\nint x = 1;\n";
+
+        let result = analyze(source, "java").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 2);
+        assert_eq!(result.vulnerabilities[0].crypto_type, CryptoType::Des);
+        assert_eq!(result.vulnerabilities[0].line, 3);
+        assert_eq!(result.vulnerabilities[0].column, 9);
+        assert_eq!(result.vulnerabilities[1].crypto_type, CryptoType::EcbMode);
+        assert_eq!(result.vulnerabilities[1].column, 13);
+    }
+
+    #[test]
+    fn test_statement_ranges() {
+        let lines = [
+            "fn main() {",
+            "    let k = Rsa::generate(",
+            "        2048,",
+            "    );",
+            "    q.a()",
+            "        .b();",
+            "}",
+        ];
+        assert_eq!(
+            statement_ranges(&lines, &lines, Language::Rust),
+            vec![0..1, 1..4, 4..6, 6..7]
+        );
+
+        let lines = ["cfg = {", "    'alg': 'md5',", "}", "x = a + \\", "    b"];
+        assert_eq!(
+            statement_ranges(&lines, &lines, Language::Python),
+            vec![0..3, 3..5]
+        );
+
+        let lines = [
+            "- (void)generate {",
+            "    NSDictionary *attrs = @{",
+            "        (id)kSecAttrKeySizeInBits: @1024,",
+            "    };",
+            "}",
+        ];
+        assert_eq!(
+            statement_ranges(&lines, &lines, Language::ObjectiveC),
+            vec![0..1, 1..4, 4..5]
+        );
+
+        // `. ./env.sh` sources a file rather than continuing a chain
+        let lines = ["openssl req -new \\", "    -newkey rsa:2048", ". ./env.sh"];
+        assert_eq!(
+            statement_ranges(&lines, &lines, Language::Shell),
+            vec![0..2, 2..3]
+        );
+
+        // A dangling `=` or operator joins the next line; a string literal
+        // blanked out of the scanned line does not dangle
+        let lines = [
+            "KeyPairGenerator kpg =",
+            "    KeyPairGenerator.getInstance(\"RSA\");",
+            "kpg.initialize(1024);",
+            "boolean weak = legacy &&",
+            "    bits < 2048;",
+            "String name = \"RSA\";",
+            "i++",
+            "import java.util.*",
+        ];
+        let scan_lines = lines.map(|line| line.replace("\"RSA\"", "     "));
+        let scan_lines: Vec<&str> = scan_lines.iter().map(String::as_str).collect();
+        assert_eq!(
+            statement_ranges(&scan_lines, &lines, Language::Java),
+            vec![0..2, 2..3, 3..5, 5..6, 6..7, 7..8]
+        );
+
+        let source = "class Keys {\n    KeyPair weak() throws Exception {\n        KeyPairGenerator kpg =\n            KeyPairGenerator.getInstance(\"RSA\");\n        kpg.initialize(1024);\n        return kpg.generateKeyPair();\n    }\n}\n";
+        let result = analyze(source, "java").unwrap();
+        let rsa = result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.crypto_type == CryptoType::Rsa)
+            .unwrap();
+        assert_eq!(rsa.line, 4);
+        assert_eq!(rsa.key_size, Some(1024));
+    }
+
+    #[test]
+    fn test_is_crypto_api_argument() {
+        let check = |source: &str, literal: &str| {
+            let start = source.find(literal).unwrap();
+            let span = Span {
+                kind: SpanKind::StringLiteral,
+                start,
+                end: start + literal.len(),
+            };
+            // The parsed calls and the textual fallback agree
+            let parsed = parser::parse_file(source, "python").unwrap();
+            let arguments = ApiArguments::new(&parsed);
+            let from_calls = is_crypto_api_argument(source, &span, Some(&arguments));
+            assert_eq!(from_calls, is_crypto_api_argument(source, &span, None));
+            from_calls
+        };
+
+        assert!(check(
+            r#"Cipher.getInstance("DES/ECB/PKCS5Padding")"#,
+            r#""DES/ECB/PKCS5Padding""#
+        ));
+        assert!(check("hashlib.new('md5', data)", "'md5'"));
+        assert!(check("sign(key, f(x), 'sha1')", "'sha1'"));
+        assert!(!check("log('md5')", "'md5'"));
+        assert!(!check(r#"x = "RSA""#, r#""RSA""#));
+        assert!(check("hashlib.new(\n    'md5',\n    data)", "'md5'"));
+        assert!(check("generate({'alg': 'rsa'})", "'rsa'"));
+        assert!(!check("log(x)\n'md5'", "'md5'"));
+        assert!(!check(
+            r#"createHash("md5 is broken")"#,
+            r#""md5 is broken""#
+        ));
+    }
+
+    #[test]
+    fn test_api_arguments_come_from_parsed_calls() {
+        // A comment inside the argument list, and a Ruby call without parentheses
+        let source = "const h = crypto.createHash( // pick the digest (legacy)\n  'md5');\n";
+        let result = analyze(source, "javascript").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].crypto_type, CryptoType::Md5);
+        assert_eq!(result.vulnerabilities[0].line, 2);
+
+        let result = analyze("d = OpenSSL::Digest.new \"md5\"\n", "ruby").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].crypto_type, CryptoType::Md5);
+
+        // The same literal outside a crypto call stays blanked
+        let result = analyze("logger.info \"md5\"\n", "ruby").unwrap();
+        assert!(result.vulnerabilities.is_empty());
+    }
+
+    #[test]
+    fn test_calls_inside_string_interpolation() {
+        let cases = [
+            (
+                "python",
+                "import hashlib\nprint(f\"{hashlib.md5(x).hexdigest()}\")\n",
+            ),
+            (
+                "javascript",
+                "const crypto = require('crypto');\nconst s = `${crypto.createHash('md5')}`;\n",
+            ),
+            (
+                "csharp",
+                "using System.Security.Cryptography;\nvar s = $\"{SHA1.Create()}\";\n",
+            ),
+            (
+                "ruby",
+                "require 'digest'\nputs \"#{Digest::MD5.hexdigest(x)}\"\n",
+            ),
+            (
+                "swift",
+                "import CryptoKit\nprint(\"\\(Insecure.MD5.hash(data: d))\")\n",
+            ),
+        ];
+        for (language, source) in cases {
+            let result = analyze(source, language).unwrap();
+            let lines: Vec<usize> = result.vulnerabilities.iter().map(|v| v.line).collect();
+            assert_eq!(lines, [2], "{}: {:?}", language, result.vulnerabilities);
+        }
+
+        // A quote inside a regex literal does not hide the next statement
+        let source = "const re = /rsa\"/;\nconst h = crypto.createHash('sha1');\n";
+        let result = analyze(source, "javascript").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].crypto_type, CryptoType::Sha1);
+    }
+
+    const CORP_PACK: &str = r#"{
+        "metadata": {"name": "corp", "version": "1.0.0"},
+        "rules": [{
+            "id": "corp-legacy-sign",
+            "tokens": ["corp_crypto::legacy_sign"],
+            "languages": ["rust"],
+            "crypto_type": "RSA",
+            "severity": "high",
+            "risk_score": 85,
+            "message": "corp_crypto::legacy_sign uses RSA-2048",
+            "recommendation": "Migrate to corp_crypto::pq_sign",
+            "cwe": "CWE-327"
+        }]
+    }"#;
+
+    #[test]
+    fn test_scanner_with_custom_rule_pack() {
+        let mut scanner = Scanner::default();
+        scanner
+            .add_rule_pack(&RulePack::from_json(CORP_PACK).unwrap())
+            .unwrap();
+
+        let source = "let sig = corp_crypto::legacy_sign(&key, msg);";
+        let result = scanner.analyze(source, "rust").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        assert_eq!(result.vulnerabilities[0].rule_id, "corp-legacy-sign");
+
+        // Language-scoped rule does not fire elsewhere, and the core rules still do
+        assert!(
+            scanner
+                .analyze(source, "python")
+                .unwrap()
+                .vulnerabilities
+                .is_empty()
+        );
+        assert!(
+            !analyze(source, "rust")
+                .unwrap()
+                .vulnerabilities
+                .iter()
+                .any(|v| v.rule_id == "corp-legacy-sign")
+        );
+    }
+
+    #[test]
+    fn test_scanner_rejects_rule_id_clash() {
+        let mut scanner = Scanner::default();
+        let pack = RulePack::from_json(CORP_PACK).unwrap();
+        scanner.add_rule_pack(&pack).unwrap();
+        assert!(matches!(
+            scanner.add_rule_pack(&pack),
+            Err(RulePackError::DuplicateRuleId(_))
+        ));
+    }
+
+    #[test]
+    fn test_scanner_min_confidence() {
+        let source = "import hashlib\ndigest = hashlib.md5(data)\nmd5_total = 3\n";
+
+        let all = Scanner::default().analyze(source, "python").unwrap();
+        assert_eq!(all.vulnerabilities.len(), 2);
+
+        let confident = Scanner::default()
+            .with_min_confidence(0.8)
+            .analyze(source, "python")
+            .unwrap();
+        assert_eq!(confident.vulnerabilities.len(), 1);
+        assert_eq!(confident.vulnerabilities[0].line, 2);
+        assert_eq!(confident.stats.total_vulnerabilities, 1);
+    }
+
+    #[test]
+    fn test_scanner_without_core_rules() {
+        let config = ScannerConfig {
+            include_core_rules: false,
+            ..ScannerConfig::default()
+        };
+        let scanner = Scanner::new(&config).unwrap();
+        assert!(scanner.patterns().is_empty());
+        let result = scanner.analyze("hashlib.md5(data)", "python").unwrap();
+        assert!(result.vulnerabilities.is_empty());
+
+        // Without the core rules a pack cannot supersede one of them
+        let pack = CORP_PACK.replace(
+            r#""cwe": "CWE-327""#,
+            r#""cwe": "CWE-327", "supersedes": ["rsa"]"#,
+        );
+        let pack = RulePack::from_json(&pack).unwrap();
+        assert!(matches!(
+            scanner.clone().add_rule_pack(&pack),
+            Err(RulePackError::UnknownSupersededRule { .. })
+        ));
+        assert!(Scanner::default().add_rule_pack(&pack).is_ok());
+    }
+
+    #[test]
+    fn test_command_rules_come_from_rule_pack() {
+        let script = "ssh-keygen -t dsa -f deploy_key\nopenssl genrsa -out ca.key 1024\n";
+        let result = analyze(script, "shell").unwrap();
+        let found: Vec<(&str, Severity)> = result
+            .vulnerabilities
+            .iter()
+            .map(|v| (v.rule_id.as_str(), v.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("ssh-keygen-dsa", Severity::High),
+                ("openssl-cli-rsa", Severity::Critical)
+            ]
+        );
+
+        // Only the command rules a pack declares are reported
+        let config = ScannerConfig {
+            include_core_rules: false,
+            ..ScannerConfig::default()
+        };
+        let mut scanner = Scanner::new(&config).unwrap();
+        assert!(
+            scanner
+                .analyze(script, "shell")
+                .unwrap()
+                .vulnerabilities
+                .is_empty()
+        );
+
+        let pack = r#"{
+            "metadata": {"name": "ops", "version": "1.0.0"},
+            "rules": [{
+                "id": "ssh-keygen-dsa",
+                "pattern": "\\b(ssh-keygen)\\b",
+                "languages": ["shell"],
+                "crypto_type": "DSA",
+                "severity": "critical",
+                "risk_score": 100,
+                "message": "DSA deploy keys are banned",
+                "recommendation": "Use the ops key service"
+            }]
+        }"#;
+        scanner
+            .add_rule_pack(&RulePack::from_json(pack).unwrap())
+            .unwrap();
+        let result = scanner.analyze(script, "shell").unwrap();
+        assert_eq!(result.vulnerabilities.len(), 1);
+        let dsa = &result.vulnerabilities[0];
+        assert_eq!(dsa.rule_id, "ssh-keygen-dsa");
+        assert_eq!(dsa.severity, Severity::Critical);
+        assert_eq!(dsa.message, "DSA deploy keys are banned");
+        assert_eq!(dsa.explanation.command.as_deref(), Some("ssh-keygen"));
+    }
+
     #[test]
     fn test_clean_code() {
         let source = r#"
@@ -2,8 +2,8 @@
 // Command-line interface for scanning directories for cryptographic vulnerabilities
 
 use pqc_scanner::{
-    Language, analyze, export_oscal_json, export_sc13_json, generate_oscal_json,
-    generate_sc13_report,
+    Language, ProjectIndex, Scanner, ScannerConfig, export_oscal_json, export_sc13_json,
+    generate_oscal_json, generate_sc13_report,
 };
 use std::env;
 use std::fs;
@@ -13,12 +13,17 @@ use std::process;
 // File size limits
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 
+// Files whose language is only a weak guess from their content are not scanned
+const MIN_LANGUAGE_CONFIDENCE: f32 = 0.6;
+
 struct ScanOptions {
     target_path: String,
     report_dir: String,
     report_name: Option<String>,
     is_repo_url: bool,
     cleanup_after_scan: bool,
+    rule_packs: Vec<PathBuf>,
+    min_confidence: f32,
 }
 
 fn main() {
@@ -79,6 +84,8 @@ fn parse_scan_args(args: &[String]) -> Result<ScanOptions, String> {
     let mut report_dir = "reports".to_string();
     let mut report_name = None;
     let mut cleanup_after_scan = true;
+    let mut rule_packs = Vec::new();
+    let mut min_confidence = 0.0;
     let mut i = 0;
 
     while i < args.len() {
@@ -97,6 +104,28 @@ fn parse_scan_args(args: &[String]) -> Result<ScanOptions, String> {
                 report_name = Some(args[i + 1].clone());
                 i += 2;
             }
+            "--rules" => {
+                if i + 1 >= args.len() {
+                    return Err("--rules requires a value".to_string());
+                }
+                rule_packs.push(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--min-confidence" => {
+                if i + 1 >= args.len() {
+                    return Err("--min-confidence requires a value".to_string());
+                }
+                min_confidence = match args[i + 1].parse::<f32>() {
+                    Ok(value) if (0.0..=1.0).contains(&value) => value,
+                    _ => {
+                        return Err(format!(
+                            "--min-confidence must be between 0.0 and 1.0, got {}",
+                            args[i + 1]
+                        ));
+                    }
+                };
+                i += 2;
+            }
             "--keep-clone" => {
                 cleanup_after_scan = false;
                 i += 1;
@@ -124,6 +153,8 @@ fn parse_scan_args(args: &[String]) -> Result<ScanOptions, String> {
                 report_name,
                 is_repo_url,
                 cleanup_after_scan,
+                rule_packs,
+                min_confidence,
             })
         }
         None => Err("Missing target path or repository URL".to_string()),
@@ -178,12 +209,16 @@ fn print_usage(program: &str) {
     eprintln!("  --report-dir <dir>     Output directory for reports (default: reports)");
     eprintln!("  --report-name <name>   Base name for report files (default: directory/repo name)");
     eprintln!("  --keep-clone           Keep cloned repository after scanning (default: cleanup)");
+    eprintln!("  --rules <file>         Load an additional JSON rule pack (repeatable)");
+    eprintln!("  --min-confidence <n>   Only report findings with confidence >= n (0.0-1.0)");
     eprintln!();
     eprintln!("Examples:");
     eprintln!("  {} --version", program);
     eprintln!("  {} --help", program);
     eprintln!("  {} scan samples/vulnerable-app-1", program);
     eprintln!("  {} scan https://github.com/digininja/DVWA.git", program);
+    eprintln!("  {} scan src --rules corp-rules.json", program);
+    eprintln!("  {} scan src --min-confidence 0.8", program);
     eprintln!(
         "  {} scan https://github.com/org/repo.git --report-name my-audit --keep-clone",
         program
@@ -191,6 +226,14 @@ fn print_usage(program: &str) {
 }
 
 fn scan_directory(mut options: ScanOptions) -> Result<(), String> {
+    // Load rule packs up front so a bad pack fails before any cloning or scanning
+    let scanner = Scanner::new(&ScannerConfig {
+        rule_pack_paths: options.rule_packs.clone(),
+        min_confidence: options.min_confidence,
+        ..ScannerConfig::default()
+    })
+    .map_err(|e| e.to_string())?;
+
     // If it's a repository URL, clone it first
     let cloned_path = if options.is_repo_url {
         println!("=== Cloning Repository ===");
@@ -235,6 +278,13 @@ fn scan_directory(mut options: ScanOptions) -> Result<(), String> {
     println!("=== PQC Scanner ===");
     println!("Scanning: {}\n", options.target_path);
 
+    // Index exported constants first so files can resolve each other's
+    let mut index = ProjectIndex::default();
+    if target.is_dir() {
+        index_dir_recursive(&target, &mut index)?;
+    }
+    let scanner = scanner.with_project_index(index);
+
     let mut total_files = 0;
     let mut total_vulnerabilities = 0;
     let mut critical_count = 0;
@@ -244,6 +294,7 @@ fn scan_directory(mut options: ScanOptions) -> Result<(), String> {
     // Scan all supported files in directory
     if target.is_dir() {
         scan_dir_recursive(
+            &scanner,
             &target,
             &mut total_files,
             &mut total_vulnerabilities,
@@ -393,6 +444,7 @@ fn extract_repo_name(url: &str) -> Option<String> {
 }
 
 fn scan_dir_recursive(
+    scanner: &Scanner,
     dir: &Path,
     total_files: &mut usize,
     total_vulns: &mut usize,
@@ -407,16 +459,20 @@ fn scan_dir_recursive(
         let path = entry.path();
 
         if path.is_dir() {
-            // Skip node_modules and common directories
-            if let Some(name) = path.file_name() {
-                let name_str = name.to_string_lossy();
-                if name_str == "node_modules" || name_str == ".git" || name_str == "target" {
-                    continue;
-                }
+            if is_skipped_dir(&path) {
+                continue;
             }
-            scan_dir_recursive(&path, total_files, total_vulns, critical, high, results)?;
+            scan_dir_recursive(
+                scanner,
+                &path,
+                total_files,
+                total_vulns,
+                critical,
+                high,
+                results,
+            )?;
         } else if path.is_file()
-            && let Some(result) = scan_file(&path)?
+            && let Some(result) = scan_file(scanner, &path)?
         {
             *total_files += 1;
             *total_vulns += result.stats.total_vulnerabilities;
@@ -430,16 +486,29 @@ fn scan_dir_recursive(
                     "  Critical: {}, High: {}",
                     result.stats.critical_count, result.stats.high_count
                 );
+                if !result.quantum_safe.is_empty() {
+                    println!(
+                        "  Post-quantum: {}",
+                        result.quantum_safe_algorithms().join(", ")
+                    );
+                }
 
                 // Show first few vulnerabilities
                 for (i, vuln) in result.vulnerabilities.iter().take(3).enumerate() {
                     println!(
-                        "    {}. [{:?}] {} (line {})",
+                        "    {}. [{:?}] {} (line {}, confidence {:.2})",
                         i + 1,
                         vuln.severity,
                         vuln.crypto_type,
-                        vuln.line
+                        vuln.line,
+                        vuln.confidence
                     );
+                    if !vuln.scope.is_empty() {
+                        println!("       in {}", vuln.scope);
+                    }
+                    if let Some(level) = vuln.security_level {
+                        println!("       security: {}", level);
+                    }
                 }
 
                 if result.vulnerabilities.len() > 3 {
@@ -454,70 +523,113 @@ fn scan_dir_recursive(
     Ok(())
 }
 
-fn scan_file(path: &Path) -> Result<Option<pqc_scanner::AuditResult>, String> {
-    // Determine language from file extension
-    let language = match path.extension().and_then(|s| s.to_str()) {
-        Some("js") => Some(Language::JavaScript),
-        Some("ts") => Some(Language::TypeScript),
-        Some("py") => Some(Language::Python),
-        Some("rs") => Some(Language::Rust),
-        Some("java") => Some(Language::Java),
-        Some("go") => Some(Language::Go),
-        Some("cpp") | Some("cc") | Some("cxx") => Some(Language::Cpp),
-        Some("cs") => Some(Language::Csharp),
-        _ => None,
-    };
+/// Skip node_modules and common build/VCS directories
+fn is_skipped_dir(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        let name_str = name.to_string_lossy();
+        name_str == "node_modules" || name_str == ".git" || name_str == "target"
+    })
+}
 
-    if let Some(lang) = language {
-        // Check file size before reading
-        let metadata = fs::metadata(path)
-            .map_err(|e| format!("Failed to get metadata for {}: {}", path.display(), e))?;
+/// Collect exported constants from every supported file under `dir`
+fn index_dir_recursive(dir: &Path, index: &mut ProjectIndex) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Cannot read directory: {}", e))?;
+
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
 
-        let file_size = metadata.len();
-        if file_size > MAX_FILE_SIZE {
+        if path.is_dir() {
+            if !is_skipped_dir(&path) {
+                index_dir_recursive(&path, index)?;
+            }
+        } else if path.is_file()
+            && let Ok(Some((lang, content))) = read_source(&path)
+        {
+            index.add_file(&path.display().to_string(), &content, lang);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a source file within the size limit and detect its language. Files
+/// without an extension are read too, so scripts such as `bin/deploy` are
+/// recognized by their `#!` line or modeline.
+fn read_source(path: &Path) -> Result<Option<(Language, String)>, String> {
+    let known = Language::from_path(path).is_some();
+    // Any `.json` file may be a JWK Set; its content decides
+    let json = path.extension().is_some_and(|ext| ext == "json");
+    if !known && !json && path.extension().is_some() {
+        return Ok(None);
+    }
+
+    // Check file size before reading
+    let metadata = fs::metadata(path)
+        .map_err(|e| format!("Failed to get metadata for {}: {}", path.display(), e))?;
+
+    let file_size = metadata.len();
+    if file_size > MAX_FILE_SIZE {
+        if known {
             eprintln!(
                 "Warning: Skipping {} - file too large ({} bytes, max {})",
                 path.display(),
                 file_size,
                 MAX_FILE_SIZE
             );
-            return Ok(None);
         }
+        return Ok(None);
+    }
 
-        if file_size == 0 {
-            // Skip empty files
-            return Ok(None);
-        }
+    if file_size == 0 {
+        // Skip empty files
+        return Ok(None);
+    }
 
-        // Read file content
-        let content = fs::read_to_string(path).map_err(|e| {
+    // Read file content
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        // Extensionless files are often executables
+        Err(e) if !known && e.kind() == std::io::ErrorKind::InvalidData => return Ok(None),
+        Err(e) => {
             // Check if error is due to binary file
             if e.kind() == std::io::ErrorKind::InvalidData {
-                return format!("Skipping {} - appears to be binary", path.display());
+                return Err(format!(
+                    "Skipping {} - appears to be binary",
+                    path.display()
+                ));
             }
-            format!("Failed to read {}: {}", path.display(), e)
-        })?;
-
-        // Analyze content
-        let lang_str = match lang {
-            Language::JavaScript => "javascript",
-            Language::TypeScript => "typescript",
-            Language::Python => "python",
-            Language::Rust => "rust",
-            Language::Java => "java",
-            Language::Go => "go",
-            Language::Cpp => "cpp",
-            Language::Csharp => "csharp",
-        };
+            return Err(format!("Failed to read {}: {}", path.display(), e));
+        }
+    };
 
-        match analyze(&content, lang_str) {
-            Ok(result) => Ok(Some(result)),
-            Err(e) => {
-                eprintln!("Warning: Failed to analyze {}: {}", path.display(), e);
-                Ok(None)
+    let detection = Language::detect(path, &content)
+        .filter(|detection| detection.confidence >= MIN_LANGUAGE_CONFIDENCE)
+        .filter(|detection| !json || detection.language == Language::Json);
+    Ok(detection.map(|detection| (detection.language, content)))
+}
+
+fn scan_file(scanner: &Scanner, path: &Path) -> Result<Option<pqc_scanner::AuditResult>, String> {
+    let Some((lang, content)) = read_source(path)? else {
+        return Ok(None);
+    };
+
+    // Analyze content
+    match scanner.analyze(&content, &lang.to_string()) {
+        Ok(mut result) => {
+            // A Python module is its file
+            if lang == Language::Python {
+                let module = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned());
+                for vuln in &mut result.vulnerabilities {
+                    vuln.scope.module = vuln.scope.module.take().or_else(|| module.clone());
+                }
             }
+            Ok(Some(result))
+        }
+        Err(e) => {
+            eprintln!("Warning: Failed to analyze {}: {}", path.display(), e);
+            Ok(None)
         }
-    } else {
-        Ok(None)
     }
 }
@@ -220,6 +220,7 @@ fn generate_canadian_summary(
         match vuln.crypto_type {
             CryptoType::Rsa
             | CryptoType::Ecdsa
+            | CryptoType::Eddsa
             | CryptoType::Ecdh
             | CryptoType::Dsa
             | CryptoType::DiffieHellman => {
@@ -231,11 +232,26 @@ fn generate_canadian_summary(
             | CryptoType::Md5
             | CryptoType::Des
             | CryptoType::TripleDes
-            | CryptoType::Rc4 => {
+            | CryptoType::Rc4
+            | CryptoType::Rc2
+            | CryptoType::Blowfish
+            | CryptoType::Sike
+            | CryptoType::Rainbow => {
                 if !deprecated.contains(&crypto_name) {
                     deprecated.push(crypto_name.clone());
                 }
             }
+            CryptoType::Aes
+            | CryptoType::Sha224
+            | CryptoType::EcbMode
+            | CryptoType::StaticIv
+            | CryptoType::Kyber
+            | CryptoType::Dilithium
+            | CryptoType::SphincsPlus
+            | CryptoType::NonHybridKem
+            | CryptoType::WeakRandom
+            | CryptoType::UnsignedJwt
+            | CryptoType::WeakJwtSecret => {}
         }
 
         // Categorize by CCCS status
@@ -300,6 +316,8 @@ fn generate_canadian_summary(
         quantum_vulnerable_algorithms: quantum_vulnerable,
         deprecated_algorithms: deprecated,
         weak_key_sizes: weak_keys,
+        quantum_safe_algorithms: audit_result.quantum_safe_algorithms(),
+        migration_progress: audit_result.migration_progress(),
         compliance_score,
         risk_score: audit_result.risk_score,
         cccs_approved_algorithms: cccs_approved,
@@ -336,24 +354,17 @@ fn calculate_canadian_compliance_score(
     // Weak key sizes (-15 points each)
     score = score.saturating_sub(weak_keys.len() as u32 * 15);
 
-    // Quantum-vulnerable algorithms (-10 points per unique type)
+    // Quantum-vulnerable algorithms (-10 points per unique type), reduced by
+    // the share of public-key uses already migrated to post-quantum algorithms
     let quantum_count = audit_result
         .vulnerabilities
         .iter()
-        .filter(|v| {
-            matches!(
-                v.crypto_type,
-                CryptoType::Rsa
-                    | CryptoType::Ecdsa
-                    | CryptoType::Ecdh
-                    | CryptoType::Dsa
-                    | CryptoType::DiffieHellman
-            )
-        })
+        .filter(|v| v.crypto_type.is_quantum_vulnerable())
         .map(|v| v.crypto_type.to_string())
         .collect::<std::collections::HashSet<_>>()
-        .len();
-    score = score.saturating_sub(quantum_count as u32 * 10);
+        .len() as u32;
+    let unmigrated = 100 - audit_result.migration_progress().unwrap_or(0);
+    score = score.saturating_sub(quantum_count * 10 * unmigrated / 100);
 
     score
 }
@@ -453,6 +464,7 @@ fn generate_canadian_findings(
                 line: vuln.line,
                 column: vuln.column,
                 snippet: vuln.context.clone(),
+                scope: vuln.scope.clone(),
             };
 
             let evidence_data = serde_json::json!({
@@ -756,27 +768,47 @@ mod tests {
 
         result.add_vulnerability(Vulnerability {
             crypto_type: CryptoType::Rsa,
+            category: CryptoCategory::AsymmetricEncryption,
             severity: Severity::High,
             risk_score: 85,
             line: 10,
             column: 5,
+            span: SourceSpan::default(),
             context: "const rsa = crypto.generateKeyPair('rsa', { modulusLength: 2048 })"
                 .to_string(),
+            scope: EnclosingScope::default(),
             message: "RSA detected - quantum vulnerable".to_string(),
             recommendation: "Replace with CRYSTALS-Kyber".to_string(),
             key_size: Some(2048),
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: String::new(),
+            cwe: None,
+            confidence: 1.0,
+            explanation: Explanation::default(),
+            suppressed: Vec::new(),
         });
 
         result.add_vulnerability(Vulnerability {
             crypto_type: CryptoType::Md5,
+            category: CryptoCategory::Hash,
             severity: Severity::Critical,
             risk_score: 100,
             line: 15,
             column: 10,
+            span: SourceSpan::default(),
             context: "const hash = crypto.createHash('md5')".to_string(),
+            scope: EnclosingScope::default(),
             message: "MD5 is cryptographically broken".to_string(),
             recommendation: "Replace with SHA-256".to_string(),
             key_size: None,
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: String::new(),
+            cwe: None,
+            confidence: 1.0,
+            explanation: Explanation::default(),
+            suppressed: Vec::new(),
         });
 
         result.calculate_risk_score();
@@ -0,0 +1,886 @@
+//! Crypto Command-Line Tools
+//!
+//! Keys, parameters and encrypted files are often produced by `openssl` and
+//! `ssh-keygen` in shell scripts, Dockerfile `RUN` lines and Makefile recipes
+//! rather than through a library API. This module splits a shell statement
+//! into simple commands, parses the arguments of those tools and reports the
+//! algorithm, key size and cipher each invocation selects. Each tool and
+//! algorithm has its own rule in the rule pack (`openssl-cli-rsa`,
+//! `ssh-keygen-dsa`, ...), which supplies severity, messages and key-size tiers.
+
+use crate::detector::CryptoPattern;
+use crate::types::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // `$NAME`, `${NAME}` and Makefile `$(NAME)`
+    static ref VARIABLE: Regex = Regex::new(r"\$(?:\{(\w+)\}|\((\w+)\)|(\w+))")
+        .expect("VARIABLE: Invalid regex - this is a compile-time bug");
+
+    // Redirections such as `>`, `2>>` or `<in.pem`
+    static ref REDIRECTION: Regex = Regex::new(r"^\d*(?:[<>]+&?|&>)")
+        .expect("REDIRECTION: Invalid regex - this is a compile-time bug");
+
+    // `prime256v1`, `secp384r1`, `P-521`, `brainpoolP256r1`
+    static ref CURVE_BITS: Regex = Regex::new(r"(?i)(?:prime|secp|sect|p-?|brainpoolp)(\d{3})")
+        .expect("CURVE_BITS: Invalid regex - this is a compile-time bug");
+}
+
+/// `openssl` options that take a value, so the value is not a positional argument
+const OPENSSL_VALUE_OPTIONS: &[&str] = &[
+    "-addext",
+    "-algorithm",
+    "-CA",
+    "-CAform",
+    "-CAkey",
+    "-CAserial",
+    "-caname",
+    "-certfile",
+    "-certpbe",
+    "-cipher",
+    "-config",
+    "-days",
+    "-digest",
+    "-engine",
+    "-extensions",
+    "-extfile",
+    "-in",
+    "-inform",
+    "-iter",
+    "-iv",
+    "-K",
+    "-k",
+    "-key",
+    "-keyform",
+    "-keyout",
+    "-keypbe",
+    "-kfile",
+    "-mac",
+    "-macalg",
+    "-macopt",
+    "-md",
+    "-name",
+    "-newkey",
+    "-out",
+    "-outform",
+    "-paramfile",
+    "-pass",
+    "-passin",
+    "-passout",
+    "-pkeyopt",
+    "-primes",
+    "-propquery",
+    "-provider",
+    "-provider-path",
+    "-rand",
+    "-reqexts",
+    "-S",
+    "-section",
+    "-set_serial",
+    "-sigopt",
+    "-signature",
+    "-signkey",
+    "-subj",
+    "-v1",
+    "-v2",
+    "-vfyopt",
+    "-writerand",
+];
+
+/// `ssh-keygen` options that take a value
+const SSH_KEYGEN_VALUE_OPTIONS: &str = "abCDEFfGIJjKMmNnOPrSsTtVwYZz";
+
+/// `ssh-keygen` options that select a mode other than key generation
+const SSH_KEYGEN_MODE_OPTIONS: &str = "ABcDeFGHikLlMpQRrsTYy";
+
+/// Rule ids for the algorithms an `openssl` invocation can select
+const OPENSSL_RULES: &[(CryptoType, &str)] = &[
+    (CryptoType::Rsa, "openssl-cli-rsa"),
+    (CryptoType::Ecdsa, "openssl-cli-ec"),
+    (CryptoType::Eddsa, "openssl-cli-eddsa"),
+    (CryptoType::Ecdh, "openssl-cli-x25519"),
+    (CryptoType::Dsa, "openssl-cli-dsa"),
+    (CryptoType::DiffieHellman, "openssl-cli-dh"),
+    (CryptoType::Des, "openssl-cli-des"),
+    (CryptoType::TripleDes, "openssl-cli-3des"),
+    (CryptoType::Rc4, "openssl-cli-rc4"),
+    (CryptoType::Rc2, "openssl-cli-rc2"),
+    (CryptoType::Blowfish, "openssl-cli-blowfish"),
+    (CryptoType::Aes, "openssl-cli-aes"),
+    (CryptoType::EcbMode, "openssl-cli-ecb"),
+    (CryptoType::StaticIv, "openssl-cli-static-iv"),
+    (CryptoType::Md5, "openssl-cli-md5"),
+    (CryptoType::Sha1, "openssl-cli-sha1"),
+    (CryptoType::Sha224, "openssl-cli-sha224"),
+];
+
+/// Rule ids for the key types `ssh-keygen` can generate
+const SSH_KEYGEN_RULES: &[(CryptoType, &str)] = &[
+    (CryptoType::Rsa, "ssh-keygen-rsa"),
+    (CryptoType::Ecdsa, "ssh-keygen-ecdsa"),
+    (CryptoType::Eddsa, "ssh-keygen-ed25519"),
+    (CryptoType::Dsa, "ssh-keygen-dsa"),
+];
+
+/// Words that may precede the command word of a simple command
+const COMMAND_PREFIXES: &[&str] = &[
+    "RUN", "sudo", "exec", "time", "nohup", "command", "env", "xargs", "then", "do", "else", "if",
+    "while", "until", "!", "{",
+];
+
+/// Reserved words that close a compound command rather than run one
+const BLOCK_ENDS: &[&str] = &["}", "fi", "done", "esac"];
+
+/// A shell word with quotes removed and variables expanded
+#[derive(Debug, Clone)]
+struct Word {
+    text: String,
+    /// Byte range of the word as written
+    start: usize,
+    end: usize,
+    /// Variable the word was expanded from
+    resolution: Option<Resolution>,
+}
+
+/// An algorithm selected by a command-line tool invocation
+#[derive(Debug, Clone)]
+pub struct Invocation {
+    /// Tool and subcommand, e.g. `openssl genrsa` or `ssh-keygen`
+    pub command: String,
+    pub crypto_type: CryptoType,
+    /// Byte range of the arguments that select the algorithm
+    pub start: usize,
+    pub end: usize,
+    pub key_size: Option<u32>,
+    /// Variable the algorithm name was resolved from
+    pub algorithm_resolution: Option<Resolution>,
+    /// Variable the key size was resolved from
+    pub key_size_resolution: Option<Resolution>,
+}
+
+impl Invocation {
+    fn new(command: &str, crypto_type: CryptoType, words: &[&Word]) -> Self {
+        Self {
+            command: command.to_string(),
+            crypto_type,
+            start: words.iter().map(|w| w.start).min().unwrap_or(0),
+            end: words.iter().map(|w| w.end).max().unwrap_or(0),
+            key_size: None,
+            algorithm_resolution: words.iter().find_map(|w| w.resolution.clone()),
+            key_size_resolution: None,
+        }
+    }
+
+    /// Rule pack id for the tool and the algorithm it selects
+    fn rule_id(&self) -> Option<&'static str> {
+        let rules = if self.command == "ssh-keygen" {
+            SSH_KEYGEN_RULES
+        } else {
+            OPENSSL_RULES
+        };
+        rules
+            .iter()
+            .find(|(crypto_type, _)| *crypto_type == self.crypto_type)
+            .map(|(_, id)| *id)
+    }
+
+    fn with_key_size(mut self, bits: Option<(u32, Option<Resolution>)>) -> Self {
+        if let Some((bits, resolution)) = bits {
+            self.key_size = Some(bits);
+            self.key_size_resolution = resolution;
+        }
+        self
+    }
+}
+
+/// Findings for the `openssl` and `ssh-keygen` invocations in one shell
+/// statement. `resolve` looks up shell, Dockerfile and Makefile variables.
+/// Invocations whose rule is not in `patterns` are not reported.
+pub fn detect(
+    patterns: &[CryptoPattern],
+    statement: &str,
+    line_num: usize,
+    resolve: &dyn Fn(&str) -> Option<Resolution>,
+) -> Vec<Vulnerability> {
+    scan(statement, resolve)
+        .into_iter()
+        .filter_map(|invocation| {
+            let rule_id = invocation.rule_id()?;
+            let pattern = patterns.iter().find(|p| p.id == rule_id)?;
+            let mut vuln = pattern.finding(
+                statement,
+                line_num,
+                invocation.start,
+                invocation.end,
+                invocation.key_size,
+            );
+            vuln.explanation.command = Some(invocation.command);
+            vuln.explanation.algorithm_resolution = invocation.algorithm_resolution;
+            vuln.explanation.key_size_resolution = invocation.key_size_resolution;
+            Some(vuln)
+        })
+        .collect()
+}
+
+/// Whether `rule_id` names a rule reported by `detect`. Those rules only name
+/// the tool in their pattern, so their plain pattern matches are dropped.
+pub fn is_command_rule(rule_id: &str) -> bool {
+    OPENSSL_RULES
+        .iter()
+        .chain(SSH_KEYGEN_RULES)
+        .any(|(_, id)| *id == rule_id)
+}
+
+/// Parse the crypto tool invocations in one shell statement
+pub fn scan(statement: &str, resolve: &dyn Fn(&str) -> Option<Resolution>) -> Vec<Invocation> {
+    simple_commands(statement)
+        .into_iter()
+        .flat_map(|words| {
+            let words: Vec<Word> = words.into_iter().map(|w| expand(w, resolve)).collect();
+            let Some((tool, args)) = command_word(&words) else {
+                return Vec::new();
+            };
+            match tool {
+                "openssl" => openssl(args),
+                "ssh-keygen" => ssh_keygen(&words[words.len() - args.len() - 1], args),
+                _ => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// A command in a line as the parser sees it: its name, byte offset and
+/// arguments, each as unquoted text, as written and by byte offset
+pub(crate) struct CommandLine {
+    pub name: String,
+    pub column: usize,
+    pub args: Vec<(String, String, usize)>,
+}
+
+/// Commands in a line, for the parser
+pub(crate) fn command_lines(line: &str) -> Vec<CommandLine> {
+    simple_commands(line)
+        .iter()
+        .filter_map(|words| {
+            let (name, args) = command_word(words)?;
+            let word = &words[words.len() - args.len() - 1];
+            Some(CommandLine {
+                name: name.to_string(),
+                column: word.start,
+                args: args
+                    .iter()
+                    .map(|arg| {
+                        (
+                            arg.text.clone(),
+                            line[arg.start..arg.end].to_string(),
+                            arg.start,
+                        )
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Split a statement into simple commands at `;`, `&&`, `||`, `|`, subshells
+/// and unescaped newlines, honouring quotes and `\` line continuations
+fn simple_commands(text: &str) -> Vec<Vec<Word>> {
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    let mut current: Option<Word> = None;
+    let mut quote = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some('"'), '\\') if next.is_some_and(|n| "$`\"\\\n".contains(n)) => {
+                chars.next();
+                if next != Some('\n') {
+                    begin(&mut current, i).text.extend(next);
+                }
+            }
+            (Some(_), _) => begin(&mut current, i).text.push(c),
+            (None, '\'' | '"') => {
+                begin(&mut current, i);
+                quote = Some(c);
+            }
+            (None, '\\') => match chars.next() {
+                Some((_, '\n')) | None => {}
+                Some((_, escaped)) => begin(&mut current, i).text.push(escaped),
+            },
+            // Makefile `$(VAR)` references stay in the word; `$(command)` starts a new command
+            (None, '$')
+                if next == Some('(')
+                    && text[i + 2..].find(')').is_some_and(|close| {
+                        close > 0
+                            && text[i + 2..i + 2 + close]
+                                .bytes()
+                                .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+                    }) =>
+            {
+                let close = i + 2 + text[i + 2..].find(')').unwrap_or(0);
+                begin(&mut current, i).text.push_str(&text[i..=close]);
+                while chars.next_if(|&(j, _)| j <= close).is_some() {}
+            }
+            (None, '$') if next == Some('(') => {
+                words.extend(current.take());
+                commands.push(std::mem::take(&mut words));
+                chars.next();
+            }
+            (None, ';' | '&' | '|' | '(' | ')' | '`' | '\n') => {
+                words.extend(current.take());
+                commands.push(std::mem::take(&mut words));
+            }
+            (None, c) if c.is_whitespace() => words.extend(current.take()),
+            (None, c) => begin(&mut current, i).text.push(c),
+        }
+        if let Some(word) = current.as_mut() {
+            word.end = chars.peek().map_or(text.len(), |&(j, _)| j);
+        }
+    }
+    words.extend(current.take());
+    commands.push(words);
+
+    commands.retain(|words| !words.is_empty());
+    commands
+}
+
+/// The word being built, starting one at byte `i` if there is none
+fn begin(current: &mut Option<Word>, i: usize) -> &mut Word {
+    current.get_or_insert_with(|| Word {
+        text: String::new(),
+        start: i,
+        end: i,
+        resolution: None,
+    })
+}
+
+/// Expand `$NAME`, `${NAME}` and `$(NAME)` references that resolve to constants
+fn expand(mut word: Word, resolve: &dyn Fn(&str) -> Option<Resolution>) -> Word {
+    if !word.text.contains('$') {
+        return word;
+    }
+    let mut resolution = None;
+    let expanded = VARIABLE.replace_all(&word.text, |caps: &regex::Captures| {
+        let name = (1..=3).find_map(|i| caps.get(i)).map_or("", |m| m.as_str());
+        match resolve(name) {
+            Some(found) => {
+                let value = match &found.value {
+                    ConstantValue::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                resolution.get_or_insert(found);
+                value
+            }
+            None => caps[0].to_string(),
+        }
+    });
+    word.text = expanded.into_owned();
+    word.resolution = resolution;
+    word
+}
+
+/// The tool name and its arguments, skipping variable assignments, `sudo`,
+/// Dockerfile `RUN` and Makefile `@`/`-`/`+` recipe prefixes
+fn command_word(words: &[Word]) -> Option<(&str, &[Word])> {
+    let mut idx = 0;
+    while let Some(word) = words.get(idx) {
+        let text = word.text.as_str();
+        let is_assignment = text.split_once('=').is_some_and(|(name, _)| {
+            !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        });
+        if COMMAND_PREFIXES.contains(&text) || is_assignment {
+            idx += 1;
+        } else if words[..idx].last().is_some_and(|w| w.text == "sudo") && text.starts_with('-') {
+            // `sudo -u user cmd`
+            idx += if matches!(text, "-u" | "-g") { 2 } else { 1 };
+        } else {
+            break;
+        }
+    }
+
+    let word = words
+        .get(idx)
+        .filter(|w| !BLOCK_ENDS.contains(&w.text.as_str()))?;
+    let name = word.text.trim_start_matches(['@', '-', '+']);
+    let name = name.rsplit('/').next().unwrap_or(name);
+    Some((name, &words[idx + 1..]))
+}
+
+/// An option and the value that follows it, if it takes one
+struct Opt<'a> {
+    name: &'a Word,
+    value: Option<&'a Word>,
+}
+
+/// `openssl <subcommand> ...`
+fn openssl(args: &[Word]) -> Vec<Invocation> {
+    let Some(sub) = args.first().filter(|w| !w.text.starts_with('-')) else {
+        return Vec::new();
+    };
+    let command = format!("openssl {}", sub.text);
+
+    // Separate options (with their values) from positional arguments
+    let mut options = Vec::new();
+    let mut positional = Vec::new();
+    let mut rest = args[1..].iter().filter(|w| !REDIRECTION.is_match(&w.text));
+    while let Some(word) = rest.next() {
+        if word.text.starts_with('-') && word.text.len() > 1 {
+            let value = OPENSSL_VALUE_OPTIONS
+                .contains(&word.text.as_str())
+                .then(|| rest.next())
+                .flatten();
+            options.push(Opt { name: word, value });
+        } else {
+            positional.push(word);
+        }
+    }
+    let option = |name: &str| options.iter().find(|o| o.name.text == name);
+    let pkeyopt = |key: &str| {
+        options
+            .iter()
+            .filter(|o| o.name.text == "-pkeyopt")
+            .find_map(|o| {
+                let value = o.value?;
+                let (k, v) = value.text.split_once(':')?;
+                (k == key).then(|| (v.to_string(), value.resolution.clone()))
+            })
+    };
+    let numbits: Option<(u32, Option<Resolution>)> = positional
+        .iter()
+        .rev()
+        .find_map(|w| Some((w.text.parse().ok()?, w.resolution.clone())));
+
+    let mut found = Vec::new();
+    match sub.text.as_str() {
+        "genrsa" => {
+            found.push(Invocation::new(&command, CryptoType::Rsa, &[sub]).with_key_size(numbits))
+        }
+        "gendsa" => found.push(Invocation::new(&command, CryptoType::Dsa, &[sub])),
+        "dsaparam" => {
+            found.push(Invocation::new(&command, CryptoType::Dsa, &[sub]).with_key_size(numbits))
+        }
+        "dhparam" => found.push(
+            Invocation::new(&command, CryptoType::DiffieHellman, &[sub]).with_key_size(numbits),
+        ),
+        "ecparam" => {
+            if let Some(opt) = option("-name")
+                && let Some(curve) = opt.value
+            {
+                let bits = curve_bits(&curve.text).map(|b| (b, curve.resolution.clone()));
+                found.push(
+                    Invocation::new(&command, CryptoType::Ecdsa, &[opt.name, curve])
+                        .with_key_size(bits),
+                );
+            }
+        }
+        "genpkey" => {
+            if let Some(opt) = option("-algorithm")
+                && let Some(algorithm) = opt.value
+                && let Some(crypto_type) = key_algorithm(&algorithm.text)
+            {
+                let bits = [
+                    "rsa_keygen_bits",
+                    "dsa_paramgen_bits",
+                    "dh_paramgen_prime_len",
+                ]
+                .iter()
+                .find_map(|key| pkeyopt(key))
+                .and_then(|(v, r)| Some((v.parse().ok()?, r)))
+                .or_else(|| {
+                    let (curve, r) = pkeyopt("ec_paramgen_curve")?;
+                    Some((curve_bits(&curve)?, r))
+                });
+                found.push(
+                    Invocation::new(&command, crypto_type, &[opt.name, algorithm])
+                        .with_key_size(bits),
+                );
+            }
+        }
+        "req" => {
+            // `-newkey rsa:2048`, `-newkey ec -pkeyopt ec_paramgen_curve:P-256`
+            if let Some(opt) = option("-newkey")
+                && let Some(newkey) = opt.value
+            {
+                let (algorithm, param) = newkey.text.split_once(':').unwrap_or((&newkey.text, ""));
+                if let Some(crypto_type) = key_algorithm(algorithm) {
+                    let bits = param
+                        .parse()
+                        .ok()
+                        .map(|b| (b, newkey.resolution.clone()))
+                        .or_else(|| {
+                            let (curve, r) = pkeyopt("ec_paramgen_curve")?;
+                            Some((curve_bits(&curve)?, r))
+                        });
+                    found.push(
+                        Invocation::new(&command, crypto_type, &[opt.name, newkey])
+                            .with_key_size(bits),
+                    );
+                }
+            }
+        }
+        // `openssl md5 file`, `openssl des3 -in ...`
+        name => {
+            if let Some(crypto_type) = digest_type(name).or_else(|| cipher_type(name)) {
+                found.push(
+                    Invocation::new(&command, crypto_type, &[sub])
+                        .with_key_size(cipher_key_bits(name).map(|b| (b, sub.resolution.clone()))),
+                );
+            }
+        }
+    }
+
+    // `-des3`, `-sha1`, `-cipher des-ede3-cbc`, `-md md5`, ... on any subcommand
+    for opt in &options {
+        let flag = opt.name.text.trim_start_matches('-');
+        let (crypto_type, name, words) = match (flag, opt.value) {
+            ("md" | "digest", Some(value)) => {
+                (digest_type(&value.text), value, vec![opt.name, value])
+            }
+            ("cipher" | "v2", Some(value)) => {
+                (cipher_type(&value.text), value, vec![opt.name, value])
+            }
+            // A literal `-iv` is reused for every file encrypted with it
+            ("iv", Some(value)) if value.text.chars().all(|c| c.is_ascii_hexdigit()) => {
+                (Some(CryptoType::StaticIv), value, vec![opt.name, value])
+            }
+            _ if opt.value.is_none() => (
+                digest_type(flag).or_else(|| cipher_type(flag)),
+                opt.name,
+                vec![opt.name],
+            ),
+            _ => continue,
+        };
+        if let Some(crypto_type) = crypto_type {
+            let bits = cipher_key_bits(name.text.trim_start_matches('-'))
+                .map(|b| (b, name.resolution.clone()));
+            found.push(Invocation::new(&command, crypto_type, &words).with_key_size(bits));
+        }
+    }
+    found
+}
+
+/// `ssh-keygen [-t type] [-b bits] ...`; only key generation is reported
+fn ssh_keygen(tool: &Word, args: &[Word]) -> Vec<Invocation> {
+    let mut key_type = None;
+    let mut bits = None;
+    let mut rest = args.iter();
+    while let Some(word) = rest.next() {
+        let Some(flags) = word.text.strip_prefix('-').filter(|f| !f.is_empty()) else {
+            continue;
+        };
+        for (idx, flag) in flags.char_indices() {
+            if SSH_KEYGEN_MODE_OPTIONS.contains(flag) {
+                return Vec::new();
+            }
+            if SSH_KEYGEN_VALUE_OPTIONS.contains(flag) {
+                // `-t rsa` or `-trsa`
+                let attached = &flags[idx + flag.len_utf8()..];
+                let value = if attached.is_empty() {
+                    rest.next().map(|w| (w.text.clone(), w))
+                } else {
+                    Some((attached.to_string(), word))
+                };
+                match (flag, value) {
+                    ('t', Some((text, value))) => key_type = Some((text, word, value)),
+                    ('b', Some((text, value))) => {
+                        bits = text.parse().ok().map(|b| (b, value.resolution.clone()))
+                    }
+                    _ => {}
+                }
+                break;
+            }
+        }
+    }
+
+    // Without `-t`, ssh-keygen generates an RSA key
+    let invocation = match key_type {
+        Some((text, option, value)) => {
+            let name = text.strip_suffix("-sk").unwrap_or(&text);
+            match key_algorithm(if name == "rsa1" { "rsa" } else { name }) {
+                Some(crypto_type) => Invocation::new("ssh-keygen", crypto_type, &[option, value]),
+                None => return Vec::new(),
+            }
+        }
+        None => Invocation::new("ssh-keygen", CryptoType::Rsa, &[tool]),
+    };
+    // Ed25519 keys have a fixed size and `-b` is ignored
+    if invocation.crypto_type == CryptoType::Eddsa {
+        return vec![invocation];
+    }
+    vec![invocation.with_key_size(bits)]
+}
+
+/// Key algorithm named on the command line (`-algorithm`, `-newkey`, `-t`)
+fn key_algorithm(name: &str) -> Option<CryptoType> {
+    match name.to_ascii_lowercase().as_str() {
+        "rsa" | "rsa-pss" | "rsassa-pss" => Some(CryptoType::Rsa),
+        "ec" | "ecdsa" => Some(CryptoType::Ecdsa),
+        "ed25519" | "ed448" => Some(CryptoType::Eddsa),
+        "x25519" | "x448" => Some(CryptoType::Ecdh),
+        "dsa" => Some(CryptoType::Dsa),
+        "dh" | "dhx" => Some(CryptoType::DiffieHellman),
+        _ => None,
+    }
+}
+
+/// Weak cipher or cipher mode named by an `openssl` cipher option or subcommand
+fn cipher_type(name: &str) -> Option<CryptoType> {
+    let name = name.to_ascii_lowercase();
+    if name == "des3" || name == "descert" || name.starts_with("des-ede") {
+        Some(CryptoType::TripleDes)
+    } else if name == "des"
+        || name == "desx"
+        || name.starts_with("des-")
+        || name.starts_with("desx-")
+    {
+        Some(CryptoType::Des)
+    } else if name == "rc4" || name.starts_with("rc4-") {
+        Some(CryptoType::Rc4)
+    } else if name == "rc2" || name.starts_with("rc2-") {
+        Some(CryptoType::Rc2)
+    } else if name == "bf" || name.starts_with("bf-") || name.starts_with("blowfish") {
+        Some(CryptoType::Blowfish)
+    } else if name.starts_with("aes") && cipher_key_bits(&name).is_some_and(|bits| bits < 256) {
+        Some(CryptoType::Aes)
+    } else if name.ends_with("-ecb") {
+        Some(CryptoType::EcbMode)
+    } else {
+        None
+    }
+}
+
+/// Key size in an AES or RC2 cipher name, e.g. 128 in `aes-128-cbc` or 40 in
+/// `rc2-40-cbc`
+fn cipher_key_bits(name: &str) -> Option<u32> {
+    let name = name.to_ascii_lowercase();
+    let rest = name
+        .strip_prefix("aes")
+        .or_else(|| name.strip_prefix("rc2"))?;
+    rest.trim_start_matches('-').split('-').next()?.parse().ok()
+}
+
+/// Weak digest named by an `openssl` digest option or subcommand
+fn digest_type(name: &str) -> Option<CryptoType> {
+    match name.to_ascii_lowercase().as_str() {
+        "md5" => Some(CryptoType::Md5),
+        "sha1" | "sha-1" => Some(CryptoType::Sha1),
+        "sha224" | "sha-224" | "sha512-224" => Some(CryptoType::Sha224),
+        _ => None,
+    }
+}
+
+fn curve_bits(curve: &str) -> Option<u32> {
+    CURVE_BITS.captures(curve)?.get(1)?.as_str().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invocations(statement: &str) -> Vec<(String, CryptoType, String, Option<u32>)> {
+        scan(statement, &|_| None)
+            .into_iter()
+            .map(|i| {
+                let text = statement[i.start..i.end].to_string();
+                (i.command, i.crypto_type, text, i.key_size)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_openssl_key_generation() {
+        assert_eq!(
+            invocations("openssl genrsa -out key.pem 1024 2>/dev/null"),
+            vec![(
+                "openssl genrsa".to_string(),
+                CryptoType::Rsa,
+                "genrsa".to_string(),
+                Some(1024)
+            )]
+        );
+
+        let found = invocations("openssl req -new -x509 -days 365 -newkey rsa:2048 -sha1 -nodes");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].1, CryptoType::Rsa);
+        assert_eq!(found[0].2, "-newkey rsa:2048");
+        assert_eq!(found[0].3, Some(2048));
+        assert_eq!(
+            (found[1].1.clone(), found[1].2.as_str()),
+            (CryptoType::Sha1, "-sha1")
+        );
+
+        let found = invocations(
+            "openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-384 -out ec.pem",
+        );
+        assert_eq!(found[0].1, CryptoType::Ecdsa);
+        assert_eq!(found[0].3, Some(384));
+
+        let found = invocations("openssl ecparam -name prime256v1 -genkey -noout");
+        assert_eq!(
+            (found[0].2.as_str(), found[0].3),
+            ("-name prime256v1", Some(256))
+        );
+
+        assert_eq!(
+            invocations("openssl dhparam -out dh.pem 2048")[0].3,
+            Some(2048)
+        );
+        assert_eq!(
+            invocations("openssl genpkey -algorithm ED25519 -out k.pem")[0].1,
+            CryptoType::Eddsa
+        );
+        assert!(invocations("openssl genpkey -algorithm ML-DSA-65 -out k.pem").is_empty());
+        assert!(invocations("openssl x509 -in cert.pem -noout -text").is_empty());
+    }
+
+    #[test]
+    fn test_openssl_ciphers_and_digests() {
+        let found = invocations("openssl enc -des3 -salt -in secret.txt -out secret.enc");
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            (found[0].1.clone(), found[0].2.as_str()),
+            (CryptoType::TripleDes, "-des3")
+        );
+
+        let found = invocations("openssl genrsa -des3 -passout pass:x 2048");
+        assert_eq!(found[0].1, CryptoType::Rsa);
+        assert_eq!(found[1].1, CryptoType::TripleDes);
+
+        assert_eq!(
+            invocations("openssl rc4 -in a -out b")[0].1,
+            CryptoType::Rc4
+        );
+        assert_eq!(invocations("openssl dgst -md5 file")[0].1, CryptoType::Md5);
+        assert_eq!(
+            invocations("openssl enc -e -cipher des-cbc -in a")[0].2,
+            "-cipher des-cbc"
+        );
+        assert!(invocations("openssl enc -aes-256-cbc -pbkdf2 -in a").is_empty());
+    }
+
+    #[test]
+    fn test_openssl_cipher_strength_and_modes() {
+        let found = invocations(
+            "openssl enc -aes-128-cbc -K $KEY -iv 00000000000000000000000000000000 -in a",
+        );
+        assert_eq!(
+            found
+                .iter()
+                .map(|f| (f.1.clone(), f.2.as_str(), f.3))
+                .collect::<Vec<_>>(),
+            vec![
+                (CryptoType::Aes, "-aes-128-cbc", Some(128)),
+                (
+                    CryptoType::StaticIv,
+                    "-iv 00000000000000000000000000000000",
+                    None
+                ),
+            ]
+        );
+
+        assert_eq!(
+            invocations("openssl enc -aes-256-ecb -in a")[0].1,
+            CryptoType::EcbMode
+        );
+        assert_eq!(
+            invocations("openssl bf-cbc -in a")[0].1,
+            CryptoType::Blowfish
+        );
+        assert_eq!(
+            invocations("openssl enc -cipher rc2-40-cbc -in a")[0].3,
+            Some(40)
+        );
+        assert_eq!(
+            invocations("openssl dgst -sha224 f")[0].1,
+            CryptoType::Sha224
+        );
+        assert!(invocations("openssl enc -aes-256-gcm -iv \"$IV\" -in a").is_empty());
+    }
+
+    #[test]
+    fn test_ssh_keygen() {
+        let found = invocations("ssh-keygen -b 1024 -t rsa -f id_rsa -N ''");
+        assert_eq!(
+            found,
+            vec![(
+                "ssh-keygen".to_string(),
+                CryptoType::Rsa,
+                "-t rsa".to_string(),
+                Some(1024)
+            )]
+        );
+
+        assert_eq!(
+            invocations("ssh-keygen -q -t dsa -f key")[0].1,
+            CryptoType::Dsa
+        );
+        assert_eq!(invocations("ssh-keygen -tecdsa -b 384")[0].3, Some(384));
+        assert_eq!(
+            invocations("ssh-keygen -t ed25519 -b 4096 -C ci@example.com"),
+            vec![(
+                "ssh-keygen".to_string(),
+                CryptoType::Eddsa,
+                "-t ed25519".to_string(),
+                None
+            )]
+        );
+        assert_eq!(
+            invocations("ssh-keygen -t ed25519-sk")[0].1,
+            CryptoType::Eddsa
+        );
+
+        // Default key type, and modes that do not generate keys
+        assert_eq!(invocations("ssh-keygen -f id -N ''")[0].2, "ssh-keygen");
+        assert!(invocations("ssh-keygen -l -f id_rsa.pub").is_empty());
+        assert!(invocations("ssh-keygen -R host.example.com").is_empty());
+    }
+
+    #[test]
+    fn test_shell_syntax() {
+        // Dockerfile RUN with continuations, chained commands and a Makefile prefix
+        let statement =
+            "RUN apt-get install -y openssl && \\\n    openssl genrsa \\\n      -out /k.pem 1024";
+        let found = invocations(statement);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].3, Some(1024));
+        assert_eq!(
+            &statement[scan(statement, &|_| None)[0].start..][..6],
+            "genrsa"
+        );
+
+        assert_eq!(
+            invocations("\t@/usr/bin/openssl dsaparam 1024 > p.pem")[0].3,
+            Some(1024)
+        );
+        assert_eq!(
+            invocations("sudo -u root ssh-keygen -t \"dsa\"")[0].2,
+            "-t \"dsa\""
+        );
+        assert_eq!(invocations("KEY=$(openssl genrsa 512)")[0].3, Some(512));
+        assert!(invocations("echo 'openssl genrsa 1024'").is_empty());
+    }
+
+    #[test]
+    fn test_variables_are_resolved() {
+        let resolve = |name: &str| {
+            (name == "KEY_BITS").then(|| Resolution {
+                symbol: name.to_string(),
+                value: ConstantValue::Integer(1024),
+                chain: vec![],
+            })
+        };
+        let found = scan("openssl genrsa -out k.pem ${KEY_BITS}", &resolve);
+        assert_eq!(found[0].key_size, Some(1024));
+        assert_eq!(
+            found[0]
+                .key_size_resolution
+                .as_ref()
+                .map(|r| r.symbol.as_str()),
+            Some("KEY_BITS")
+        );
+    }
+}
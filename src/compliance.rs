@@ -1,6 +1,7 @@
 // NIST 800-53 SC-13 Compliance Reporting
 // Generates detailed compliance reports with OSCAL JSON output
 
+use crate::strength;
 use crate::types::*;
 use chrono::Utc;
 use serde_json::json;
@@ -8,6 +9,8 @@ use uuid::Uuid;
 
 const OSCAL_VERSION: &str = "1.1.2";
 const REPORT_VERSION: &str = "1.0.0";
+/// Namespace for the scanner's own OSCAL property names
+const OSCAL_PROPERTY_NS: &str = "https://github.com/arcqubit/pqc-scanner/ns/oscal";
 
 /// Generate NIST 800-53 SC-13 Assessment Report from AuditResult
 pub fn generate_sc13_report(
@@ -97,14 +100,22 @@ fn generate_summary(audit_result: &AuditResult) -> AssessmentSummary {
     let mut quantum_vulnerable = Vec::new();
     let mut deprecated = Vec::new();
     let mut weak_keys = Vec::new();
+    let mut policy_violations = Vec::new();
 
     for vuln in &audit_result.vulnerabilities {
         let crypto_name = vuln.crypto_type.to_string();
 
+        if let Some(violation) = policy_violation(vuln)
+            && !policy_violations.contains(&violation)
+        {
+            policy_violations.push(violation);
+        }
+
         // Categorize algorithms
         match vuln.crypto_type {
             CryptoType::Rsa
             | CryptoType::Ecdsa
+            | CryptoType::Eddsa
             | CryptoType::Ecdh
             | CryptoType::Dsa
             | CryptoType::DiffieHellman => {
@@ -116,15 +127,32 @@ fn generate_summary(audit_result: &AuditResult) -> AssessmentSummary {
             | CryptoType::Md5
             | CryptoType::Des
             | CryptoType::TripleDes
-            | CryptoType::Rc4 => {
+            | CryptoType::Rc4
+            | CryptoType::Rc2
+            | CryptoType::Blowfish
+            | CryptoType::Sike
+            | CryptoType::Rainbow => {
                 if !deprecated.contains(&crypto_name) {
                     deprecated.push(crypto_name.clone());
                 }
             }
+            CryptoType::Aes
+            | CryptoType::Sha224
+            | CryptoType::EcbMode
+            | CryptoType::StaticIv
+            | CryptoType::Kyber
+            | CryptoType::Dilithium
+            | CryptoType::SphincsPlus
+            | CryptoType::NonHybridKem
+            | CryptoType::WeakRandom
+            | CryptoType::UnsignedJwt
+            | CryptoType::WeakJwtSecret => {}
         }
 
         // Track weak key sizes
-        if let Some(key_size) = vuln.key_size {
+        if let Some(key_size) = vuln.key_size
+            && vuln.crypto_type.category() != CryptoCategory::SymmetricEncryption
+        {
             let key_info = format!("{} {}-bit", crypto_name, key_size);
             if key_size < 2048 && !weak_keys.contains(&key_info) {
                 weak_keys.push(key_info);
@@ -132,12 +160,8 @@ fn generate_summary(audit_result: &AuditResult) -> AssessmentSummary {
         }
     }
 
-    // Calculate compliance score (inverse of risk score)
-    let compliance_score = if audit_result.risk_score == 0 {
-        100
-    } else {
-        100 - audit_result.risk_score
-    };
+    // Calculate compliance score (inverse of risk score, crediting migration)
+    let compliance_score = 100 - migration_adjusted_risk(audit_result);
 
     AssessmentSummary {
         files_scanned: 1,
@@ -146,11 +170,61 @@ fn generate_summary(audit_result: &AuditResult) -> AssessmentSummary {
         quantum_vulnerable_algorithms: quantum_vulnerable,
         deprecated_algorithms: deprecated,
         weak_key_sizes: weak_keys,
+        policy_violations,
+        quantum_safe_algorithms: audit_result.quantum_safe_algorithms(),
+        migration_progress: audit_result.migration_progress(),
         compliance_score,
         risk_score: audit_result.risk_score,
     }
 }
 
+/// Mean risk of the findings, with each quantum-vulnerable one weighted by the
+/// share of public-key uses not yet migrated to post-quantum algorithms
+fn migration_adjusted_risk(audit_result: &AuditResult) -> u32 {
+    let Some(progress) = audit_result.migration_progress() else {
+        return audit_result.risk_score;
+    };
+    if audit_result.vulnerabilities.is_empty() {
+        return 0;
+    }
+    let total: u32 = audit_result
+        .vulnerabilities
+        .iter()
+        .map(|v| {
+            if v.crypto_type.is_quantum_vulnerable() {
+                v.risk_score * (100 - progress) / 100
+            } else {
+                v.risk_score
+            }
+        })
+        .sum();
+    total / audit_result.vulnerabilities.len() as u32
+}
+
+/// Why a finding falls short of the CNSA 2.0-style policy, if it does:
+/// under `strength::POLICY_POST_QUANTUM_BITS` of post-quantum security, or an
+/// insecure cipher mode or IV. Token and key-exchange configuration findings
+/// are not about symmetric strength and do not count.
+fn policy_violation(vuln: &Vulnerability) -> Option<String> {
+    if matches!(vuln.crypto_type, CryptoType::EcbMode | CryptoType::StaticIv) {
+        return Some(vuln.crypto_type.to_string());
+    }
+    let level = vuln.security_level?;
+    if strength::meets_policy(&level) {
+        return None;
+    }
+    let name = match vuln.key_size {
+        Some(bits) => format!("{} {}-bit", vuln.crypto_type, bits),
+        None => vuln.crypto_type.to_string(),
+    };
+    Some(format!(
+        "{}: {}-bit post-quantum security, below the {}-bit policy",
+        name,
+        level.post_quantum_bits,
+        strength::POLICY_POST_QUANTUM_BITS
+    ))
+}
+
 /// Generate detailed findings with evidence
 fn generate_findings(
     audit_result: &AuditResult,
@@ -222,6 +296,7 @@ fn generate_findings(
                 line: vuln.line,
                 column: vuln.column,
                 snippet: vuln.context.clone(),
+                scope: vuln.scope.clone(),
             };
 
             let evidence_data = json!({
@@ -229,6 +304,7 @@ fn generate_findings(
                 "severity": format!("{:?}", vuln.severity),
                 "risk_score": vuln.risk_score,
                 "key_size": vuln.key_size,
+                "security_level": vuln.security_level,
                 "message": vuln.message,
             });
 
@@ -236,8 +312,12 @@ fn generate_findings(
                 evidence_id,
                 evidence_type: EvidenceType::StaticScan,
                 description: format!(
-                    "Detected {} at line {} column {}: {}",
-                    crypto_type, vuln.line, vuln.column, vuln.message
+                    "Detected {} at line {} column {}{}: {}",
+                    crypto_type,
+                    vuln.line,
+                    vuln.column,
+                    in_scope(&vuln.scope),
+                    vuln.message
                 ),
                 source_location: Some(source_location),
                 collected_at: timestamp.to_string(),
@@ -250,11 +330,7 @@ fn generate_findings(
             This algorithm is {} and poses a {} risk to cryptographic protection.",
             vulns.len(),
             crypto_type,
-            if is_quantum_vulnerable(&first_vuln.crypto_type) {
-                "quantum-vulnerable"
-            } else {
-                "cryptographically deprecated"
-            },
+            algorithm_status(&first_vuln.crypto_type),
             format!("{:?}", highest_severity).to_lowercase()
         );
 
@@ -276,12 +352,59 @@ fn generate_findings(
     findings
 }
 
+/// ` in <scope>` for evidence descriptions, empty at top level
+fn in_scope(scope: &EnclosingScope) -> String {
+    if scope.is_empty() {
+        String::new()
+    } else {
+        format!(" in {}", scope)
+    }
+}
+
+/// OSCAL props naming the module, class and function of a code location
+fn scope_props(scope: &EnclosingScope) -> Option<Vec<Property>> {
+    let props: Vec<Property> = [
+        ("enclosing-module", &scope.module),
+        ("enclosing-class", &scope.class),
+        ("enclosing-function", &scope.function),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+        Some(Property {
+            name: name.to_string(),
+            value: value.clone()?,
+            ns: Some(OSCAL_PROPERTY_NS.to_string()),
+        })
+    })
+    .collect();
+    (!props.is_empty()).then_some(props)
+}
+
+/// How a finding's algorithm falls short, for finding descriptions
+fn algorithm_status(crypto_type: &CryptoType) -> &'static str {
+    match crypto_type {
+        _ if is_quantum_vulnerable(crypto_type) => "quantum-vulnerable",
+        CryptoType::Aes | CryptoType::Sha224 => "below the post-quantum security policy",
+        CryptoType::EcbMode | CryptoType::StaticIv => "an insecure use of a cipher",
+        CryptoType::Sike | CryptoType::Rainbow => "a broken post-quantum candidate",
+        CryptoType::Kyber | CryptoType::Dilithium | CryptoType::SphincsPlus => {
+            "a pre-standard post-quantum draft"
+        }
+        CryptoType::NonHybridKem => "missing the required hybrid key exchange",
+        CryptoType::WeakRandom => "a predictable source of randomness",
+        CryptoType::UnsignedJwt => "an unsigned or unverified token",
+        CryptoType::WeakJwtSecret => "a guessable token signing secret",
+        _ => "cryptographically deprecated",
+    }
+}
+
 /// Check if crypto type is quantum vulnerable
 fn is_quantum_vulnerable(crypto_type: &CryptoType) -> bool {
     matches!(
         crypto_type,
         CryptoType::Rsa
             | CryptoType::Ecdsa
+            | CryptoType::Eddsa
             | CryptoType::Ecdh
             | CryptoType::Dsa
             | CryptoType::DiffieHellman
@@ -312,6 +435,17 @@ fn generate_compliance_recommendations(audit_result: &AuditResult) -> Vec<String
         );
     }
 
+    let policy_violations = audit_result
+        .vulnerabilities
+        .iter()
+        .any(|v| policy_violation(v).is_some());
+    if policy_violations {
+        recommendations.push(format!(
+            "CNSA 2.0 POLICY: Symmetric keys and hashes need {}-bit post-quantum security: use AES-256 and SHA-384 or SHA-512, and replace ECB mode and static IVs with an authenticated mode (GCM) using a unique random nonce per message.",
+            strength::POLICY_POST_QUANTUM_BITS
+        ));
+    }
+
     recommendations.push(
         "Implement crypto-agility: Design systems to easily swap cryptographic algorithms as new standards emerge.".to_string()
     );
@@ -363,11 +497,20 @@ pub fn generate_oscal_json(
             let relevant_evidence = if let Some(ref loc) = evidence.source_location {
                 Some(vec![RelevantEvidence {
                     href: format!("#{}:{}", loc.file_path, loc.line),
-                    description: format!("Code location: {}:{}", loc.file_path, loc.line),
+                    description: format!(
+                        "Code location: {}:{}{}",
+                        loc.file_path,
+                        loc.line,
+                        in_scope(&loc.scope)
+                    ),
                 }])
             } else {
                 None
             };
+            let props = evidence
+                .source_location
+                .as_ref()
+                .and_then(|loc| scope_props(&loc.scope));
 
             observations.push(Observation {
                 uuid: obs_uuid,
@@ -376,6 +519,7 @@ pub fn generate_oscal_json(
                 types: Some(vec![format!("{:?}", evidence.evidence_type)]),
                 collected: Some(evidence.collected_at.clone()),
                 relevant_evidence,
+                props,
             });
         }
     }
@@ -483,27 +627,47 @@ mod tests {
 
         result.add_vulnerability(Vulnerability {
             crypto_type: CryptoType::Rsa,
+            category: CryptoCategory::AsymmetricEncryption,
             severity: Severity::High,
             risk_score: 85,
             line: 10,
             column: 5,
+            span: SourceSpan::default(),
             context: "const rsa = crypto.generateKeyPair('rsa', { modulusLength: 2048 })"
                 .to_string(),
+            scope: EnclosingScope::default(),
             message: "RSA detected - quantum vulnerable".to_string(),
             recommendation: "Replace with CRYSTALS-Kyber".to_string(),
             key_size: Some(2048),
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: String::new(),
+            cwe: None,
+            confidence: 1.0,
+            explanation: Explanation::default(),
+            suppressed: Vec::new(),
         });
 
         result.add_vulnerability(Vulnerability {
             crypto_type: CryptoType::Md5,
+            category: CryptoCategory::Hash,
             severity: Severity::Critical,
             risk_score: 100,
             line: 15,
             column: 10,
+            span: SourceSpan::default(),
             context: "const hash = crypto.createHash('md5')".to_string(),
+            scope: EnclosingScope::default(),
             message: "MD5 is cryptographically broken".to_string(),
             recommendation: "Replace with SHA-256".to_string(),
             key_size: None,
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: String::new(),
+            cwe: None,
+            confidence: 1.0,
+            explanation: Explanation::default(),
+            suppressed: Vec::new(),
         });
 
         result.calculate_risk_score();
@@ -537,14 +701,24 @@ mod tests {
         // Test with critical vulnerability
         result.add_vulnerability(Vulnerability {
             crypto_type: CryptoType::Md5,
+            category: CryptoCategory::Hash,
             severity: Severity::Critical,
             risk_score: 100,
             line: 1,
             column: 1,
+            span: SourceSpan::default(),
             context: "md5".to_string(),
+            scope: EnclosingScope::default(),
             message: "test".to_string(),
             recommendation: "test".to_string(),
             key_size: None,
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: String::new(),
+            cwe: None,
+            confidence: 1.0,
+            explanation: Explanation::default(),
+            suppressed: Vec::new(),
         });
 
         let (impl_status, assess_status) = assess_implementation(&result);
@@ -0,0 +1,298 @@
+//! Confidence Scoring
+//!
+//! Starts from each rule's base confidence and applies context signals (API
+//! call, algorithm-name argument, command-line tool argument, crypto imports,
+//! key size, identifier fragments), recording each one in the finding's
+//! explanation trail.
+
+use crate::lexer::{self, LexedSource};
+use crate::types::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// Confidence adjustments per signal
+const API_CALL: f32 = 0.25;
+const ALGORITHM_ARGUMENT: f32 = 0.25;
+const CRYPTO_IMPORT: f32 = 0.1;
+const KEY_SIZE: f32 = 0.1;
+const IDENTIFIER_FRAGMENT: f32 = -0.25;
+
+// Lazy-compiled import patterns for well-known cryptography libraries
+lazy_static! {
+    static ref RUST_CRYPTO_IMPORT: Regex = Regex::new(
+        r"(?m)^\s*(pub\s+)?(use|extern\s+crate)\s+(ring|rsa|openssl|sha1|sha2|md5|des|rc4|dsa|p256|p384|k256|secp256k1|ecdsa|x25519_dalek|ed25519_dalek|aes|aes_gcm|rustls)\b"
+    ).expect("RUST_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref PYTHON_CRYPTO_IMPORT: Regex = Regex::new(
+        r"(?m)^\s*(import|from)\s+(hashlib|hmac|ssl|Crypto|Cryptodome|cryptography|rsa|ecdsa|nacl|OpenSSL|web3|eth_account|eth_keys|coincurve)\b"
+    ).expect("PYTHON_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref JS_CRYPTO_IMPORT: Regex = Regex::new(
+        r#"(require\(\s*|from\s+|import\s+)['"](node:)?(crypto|crypto-js|node-forge|jsrsasign|elliptic|tweetnacl|node-rsa|ethers|web3|secp256k1|@noble/secp256k1|@noble/curves)['"/]"#
+    ).expect("JS_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    // Java, Kotlin and Scala
+    static ref JVM_CRYPTO_IMPORT: Regex = Regex::new(
+        r"(?m)^\s*import\s+(static\s+)?(java\.security|javax\.crypto|org\.bouncycastle)\b"
+    ).expect("JVM_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref GO_CRYPTO_IMPORT: Regex = Regex::new(
+        r#""(crypto/|golang\.org/x/crypto/|github\.com/ethereum/go-ethereum/crypto|github\.com/decred/dcrd/dcrec/secp256k1|github\.com/btcsuite/btcd/btcec)"#
+    ).expect("GO_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref CPP_CRYPTO_IMPORT: Regex = Regex::new(
+        r#"(?m)^\s*#\s*include\s*[<"](openssl|mbedtls|gcrypt|cryptopp|sodium|wolfssl|secp256k1)"#
+    ).expect("CPP_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref PHP_CRYPTO_IMPORT: Regex = Regex::new(
+        r"(?m)^\s*use\s+\\?(phpseclib3?|ParagonIE|Defuse)\\"
+    ).expect("PHP_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref RUBY_CRYPTO_IMPORT: Regex = Regex::new(
+        r#"(?m)^\s*require\s*\(?\s*['"](openssl|digest|rbnacl|bcrypt)\b"#
+    ).expect("RUBY_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    // Swift `import`, Objective-C `@import` and `#import`
+    static ref APPLE_CRYPTO_IMPORT: Regex = Regex::new(
+        r#"(?m)^\s*(?:@?import\s+|#\s*(?:import|include)\s*[<"])(CryptoKit|CommonCrypto|Security|CryptoSwift|openssl)\b"#
+    ).expect("APPLE_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    // Shell scripts and Dockerfiles that install the crypto tooling
+    static ref SHELL_CRYPTO_IMPORT: Regex = Regex::new(
+        r"\b(?:apt-get|apt|apk|yum|dnf|brew)\s+(?:-\S+\s+)*(?:install|add)\b[^\n]*\b(openssl|libssl-dev|openssh-client|openssh-keygen)\b"
+    ).expect("SHELL_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    // OpenZeppelin's signature helpers
+    static ref SOLIDITY_CRYPTO_IMPORT: Regex = Regex::new(
+        r#"(?m)^\s*import\s+[^;]*["'][^"']*/(?:cryptography/|ECDSA\.sol|SignatureChecker\.sol)"#
+    ).expect("SOLIDITY_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+
+    static ref CSHARP_CRYPTO_IMPORT: Regex = Regex::new(
+        r"(?m)^\s*using\s+(System\.Security\.Cryptography|Org\.BouncyCastle)\b"
+    ).expect("CSHARP_CRYPTO_IMPORT: Invalid regex - this is a compile-time bug");
+}
+
+/// Import of a crypto library in `language`; JSON documents import nothing
+fn crypto_import_pattern(language: Language) -> Option<&'static Regex> {
+    let pattern: &'static Regex = match language {
+        Language::Rust => &RUST_CRYPTO_IMPORT,
+        Language::Python => &PYTHON_CRYPTO_IMPORT,
+        Language::JavaScript | Language::TypeScript => &JS_CRYPTO_IMPORT,
+        Language::Java | Language::Kotlin | Language::Scala => &JVM_CRYPTO_IMPORT,
+        Language::Go => &GO_CRYPTO_IMPORT,
+        Language::Cpp => &CPP_CRYPTO_IMPORT,
+        Language::Csharp => &CSHARP_CRYPTO_IMPORT,
+        Language::Php => &PHP_CRYPTO_IMPORT,
+        Language::Ruby => &RUBY_CRYPTO_IMPORT,
+        Language::Swift | Language::ObjectiveC => &APPLE_CRYPTO_IMPORT,
+        Language::Shell => &SHELL_CRYPTO_IMPORT,
+        Language::Solidity => &SOLIDITY_CRYPTO_IMPORT,
+        Language::Json => return None,
+    };
+    Some(pattern)
+}
+
+/// Whether the source imports a cryptography library outside of comments
+pub fn has_crypto_import(source: &str, lexed: &LexedSource, language: Language) -> bool {
+    crypto_import_pattern(language).is_some_and(|pattern| {
+        pattern.find_iter(source).any(|m| {
+            !matches!(
+                lexed.kind_at(m.start()),
+                SpanKind::Comment | SpanKind::DocString
+            )
+        })
+    })
+}
+
+/// Apply context signals to a finding and set its final confidence.
+///
+/// `scan_line` is the comment-stripped line the match was found on; the
+/// finding's `column` and span length locate the match within it.
+pub fn assess(vuln: &mut Vulnerability, scan_line: &str, language: Language, crypto_import: bool) {
+    let is_identifier_char = |c: char| lexer::is_identifier_char(c, language);
+    let is_chain_char = |c: char| is_identifier_char(c) || c == '.' || c == ':';
+    let start = vuln.column;
+    let end = start + (vuln.span.byte_end - vuln.span.byte_start);
+    let mut signals = Vec::new();
+
+    if let Some(command) = &vuln.explanation.command {
+        signals.push(ConfidenceSignal {
+            kind: SignalKind::CommandArgument,
+            adjustment: ALGORITHM_ARGUMENT,
+            detail: format!(
+                "'{}' is an argument to `{}`",
+                vuln.explanation.matched_text, command
+            ),
+        });
+    } else if let Some(resolution) = &vuln.explanation.algorithm_resolution {
+        signals.push(ConfidenceSignal {
+            kind: SignalKind::AlgorithmArgument,
+            adjustment: ALGORITHM_ARGUMENT,
+            detail: format!(
+                "`{}` resolves to '{}' and is passed to a crypto API",
+                resolution.symbol, vuln.explanation.matched_text
+            ),
+        });
+    } else {
+        match vuln.span_kind {
+            // Literals that are not crypto API arguments are blanked in `scan_line`;
+            // only rules that match every string literal (JOSE `"alg"`) find them
+            SpanKind::StringLiteral if scan_line[start..end].trim().is_empty() => {}
+            SpanKind::StringLiteral => signals.push(ConfidenceSignal {
+                kind: SignalKind::AlgorithmArgument,
+                adjustment: ALGORITHM_ARGUMENT,
+                detail: format!(
+                    "'{}' is passed to a crypto API",
+                    vuln.explanation.matched_text
+                ),
+            }),
+            // Comments are blanked before matching, so no finding starts in one
+            SpanKind::Comment | SpanKind::DocString => {}
+            SpanKind::Code => {
+                // The dotted/pathed identifier chain around the match, e.g. `hashlib.md5`
+                let chain_start = scan_line[..start]
+                    .char_indices()
+                    .rev()
+                    .take_while(|&(_, c)| is_chain_char(c))
+                    .last()
+                    .map_or(start, |(i, _)| i);
+                let chain_end = scan_line[end..]
+                    .char_indices()
+                    .find(|&(_, c)| !is_chain_char(c))
+                    .map_or(scan_line.len(), |(i, _)| end + i);
+                let chain = &scan_line[chain_start..chain_end];
+
+                let is_call = scan_line[chain_end..].trim_start().starts_with('(');
+                let is_fragment = scan_line[..start]
+                    .chars()
+                    .next_back()
+                    .is_some_and(is_identifier_char)
+                    || scan_line[end..]
+                        .chars()
+                        .next()
+                        .is_some_and(is_identifier_char);
+
+                if is_call {
+                    signals.push(ConfidenceSignal {
+                        kind: SignalKind::ApiCall,
+                        adjustment: API_CALL,
+                        detail: format!("`{}` is called", chain),
+                    });
+                } else if is_fragment {
+                    signals.push(ConfidenceSignal {
+                        kind: SignalKind::IdentifierFragment,
+                        adjustment: IDENTIFIER_FRAGMENT,
+                        detail: format!("match is part of the identifier `{}`", chain),
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(bits) = vuln.key_size {
+        signals.push(ConfidenceSignal {
+            kind: SignalKind::KeySize,
+            adjustment: KEY_SIZE,
+            detail: match &vuln.explanation.key_size_resolution {
+                Some(resolution) => {
+                    format!(
+                        "{}-bit key size resolved from `{}`",
+                        bits, resolution.symbol
+                    )
+                }
+                None => format!("{}-bit key size found", bits),
+            },
+        });
+    }
+
+    if crypto_import {
+        signals.push(ConfidenceSignal {
+            kind: SignalKind::CryptoImport,
+            adjustment: CRYPTO_IMPORT,
+            detail: "file imports a cryptography library".to_string(),
+        });
+    }
+
+    let total =
+        vuln.explanation.base_confidence + signals.iter().map(|s| s.adjustment).sum::<f32>();
+    vuln.confidence = (total.clamp(0.0, 1.0) * 100.0).round() / 100.0;
+    vuln.explanation.signals = signals;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze;
+
+    fn finding<'a>(result: &'a AuditResult, matched: &str) -> &'a Vulnerability {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.explanation.matched_text == matched)
+            .unwrap()
+    }
+
+    fn signal_kinds(vuln: &Vulnerability) -> Vec<SignalKind> {
+        vuln.explanation.signals.iter().map(|s| s.kind).collect()
+    }
+
+    #[test]
+    fn test_api_call_outranks_identifier_fragment() {
+        let source = "import hashlib\ndigest = hashlib.md5(data)\nmd5_total = 3\n";
+        let result = analyze(source, "python").unwrap();
+
+        let call = finding(&result, "md5");
+        assert_eq!(call.line, 2);
+        assert_eq!(
+            signal_kinds(call),
+            vec![SignalKind::ApiCall, SignalKind::CryptoImport]
+        );
+        assert_eq!(call.confidence, 0.85);
+        assert_eq!(call.explanation.rule_id, "md5");
+
+        let fragment = result.vulnerabilities.iter().find(|v| v.line == 3).unwrap();
+        assert_eq!(
+            signal_kinds(fragment),
+            vec![SignalKind::IdentifierFragment, SignalKind::CryptoImport]
+        );
+        assert!(fragment.confidence < call.confidence);
+    }
+
+    #[test]
+    fn test_algorithm_argument_signal() {
+        let source = r#"KeyPairGenerator kpg = KeyPairGenerator.getInstance("RSA");"#;
+        let result = analyze(source, "java").unwrap();
+
+        let vuln = finding(&result, "RSA");
+        assert_eq!(signal_kinds(vuln), vec![SignalKind::AlgorithmArgument]);
+        // The JCA rule starts from a base confidence of 0.7
+        assert_eq!(vuln.rule_id, "jca-rsa");
+        assert_eq!(vuln.confidence, 0.95);
+    }
+
+    #[test]
+    fn test_key_size_signal() {
+        let result = analyze("key = RSA.generate(1024)", "python").unwrap();
+        let vuln = finding(&result, "RSA");
+        assert!(signal_kinds(vuln).contains(&SignalKind::KeySize));
+        assert_eq!(vuln.confidence, 0.85);
+    }
+
+    #[test]
+    fn test_commented_import_does_not_count() {
+        let source = "// use md5;\nfn f() {}\n";
+        let lexed = LexedSource::new(source, Language::Rust);
+        assert!(!has_crypto_import(source, &lexed, Language::Rust));
+
+        let source = "use md5;\nfn f() {}\n";
+        let lexed = LexedSource::new(source, Language::Rust);
+        assert!(has_crypto_import(source, &lexed, Language::Rust));
+    }
+
+    #[test]
+    fn test_go_import_in_string_literal_counts() {
+        let source = "import (\n\t\"crypto/rsa\"\n)\n";
+        let lexed = LexedSource::new(source, Language::Go);
+        assert!(has_crypto_import(source, &lexed, Language::Go));
+    }
+}
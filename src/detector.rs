@@ -1,208 +1,422 @@
 //! Cryptographic Pattern Detection Module
 //!
-//! Detects cryptographic algorithms, key sizes, and security issues across multiple languages.
-//! Provides severity classifications and actionable quantum-readiness recommendations.
+//! The single rule engine behind `audit::analyze`. Each `CryptoPattern` maps a
+//! regex to a `CryptoType`, `CryptoCategory` and severity, with optional
+//! key-size tiers that adjust severity when a key size is found on the line.
+//! Patterns are compiled from rule packs (see `rules`).
 
+use crate::lexer;
+use crate::rules;
+use crate::types::*;
 use once_cell::sync::Lazy;
-use regex::Regex;
+use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
-use std::fmt;
-
-/// Severity level for detected cryptographic patterns
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Severity {
-    /// Critical security issue requiring immediate action
-    Critical,
-    /// High priority issue requiring migration planning
-    High,
-    /// Medium priority - deprecated but not urgent
-    Medium,
-    /// Low priority - informational findings
-    Low,
-}
-
-impl fmt::Display for Severity {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Severity::Critical => write!(f, "CRITICAL"),
-            Severity::High => write!(f, "HIGH"),
-            Severity::Medium => write!(f, "MEDIUM"),
-            Severity::Low => write!(f, "LOW"),
-        }
-    }
-}
+use std::collections::HashMap;
+use std::str::FromStr;
 
-/// Category of cryptographic algorithm
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum CryptoCategory {
-    AsymmetricEncryption,
-    KeyExchange,
-    DigitalSignature,
-    Hash,
-    SymmetricEncryption,
-    Random,
+/// Severity override applied when an extracted key size falls below `below`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeySizeTier {
+    /// Exclusive upper bound in bits; `None` matches any remaining size
+    pub below: Option<u32>,
+    pub severity: Severity,
+    pub risk_score: u32,
+    /// Finding message; `{bits}` is replaced with the key size
+    pub message: String,
 }
 
 /// A cryptographic pattern with detection rules
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct CryptoPattern {
+    pub id: String,
     pub name: String,
     pub regex: Regex,
+    pub crypto_type: CryptoType,
+    pub category: CryptoCategory,
+    /// Languages this pattern runs on; empty means all
+    pub languages: Vec<Language>,
     pub severity: Severity,
+    pub risk_score: u32,
+    pub message: String,
     pub recommendation: String,
-    pub category: CryptoCategory,
+    pub cwe: Option<String>,
+    /// Allow matches that start or end in the middle of an identifier segment
+    pub match_inside_identifiers: bool,
+    /// Run on source with string literals kept rather than blanked
+    pub match_string_literals: bool,
+    /// Confidence before context signals are applied (0.0-1.0)
+    pub confidence: f32,
+    /// Rule ids this pattern wins against when their spans overlap
+    pub supersedes: Vec<String>,
+    /// Extracts a key size (capture group 1) from the matching line
+    pub key_size: Option<Regex>,
+    /// Checked in order; the first tier the key size falls under wins
+    pub key_size_tiers: Vec<KeySizeTier>,
 }
 
-/// A detected cryptographic pattern in source code
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Detection {
-    pub crypto_type: String,
-    pub line: usize,
-    pub column: usize,
-    pub matched_text: String,
-    pub severity: Severity,
-    pub recommendation: String,
-    pub category: CryptoCategory,
-    pub context: Option<String>,
+impl CryptoPattern {
+    /// Whether this pattern runs on source in `language`
+    pub fn applies_to(&self, language: Language) -> bool {
+        self.languages.is_empty() || self.languages.contains(&language)
+    }
+
+    /// Run this pattern against one line of (comment-stripped) source,
+    /// returning one finding per occurrence
+    ///
+    /// The reported span is the first capture group that took part in the
+    /// match, or the whole match for patterns without groups, so trailing
+    /// context such as `[^0-9]` is not part of the span. Unless the pattern
+    /// sets `match_inside_identifiers`, spans must sit on identifier segment
+    /// boundaries (see `lexer::on_segment_boundaries`).
+    pub fn detect(&self, line: &str, line_num: usize, language: Language) -> Vec<Vulnerability> {
+        let mut occurrences: Vec<_> = self
+            .regex
+            .captures_iter(line)
+            .filter_map(|caps| {
+                let whole = caps.get(0)?;
+                let span = caps.iter().skip(1).flatten().next().unwrap_or(whole);
+                Some((whole.start(), span.start(), span.end()))
+            })
+            .filter(|&(_, start, end)| {
+                self.match_inside_identifiers
+                    || lexer::on_segment_boundaries(line, start, end, language)
+            })
+            .collect();
+
+        // `Blowfish.MODE_CBC` in `Blowfish.new(key, Blowfish.MODE_CBC)` names the
+        // cipher already reported for the call
+        if occurrences.len() > 1 {
+            // Spans such as `DES.` take the dot with them
+            let qualifier = |&(_, _, end): &(usize, usize, usize)| {
+                let end = if line[..end].ends_with('.') {
+                    end - 1
+                } else {
+                    end
+                };
+                qualifies_constant(&line[end..])
+            };
+            if occurrences.iter().any(|o| !qualifier(o)) {
+                occurrences.retain(|o| !qualifier(o));
+            }
+        }
+
+        occurrences
+            .iter()
+            .enumerate()
+            .map(|(idx, &(match_start, start, end))| {
+                // Look for this occurrence's key size before the next occurrence begins
+                let next_start = occurrences
+                    .get(idx + 1)
+                    .map_or(line.len(), |&(next, _, _)| next);
+                let key_size = self.extract_key_size(&line[..next_start], match_start);
+                self.finding(line, line_num, start, end, key_size)
+            })
+            .collect()
+    }
+
+    fn extract_key_size(&self, haystack: &str, from: usize) -> Option<u32> {
+        self.key_size
+            .as_ref()
+            .and_then(|re| re.captures_at(haystack, from))
+            .and_then(|cap| cap.get(1))
+            .and_then(|m| u32::from_str(m.as_str()).ok())
+    }
+
+    /// Key size in `text`, which must start at this pattern's match
+    pub fn key_size_in(&self, text: &str) -> Option<u32> {
+        self.extract_key_size(text, 0)
+    }
+
+    /// Severity, risk score and message for a finding with `key_size`
+    fn graded(&self, key_size: Option<u32>) -> (Severity, u32, String) {
+        let tier = key_size.and_then(|bits| {
+            self.key_size_tiers
+                .iter()
+                .find(|t| t.below.is_none_or(|below| bits < below))
+                .map(|t| (t, bits))
+        });
+
+        match tier {
+            Some((t, bits)) => (
+                t.severity,
+                t.risk_score,
+                t.message.replace("{bits}", &bits.to_string()),
+            ),
+            None => (self.severity, self.risk_score, self.message.clone()),
+        }
+    }
+
+    /// Set a key size found after detection and re-grade the finding
+    pub fn apply_key_size(&self, vuln: &mut Vulnerability, bits: u32) {
+        let (severity, risk_score, message) = self.graded(Some(bits));
+        vuln.key_size = Some(bits);
+        vuln.severity = severity;
+        vuln.risk_score = risk_score;
+        vuln.message = message;
+    }
+
+    pub(crate) fn finding(
+        &self,
+        line: &str,
+        line_num: usize,
+        start: usize,
+        end: usize,
+        key_size: Option<u32>,
+    ) -> Vulnerability {
+        let (severity, risk_score, message) = self.graded(key_size);
+
+        Vulnerability {
+            crypto_type: self.crypto_type.clone(),
+            category: self.category,
+            severity,
+            risk_score,
+            line: line_num,
+            column: start,
+            span: SourceSpan::from_byte_range(line, start, end),
+            context: line.trim().to_string(),
+            scope: EnclosingScope::default(),
+            message,
+            recommendation: self.recommendation.clone(),
+            key_size,
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: self.id.clone(),
+            cwe: self.cwe.clone(),
+            confidence: self.confidence,
+            explanation: Explanation {
+                rule_id: self.id.clone(),
+                matched_text: line[start..end].to_string(),
+                base_confidence: self.confidence,
+                signals: Vec::new(),
+                algorithm_resolution: None,
+                key_size_resolution: None,
+                transformation: None,
+                command: None,
+                flows_into: None,
+            },
+            suppressed: Vec::new(),
+        }
+    }
+}
+
+/// Whether `rest` starts with an ALL_CAPS member that is not called, as after
+/// the match in `Blowfish.MODE_CBC` or `DES.MODE_ECB`
+fn qualifies_constant(rest: &str) -> bool {
+    let Some(member) = rest.strip_prefix('.') else {
+        return false;
+    };
+    let end = member
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(member.len());
+    let name = &member[..end];
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && !name.contains(char::is_lowercase)
+        && !member[end..].trim_start().starts_with('(')
 }
 
 static CRYPTO_PATTERNS: Lazy<Vec<CryptoPattern>> = Lazy::new(|| {
-    vec![
-        // RSA PATTERNS (keeping your comprehensive detection - it's excellent!)
-        CryptoPattern {
-            name: "RSA Key Generation (<2048 bits)".to_string(),
-            regex: Regex::new(r"(?i)(RSA|generateKeyPair.*RSA|RSA_generate_key|KeyPairGenerator.*RSA).*(?:512|768|1024|1536)").unwrap(),
-            severity: Severity::Critical,
-            recommendation: "Migrate to RSA 4096-bit keys immediately. RSA keys below 2048 bits are cryptographically broken.".to_string(),
-            category: CryptoCategory::AsymmetricEncryption,
-        },
-        CryptoPattern {
-            name: "RSA Key Generation (2048-4096 bits)".to_string(),
-            regex: Regex::new(r"(?i)(RSA|generateKeyPair.*RSA|RSA_generate_key|KeyPairGenerator.*RSA).*(?:2048|3072|4096)").unwrap(),
-            severity: Severity::High,
-            recommendation: "Plan migration to quantum-resistant algorithms (CRYSTALS-Kyber, CRYSTALS-Dilithium).".to_string(),
-            category: CryptoCategory::AsymmetricEncryption,
-        },
-        CryptoPattern {
-            name: "RSA Encryption".to_string(),
-            regex: Regex::new(r"(?i)(RSA[/_]?(PKCS1|OAEP|PSS)|Cipher\.getInstance.*RSA|crypto\.publicEncrypt|RSA_public_encrypt)").unwrap(),
-            severity: Severity::High,
-            recommendation: "Evaluate quantum-resistant alternatives (CRYSTALS-Kyber for encryption).".to_string(),
-            category: CryptoCategory::AsymmetricEncryption,
-        },
-        // All other patterns from detector.rs...
-        CryptoPattern {
-            name: "ECDSA P-256 (secp256r1)".to_string(),
-            regex: Regex::new(r"(?i)(ECDSA|EC|createECDH|ECGenParameterSpec).*(P-?256|secp256r1|prime256v1)").unwrap(),
-            severity: Severity::High,
-            recommendation: "Migrate to quantum-resistant signatures (CRYSTALS-Dilithium).".to_string(),
-            category: CryptoCategory::DigitalSignature,
-        },
-        CryptoPattern {
-            name: "MD5 Hash".to_string(),
-            regex: Regex::new(r"(?i)(MD5|MessageDigest\.getInstance.*MD5|crypto\.createHash.*md5|hashlib\.md5)").unwrap(),
-            severity: Severity::Critical,
-            recommendation: "Replace with SHA-256 or SHA-3 immediately. MD5 is broken.".to_string(),
-            category: CryptoCategory::Hash,
-        },
-        // Add more patterns as needed...
-    ]
+    rules::core_rule_pack()
+        .compile()
+        .expect("Core rule pack failed validation - this is a build.rs bug")
 });
 
-/// Detect cryptographic patterns in source code
-pub fn detect_patterns(content: &str, _language: Option<&str>) -> Vec<Detection> {
-    let mut detections = Vec::new();
-    let lines: Vec<&str> = content.lines().collect();
-
-    for pattern in CRYPTO_PATTERNS.iter() {
-        for line_match in pattern.regex.find_iter(content) {
-            let match_start = line_match.start();
-            let (line_num, column) = calculate_position(content, match_start);
-            let context = extract_context(&lines, line_num, 2);
-
-            detections.push(Detection {
-                crypto_type: pattern.name.clone(),
-                line: line_num + 1,
-                column: column + 1,
-                matched_text: line_match.as_str().to_string(),
-                severity: pattern.severity.clone(),
-                recommendation: pattern.recommendation.clone(),
-                category: pattern.category.clone(),
-                context: Some(context),
-            });
-        }
-    }
+/// Built-in detection rules, in evaluation order
+pub fn builtin_patterns() -> &'static [CryptoPattern] {
+    &CRYPTO_PATTERNS
+}
 
-    detections.sort_by(|a, b| {
-        let severity_order = |s: &Severity| match s {
-            Severity::Critical => 0,
-            Severity::High => 1,
-            Severity::Medium => 2,
-            Severity::Low => 3,
-        };
-        severity_order(&a.severity)
-            .cmp(&severity_order(&b.severity))
-            .then(a.line.cmp(&b.line))
-    });
+/// Run every pattern that applies to `language` against one line of source.
+/// Spans in the returned findings are relative to `line`.
+pub fn detect_line(
+    patterns: &[CryptoPattern],
+    language: Language,
+    line: &str,
+    line_num: usize,
+) -> Vec<Vulnerability> {
+    patterns
+        .iter()
+        .filter(|p| p.applies_to(language))
+        .flat_map(|p| p.detect(line, line_num, language))
+        .collect()
+}
 
-    detections
+/// All of a rule list's regexes in one `RegexSet` per source text, so a
+/// statement is searched once for every rule and only the rules that match
+/// somewhere in it run their own captures, key-size and boundary checks
+#[derive(Debug, Clone)]
+pub struct Prefilter {
+    /// Rules that run on the text with string literals blanked
+    scan: RuleSet,
+    /// Rules that set `match_string_literals`
+    literal: RuleSet,
 }
 
-fn calculate_position(content: &str, offset: usize) -> (usize, usize) {
-    let mut line = 0;
-    let mut column = 0;
-    for (idx, ch) in content.char_indices() {
-        if idx >= offset {
-            break;
+#[derive(Debug, Clone)]
+struct RuleSet {
+    /// `None` if the combined set is too large to build; every rule is then a candidate
+    set: Option<RegexSet>,
+    /// Index into the rule list of each regex in `set`
+    rules: Vec<usize>,
+}
+
+impl RuleSet {
+    fn new<'a>(rules: impl Iterator<Item = (usize, &'a CryptoPattern)>) -> Self {
+        let (rules, sources): (Vec<usize>, Vec<&str>) =
+            rules.map(|(idx, p)| (idx, p.regex.as_str())).unzip();
+        Self {
+            set: RegexSet::new(sources).ok(),
+            rules,
         }
-        if ch == '\n' {
-            line += 1;
-            column = 0;
-        } else {
-            column += 1;
+    }
+
+    fn matching(&self, text: &str, candidates: &mut Vec<usize>) {
+        match &self.set {
+            // Most statements match no rule, which `is_match` finds out faster
+            Some(set) if !set.is_match(text) => {}
+            Some(set) => candidates.extend(set.matches(text).into_iter().map(|i| self.rules[i])),
+            None => candidates.extend(&self.rules),
         }
     }
-    (line, column)
 }
 
-fn extract_context(lines: &[&str], line_num: usize, context_lines: usize) -> String {
-    let start = line_num.saturating_sub(context_lines);
-    let end = (line_num + context_lines + 1).min(lines.len());
-    lines[start..end].join("\n")
+impl Prefilter {
+    pub fn new(patterns: &[CryptoPattern]) -> Self {
+        let indexed = || patterns.iter().enumerate();
+        Self {
+            scan: RuleSet::new(indexed().filter(|(_, p)| !p.match_string_literals)),
+            literal: RuleSet::new(indexed().filter(|(_, p)| p.match_string_literals)),
+        }
+    }
+
+    /// Indices of the rules whose regex matches somewhere in the statement,
+    /// in rule order
+    pub fn candidates(&self, scan_text: &str, literal_text: &str) -> Vec<usize> {
+        let mut candidates = Vec::new();
+        self.scan.matching(scan_text, &mut candidates);
+        self.literal.matching(literal_text, &mut candidates);
+        candidates.sort_unstable();
+        candidates
+    }
 }
 
-pub fn filter_by_severity(detections: &[Detection], min_severity: Severity) -> Vec<Detection> {
-    let severity_threshold = match min_severity {
-        Severity::Critical => 0,
-        Severity::High => 1,
-        Severity::Medium => 2,
-        Severity::Low => 3,
-    };
-    detections
-        .iter()
-        .filter(|d| {
-            let detection_level = match d.severity {
-                Severity::Critical => 0,
-                Severity::High => 1,
-                Severity::Medium => 2,
-                Severity::Low => 3,
+static BUILTIN_PREFILTER: Lazy<Prefilter> = Lazy::new(|| Prefilter::new(builtin_patterns()));
+
+/// Prefilter for `builtin_patterns`
+pub fn builtin_prefilter() -> &'static Prefilter {
+    &BUILTIN_PREFILTER
+}
+
+/// Run every pattern that applies to `language` against one statement, given
+/// twice: as `scan_text`, with comments and most string literals blanked, and
+/// as `literal_text`, with only comments blanked, for patterns that set
+/// `match_string_literals`. Both must have the same length, and spans in the
+/// returned findings are relative to them. `prefilter` must have been built
+/// from `patterns`.
+pub fn detect_statement(
+    patterns: &[CryptoPattern],
+    prefilter: &Prefilter,
+    language: Language,
+    scan_text: &str,
+    literal_text: &str,
+    line_num: usize,
+) -> Vec<Vulnerability> {
+    prefilter
+        .candidates(scan_text, literal_text)
+        .into_iter()
+        .map(|idx| &patterns[idx])
+        .filter(|p| p.applies_to(language))
+        .flat_map(|p| {
+            let text = if p.match_string_literals {
+                literal_text
+            } else {
+                scan_text
             };
-            detection_level <= severity_threshold
+            p.detect(text, line_num, language)
         })
-        .cloned()
         .collect()
 }
 
-pub fn group_by_category(
-    detections: &[Detection],
-) -> std::collections::HashMap<CryptoCategory, Vec<Detection>> {
-    use std::collections::HashMap;
-    let mut grouped: HashMap<CryptoCategory, Vec<Detection>> = HashMap::new();
-    for detection in detections {
-        grouped
-            .entry(detection.category.clone())
-            .or_default()
-            .push(detection.clone());
+/// Resolve findings whose spans overlap to the most specific algorithm.
+///
+/// A finding beats an overlapping one if its rule `supersedes` the other's;
+/// otherwise the longer span wins, then the rule listed first in `patterns`.
+/// Losing candidates are kept on the winner as `suppressed` metadata. Cipher
+/// mode and IV findings (`DES/ECB/...`) qualify the algorithm they overlap, so
+/// they only compete with each other.
+///
+/// A library call reports its algorithm for the whole call, so a rule also
+/// beats the rules it `supersedes` for the same algorithm anywhere in the
+/// statement: the `rsa` argument of `RSA_generate_key_ex(rsa, ...)` and the
+/// `DES_ENCRYPT` flag of `DES_ecb_encrypt` are part of the call's finding.
+pub fn resolve_overlaps(
+    patterns: &[CryptoPattern],
+    mut candidates: Vec<Vulnerability>,
+) -> Vec<Vulnerability> {
+    let rank = |rule_id: &str| patterns.iter().position(|p| p.id == rule_id);
+    let supersedes = |winner: &Vulnerability, loser: &Vulnerability| {
+        patterns
+            .iter()
+            .find(|p| p.id == winner.rule_id)
+            .is_some_and(|p| p.supersedes.contains(&loser.rule_id))
+    };
+    let beats = |a: &Vulnerability, b: &Vulnerability| {
+        if supersedes(a, b) {
+            return true;
+        }
+        if supersedes(b, a) {
+            return false;
+        }
+        let len = |v: &Vulnerability| v.span.byte_end - v.span.byte_start;
+        len(a) > len(b) || (len(a) == len(b) && rank(&a.rule_id) < rank(&b.rule_id))
+    };
+    let competes = |a: &Vulnerability, b: &Vulnerability| {
+        let overlaps = a.span.byte_start < b.span.byte_end && b.span.byte_start < a.span.byte_end;
+        let same_call = a.crypto_type == b.crypto_type && (supersedes(a, b) || supersedes(b, a));
+        a.crypto_type.is_algorithm() == b.crypto_type.is_algorithm() && (overlaps || same_call)
+    };
+
+    // Repeatedly drop the first losing candidate of any overlapping pair
+    'resolve: loop {
+        for i in 0..candidates.len() {
+            for j in 0..candidates.len() {
+                if i != j
+                    && competes(&candidates[i], &candidates[j])
+                    && beats(&candidates[i], &candidates[j])
+                {
+                    let loser = candidates.remove(j);
+                    let winner = &mut candidates[if j < i { i - 1 } else { i }];
+                    winner.suppressed.push(SuppressedMatch {
+                        rule_id: loser.rule_id,
+                        crypto_type: loser.crypto_type,
+                        span: loser.span,
+                    });
+                    winner.suppressed.extend(loser.suppressed);
+                    continue 'resolve;
+                }
+            }
+        }
+        break;
+    }
+
+    candidates.sort_by_key(|v| v.span.byte_start);
+    candidates
+}
+
+/// Findings at or above `min_severity`
+pub fn filter_by_severity(result: &AuditResult, min_severity: Severity) -> Vec<&Vulnerability> {
+    result
+        .vulnerabilities
+        .iter()
+        .filter(|v| v.severity >= min_severity)
+        .collect()
+}
+
+/// Findings grouped by algorithm category
+pub fn group_by_category(result: &AuditResult) -> HashMap<CryptoCategory, Vec<&Vulnerability>> {
+    let mut grouped: HashMap<CryptoCategory, Vec<&Vulnerability>> = HashMap::new();
+    for vuln in &result.vulnerabilities {
+        grouped.entry(vuln.category).or_default().push(vuln);
     }
     grouped
 }
@@ -214,28 +428,249 @@ pub struct DetectionSummary {
     pub high: usize,
     pub medium: usize,
     pub low: usize,
-    pub by_category: std::collections::HashMap<String, usize>,
+    pub by_category: HashMap<String, usize>,
 }
 
-pub fn generate_summary(detections: &[Detection]) -> DetectionSummary {
-    use std::collections::HashMap;
+pub fn generate_summary(result: &AuditResult) -> DetectionSummary {
     let mut summary = DetectionSummary {
-        total: detections.len(),
+        total: result.vulnerabilities.len(),
         critical: 0,
         high: 0,
         medium: 0,
         low: 0,
         by_category: HashMap::new(),
     };
-    for detection in detections {
-        match detection.severity {
+    for vuln in &result.vulnerabilities {
+        match vuln.severity {
             Severity::Critical => summary.critical += 1,
             Severity::High => summary.high += 1,
             Severity::Medium => summary.medium += 1,
             Severity::Low => summary.low += 1,
         }
-        let category_name = format!("{:?}", detection.category);
+        let category_name = format!("{:?}", vuln.category);
         *summary.by_category.entry(category_name).or_insert(0) += 1;
     }
     summary
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(line: &str, crypto_type: CryptoType) -> Option<Vulnerability> {
+        detect_line(builtin_patterns(), Language::Python, line, 1)
+            .into_iter()
+            .find(|v| v.crypto_type == crypto_type)
+    }
+
+    #[test]
+    fn test_builtin_pattern_ids_unique() {
+        let mut ids: Vec<_> = builtin_patterns().iter().map(|p| p.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), builtin_patterns().len());
+    }
+
+    #[test]
+    fn test_prefilter_candidates() {
+        let patterns = builtin_patterns();
+        let ids = |text: &str| -> Vec<&str> {
+            builtin_prefilter()
+                .candidates(text, text)
+                .into_iter()
+                .map(|idx| patterns[idx].id.as_str())
+                .collect()
+        };
+        assert!(ids("let total = items.len();").is_empty());
+        assert!(ids("digest = hashlib.md5(data)").contains(&"md5"));
+        // Rules that see string literals run on the literal text
+        assert!(ids(r#"{"alg": "RS256"}"#).contains(&"jose-rsa"));
+    }
+
+    #[test]
+    fn test_detect_rsa_1024() {
+        let vuln = detect("RSA.generate(1024)", CryptoType::Rsa).unwrap();
+        assert_eq!(vuln.severity, Severity::Critical);
+        assert_eq!(vuln.key_size, Some(1024));
+        assert_eq!(vuln.category, CryptoCategory::AsymmetricEncryption);
+        assert!(vuln.message.contains("1024-bit"));
+    }
+
+    #[test]
+    fn test_detect_rsa_2048() {
+        let line = "generateKeyPair('rsa', { modulusLength: 2048 })";
+        let vuln = detect(line, CryptoType::Rsa).unwrap();
+        assert_eq!(vuln.severity, Severity::High);
+        assert_eq!(vuln.key_size, Some(2048));
+        assert_eq!(vuln.risk_score, 85);
+    }
+
+    #[test]
+    fn test_detect_rsa_4096_tier() {
+        let vuln = detect("RSA.generate(4096)", CryptoType::Rsa).unwrap();
+        assert_eq!(vuln.risk_score, 80);
+    }
+
+    #[test]
+    fn test_detect_rsa_public_encrypt() {
+        let vuln = detect("crypto.publicEncrypt(key, buf)", CryptoType::Rsa).unwrap();
+        assert_eq!(vuln.key_size, None);
+        assert_eq!(vuln.column, 0);
+    }
+
+    #[test]
+    fn test_detect_every_occurrence_with_own_key_size() {
+        let line = "a = RSA(1024); b = RSA(4096)";
+        let found: Vec<_> = detect_line(builtin_patterns(), Language::Python, line, 1)
+            .into_iter()
+            .filter(|v| v.crypto_type == CryptoType::Rsa)
+            .collect();
+
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].column, found[0].key_size), (4, Some(1024)));
+        assert_eq!(found[0].severity, Severity::Critical);
+        assert_eq!((found[1].column, found[1].key_size), (19, Some(4096)));
+        assert_eq!(found[1].risk_score, 80);
+        for vuln in &found {
+            assert_eq!(&line[vuln.span.byte_start..vuln.span.byte_end], "RSA");
+        }
+    }
+
+    #[test]
+    fn test_span_excludes_trailing_context() {
+        let vuln = detect("digest = sha1(data)", CryptoType::Sha1).unwrap();
+        assert_eq!((vuln.span.byte_start, vuln.span.byte_end), (9, 13));
+    }
+
+    /// Resolved findings for one line, as `(crypto type, suppressed rule ids)`
+    fn resolved(line: &str) -> Vec<(CryptoType, Vec<String>)> {
+        let candidates = detect_line(builtin_patterns(), Language::Python, line, 1);
+        resolve_overlaps(builtin_patterns(), candidates)
+            .into_iter()
+            .map(|v| {
+                let suppressed = v.suppressed.into_iter().map(|s| s.rule_id).collect();
+                (v.crypto_type, suppressed)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_overlap_ecdsa_over_dsa() {
+        assert_eq!(
+            resolved("var key = ECDsa.Create();"),
+            vec![(
+                CryptoType::Ecdsa,
+                vec!["ecdsa".to_string(), "dsa".to_string()]
+            )]
+        );
+        // The DSA pattern cannot match inside the ECDSA acronym at all
+        assert_eq!(
+            resolved("key = ECDSA.generate(curve)"),
+            vec![(CryptoType::Ecdsa, vec![])]
+        );
+    }
+
+    #[test]
+    fn test_overlap_ecdh_over_diffie_hellman() {
+        // The `ecdh` variable is part of the .NET call's finding too
+        assert_eq!(
+            resolved("var ecdh = ECDiffieHellman.Create();"),
+            vec![(
+                CryptoType::Ecdh,
+                vec![
+                    "ecdh".to_string(),
+                    "ecdh".to_string(),
+                    "diffie-hellman".to_string()
+                ]
+            )]
+        );
+        assert_eq!(
+            resolved("suite = ECDH_RSA"),
+            vec![(CryptoType::Ecdh, vec![]), (CryptoType::Rsa, vec![])]
+        );
+        assert_eq!(resolved("kex = ECDHE"), vec![(CryptoType::Ecdh, vec![])]);
+    }
+
+    #[test]
+    fn test_overlap_ecdh_over_ecdsa_and_diffie_hellman() {
+        let found = resolved("mode = elliptic_curve_diffie_hellman");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, CryptoType::Ecdh);
+        assert!(found[0].1.contains(&"ecdsa".to_string()));
+        assert!(found[0].1.contains(&"diffie-hellman".to_string()));
+    }
+
+    #[test]
+    fn test_overlap_triple_des_over_des() {
+        assert_eq!(
+            resolved("cipher = TripleDES.new(key)"),
+            vec![(CryptoType::TripleDes, vec!["des".to_string()])]
+        );
+    }
+
+    #[test]
+    fn test_non_overlapping_findings_are_kept() {
+        let found = resolved("sig = ECDSA(k); h = md5(x)");
+        assert_eq!(
+            found,
+            vec![(CryptoType::Ecdsa, vec![]), (CryptoType::Md5, vec![])]
+        );
+    }
+
+    #[test]
+    fn test_detect_ecdsa() {
+        let vuln = detect("crypto.createSign('ecdsa-with-SHA256')", CryptoType::Ecdsa).unwrap();
+        assert_eq!(vuln.severity, Severity::High);
+        assert_eq!(vuln.category, CryptoCategory::DigitalSignature);
+    }
+
+    #[test]
+    fn test_detect_md5() {
+        let vuln = detect("hashlib.md5(data).hexdigest()", CryptoType::Md5).unwrap();
+        assert_eq!(vuln.severity, Severity::Critical);
+        assert_eq!(vuln.risk_score, 100);
+        assert_eq!(vuln.column, 8);
+        assert_eq!(vuln.category, CryptoCategory::Hash);
+    }
+
+    fn sample_result() -> AuditResult {
+        let mut result = AuditResult::new(Language::Python, 3);
+        for line in ["hashlib.md5(x)", "RSA.generate(2048)", "DES.new(key)"] {
+            for vuln in detect_line(builtin_patterns(), Language::Python, line, 1) {
+                result.add_vulnerability(vuln);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_filter_by_severity() {
+        let result = sample_result();
+        let critical = filter_by_severity(&result, Severity::Critical);
+        assert!(critical.iter().all(|v| v.severity == Severity::Critical));
+        assert_eq!(critical.len(), result.stats.critical_count);
+        assert_eq!(
+            filter_by_severity(&result, Severity::Low).len(),
+            result.vulnerabilities.len()
+        );
+    }
+
+    #[test]
+    fn test_group_by_category() {
+        let result = sample_result();
+        let grouped = group_by_category(&result);
+        assert!(grouped.contains_key(&CryptoCategory::Hash));
+        assert!(grouped.contains_key(&CryptoCategory::AsymmetricEncryption));
+        assert!(grouped.contains_key(&CryptoCategory::SymmetricEncryption));
+    }
+
+    #[test]
+    fn test_generate_summary() {
+        let result = sample_result();
+        let summary = generate_summary(&result);
+        assert_eq!(summary.total, result.stats.total_vulnerabilities);
+        assert_eq!(summary.critical, result.stats.critical_count);
+        assert_eq!(summary.high, result.stats.high_count);
+        assert_eq!(summary.by_category.get("Hash"), Some(&1));
+    }
+}
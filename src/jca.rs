@@ -0,0 +1,129 @@
+//! Java Cryptography Architecture (JCA)
+//!
+//! Java, Kotlin and Scala all request algorithms from JCA providers by name,
+//! e.g. `Cipher.getInstance("DES/ECB/PKCS5Padding")`, so the `jca-*` rules in
+//! the core pack run on every JVM language. Cipher names are transformations;
+//! this module splits them into algorithm, mode and padding. Key sizes are set
+//! on the generator after it is created (`kpg.initialize(2048)`), so this
+//! module also finds that call for a generator variable.
+
+use crate::types::Transformation;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+lazy_static! {
+    // `KeyPairGenerator kpg = KeyPairGenerator.getInstance(`, `val kg: KeyGenerator = KeyGenerator.getInstance(`
+    static ref GENERATOR_VARIABLE: Regex = Regex::new(
+        r"([A-Za-z_$][\w$]*)\s*(?::\s*[\w.]+\s*)?=\s*(?:[\w.]+\.)?(?:KeyPairGenerator|KeyGenerator)\.getInstance\s*\("
+    ).expect("GENERATOR_VARIABLE: Invalid regex - this is a compile-time bug");
+
+    // AES names no key size; only `init(...)` on the generator does
+    static ref AES_KEY_GENERATOR: Regex = Regex::new(
+        r#"\bKeyGenerator\.getInstance\s*\(\s*"(AES)"\s*[,)]"#
+    ).expect("AES_KEY_GENERATOR: Invalid regex - this is a compile-time bug");
+}
+
+/// Split an `algorithm/mode/padding` transformation into its parts.
+///
+/// Returns `None` for bare algorithm names and anything that is not a
+/// three-part transformation.
+pub fn parse_transformation(text: &str) -> Option<Transformation> {
+    let mut parts = text.split('/').map(str::trim);
+    let (algorithm, mode, padding) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || [algorithm, mode, padding].iter().any(|p| p.is_empty()) {
+        return None;
+    }
+
+    Some(Transformation {
+        algorithm: algorithm.to_string(),
+        mode: Some(mode.to_string()),
+        padding: Some(padding.to_string()),
+    })
+}
+
+/// The variable a `KeyPairGenerator` or `KeyGenerator` created in `statement`
+/// is assigned to
+pub fn generator_variable(statement: &str) -> Option<&str> {
+    GENERATOR_VARIABLE
+        .captures(statement)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str())
+}
+
+/// The key size argument of the first `initialize(...)` or `init(...)` call on
+/// `variable` in `lines` `from..=to` (1-based line numbers), with the line it
+/// is on: `2048` or `KEY_SIZE` in `kpg.initialize(KEY_SIZE)`
+pub fn generator_init<'a>(
+    lines: &[&'a str],
+    variable: &str,
+    from: usize,
+    to: usize,
+) -> Option<(usize, &'a str)> {
+    let call = Regex::new(&format!(
+        r"(?:^|[^\w$.]){}\s*\.\s*init(?:ialize)?\s*\(\s*([\w$.]+)",
+        regex::escape(variable)
+    ))
+    .expect("variable names are escaped");
+
+    (from..=to.min(lines.len())).find_map(|line_num| {
+        let caps = call.captures(lines[line_num - 1])?;
+        Some((line_num, caps.get(1)?.as_str()))
+    })
+}
+
+/// Byte range of `AES` in a `KeyGenerator.getInstance("AES")` call in `statement`
+pub fn aes_key_generator(statement: &str) -> Option<Range<usize>> {
+    AES_KEY_GENERATOR
+        .captures(statement)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.range())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transformation() {
+        let t = parse_transformation("DES/ECB/PKCS5Padding").unwrap();
+        assert_eq!(t.algorithm, "DES");
+        assert_eq!(t.mode.as_deref(), Some("ECB"));
+        assert_eq!(t.padding.as_deref(), Some("PKCS5Padding"));
+        assert_eq!(t.to_string(), "DES in ECB mode with PKCS5Padding");
+
+        assert_eq!(
+            parse_transformation("RSA/ECB/OAEPWithSHA-256AndMGF1Padding")
+                .unwrap()
+                .padding
+                .as_deref(),
+            Some("OAEPWithSHA-256AndMGF1Padding")
+        );
+
+        assert!(parse_transformation("DESede").is_none());
+        assert!(parse_transformation("AES/GCM").is_none());
+        assert!(parse_transformation("AES//NoPadding").is_none());
+        assert!(parse_transformation("a/b/c/d").is_none());
+    }
+
+    #[test]
+    fn test_generator_init() {
+        let lines = [
+            r#"KeyGenerator kg = KeyGenerator.getInstance("AES");"#,
+            "other.init(256);",
+            "kg.init(128, new SecureRandom());",
+            "kg.init(KEY_BITS);",
+        ];
+        let variable = generator_variable(lines[0]).unwrap();
+        assert_eq!(variable, "kg");
+        assert_eq!(generator_init(&lines, variable, 1, 4), Some((3, "128")));
+        assert_eq!(
+            generator_init(&lines, variable, 4, 9),
+            Some((4, "KEY_BITS"))
+        );
+        assert_eq!(aes_key_generator(lines[0]), Some(44..47));
+
+        let kotlin = "val kpg: KeyPairGenerator = KeyPairGenerator.getInstance(ALG)";
+        assert_eq!(generator_variable(kotlin), Some("kpg"));
+    }
+}
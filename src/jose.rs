@@ -0,0 +1,310 @@
+//! JSON Web Keys
+//!
+//! JWT signing and encryption keys are published as JWKs, alone or in a JWK
+//! Set such as `/.well-known/jwks.json`. A key's algorithm and size are not
+//! spelled out in any algorithm name: `kty` gives the family, `crv` the curve,
+//! and for RSA the length of the base64url-encoded modulus `n` gives the key
+//! size. This module reads those members from each JWK object and reports the
+//! key with the generic rule for its algorithm, like a key generated in code.
+
+use crate::detector::CryptoPattern;
+use crate::types::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+lazy_static! {
+    // `"kty": "RSA"`, `kty: 'EC'`
+    static ref KEY_TYPE: Regex = Regex::new(r#"(?:["']|\b)kty["']?\s*:\s*["'](\w+)["']"#)
+        .expect("KEY_TYPE: Invalid regex - this is a compile-time bug");
+
+    // The other members a key's algorithm and size depend on
+    static ref MEMBER: Regex = Regex::new(
+        r#"(?:["']|\b)(crv|alg|use|n|k)["']?\s*:\s*["']([\w+/=.-]*)["']"#
+    ).expect("MEMBER: Invalid regex - this is a compile-time bug");
+}
+
+/// A public or secret key found in a JWK or JWK Set
+#[derive(Debug, Clone)]
+pub struct JsonWebKey {
+    /// `kty` member: `RSA`, `EC`, `OKP` or `oct`
+    pub key_type: String,
+    /// `crv` member of `EC` and `OKP` keys
+    pub curve: Option<String>,
+    /// `alg` member
+    pub algorithm: Option<String>,
+    pub crypto_type: CryptoType,
+    /// Modulus or curve size in bits
+    pub key_size: Option<u32>,
+    /// Length of an `oct` key in bytes
+    pub secret_bytes: Option<usize>,
+    /// Whether the key encrypts (`use: enc`, `RSA-OAEP`, `ECDH-ES`) rather than signs
+    pub encryption: bool,
+    /// Byte range of the `kty` value
+    pub start: usize,
+    pub end: usize,
+    /// Byte range of the JWK object
+    pub object: Range<usize>,
+}
+
+impl JsonWebKey {
+    /// Finding for this key from the generic rule for its algorithm, with
+    /// spans relative to `statement`, which starts at byte `offset` of the
+    /// text the key was scanned from
+    pub fn finding(
+        &self,
+        patterns: &[CryptoPattern],
+        statement: &str,
+        offset: usize,
+        line_num: usize,
+    ) -> Option<Vulnerability> {
+        let pattern = patterns
+            .iter()
+            .find(|p| p.crypto_type == self.crypto_type && p.languages.is_empty())?;
+        let mut vuln = pattern.finding(
+            statement,
+            line_num,
+            self.start - offset,
+            self.end - offset,
+            self.key_size,
+        );
+        vuln.rule_id = format!("jwk-{}", self.key_type.to_ascii_lowercase());
+        vuln.explanation.rule_id = vuln.rule_id.clone();
+        if let (Some(bytes), Some(alg)) = (self.secret_bytes, &self.algorithm) {
+            vuln.message = format!(
+                "JWK `oct` key for {} is {} bytes, shorter than the {}-byte minimum of RFC 7518 section 3.2",
+                alg,
+                bytes,
+                hmac_min_bytes(alg).unwrap_or_default()
+            );
+        }
+        if let Some(recommendation) = self.recommendation() {
+            vuln.recommendation = recommendation.to_string();
+        }
+        Some(vuln)
+    }
+
+    fn recommendation(&self) -> Option<&'static str> {
+        match self.crypto_type {
+            CryptoType::Rsa | CryptoType::Ecdh if self.encryption => Some(
+                "Plan token encryption on ML-KEM as JOSE support lands, rotating keys through the \
+                 key set; until then prefer RSA-OAEP-256 or ECDH-ES with P-384",
+            ),
+            CryptoType::Rsa | CryptoType::Ecdsa => Some(
+                "Publish an ML-DSA key (JOSE `ML-DSA-65`, `kty: AKP`) in the key set next to this \
+                 one and move token signing to it as verifiers add support",
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Keys in every JWK object in `text`, which should keep its string literals
+pub fn scan(text: &str) -> Vec<JsonWebKey> {
+    KEY_TYPE
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let kty = caps.get(1)?;
+            let object = enclosing_object(text, kty.start())?;
+            key(text, kty.as_str(), kty.range(), object)
+        })
+        .collect()
+}
+
+fn key(text: &str, key_type: &str, kty: Range<usize>, object: Range<usize>) -> Option<JsonWebKey> {
+    let member = |name: &str| {
+        MEMBER
+            .captures_iter(&text[object.clone()])
+            .find(|caps| &caps[1] == name)
+            .map(|caps| caps[2].to_string())
+    };
+    let curve = member("crv");
+    let algorithm = member("alg");
+    let encryption = member("use").as_deref() == Some("enc")
+        || algorithm.as_deref().is_some_and(|alg| {
+            alg.starts_with("RSA-OAEP") || alg.starts_with("RSA1_5") || alg.starts_with("ECDH")
+        });
+
+    let mut secret_bytes = None;
+    let (crypto_type, key_size) = match key_type {
+        "RSA" => (CryptoType::Rsa, member("n").and_then(|n| modulus_bits(&n))),
+        "EC" | "OKP" => {
+            let (agreement, bits) = curve_size(curve.as_deref()?)?;
+            let crypto_type = if agreement || encryption {
+                CryptoType::Ecdh
+            } else {
+                CryptoType::Ecdsa
+            };
+            (crypto_type, Some(bits))
+        }
+        // Secret keys only matter when they are too short for their HMAC
+        "oct" => {
+            let min = hmac_min_bytes(algorithm.as_deref()?)?;
+            let bytes = decode(&member("k")?)?.len();
+            if bytes >= min {
+                return None;
+            }
+            secret_bytes = Some(bytes);
+            (CryptoType::WeakJwtSecret, None)
+        }
+        _ => return None,
+    };
+
+    Some(JsonWebKey {
+        key_type: key_type.to_string(),
+        curve,
+        algorithm,
+        crypto_type,
+        key_size,
+        secret_bytes,
+        encryption,
+        start: kty.start,
+        end: kty.end,
+        object,
+    })
+}
+
+/// The innermost `{...}` around byte `at`
+fn enclosing_object(text: &str, at: usize) -> Option<Range<usize>> {
+    let mut depth = 0usize;
+    let start = text[..at].char_indices().rev().find_map(|(i, c)| match c {
+        '}' => {
+            depth += 1;
+            None
+        }
+        '{' if depth == 0 => Some(i),
+        '{' => {
+            depth -= 1;
+            None
+        }
+        _ => None,
+    })?;
+    let end = text[at..].char_indices().find_map(|(i, c)| match c {
+        '{' => {
+            depth += 1;
+            None
+        }
+        '}' if depth == 0 => Some(at + i + 1),
+        '}' => {
+            depth -= 1;
+            None
+        }
+        _ => None,
+    })?;
+    Some(start..end)
+}
+
+/// Size of an RSA modulus from its base64url encoding, ignoring leading zeros
+fn modulus_bits(n: &str) -> Option<u32> {
+    let bytes = decode(n)?;
+    let first = bytes.iter().position(|&b| b != 0)?;
+    let bits = (bytes.len() - first) * 8 - bytes[first].leading_zeros() as usize;
+    u32::try_from(bits).ok()
+}
+
+/// Whether a curve is for key agreement only, and its size in bits
+fn curve_size(crv: &str) -> Option<(bool, u32)> {
+    match crv {
+        "P-256" | "secp256k1" | "Ed25519" => Some((false, 256)),
+        "P-384" => Some((false, 384)),
+        "P-521" => Some((false, 521)),
+        "Ed448" => Some((false, 448)),
+        "X25519" => Some((true, 256)),
+        "X448" => Some((true, 448)),
+        _ => None,
+    }
+}
+
+/// Shortest key RFC 7518 allows for an HMAC `alg`: the hash output size
+fn hmac_min_bytes(alg: &str) -> Option<usize> {
+    match alg {
+        "HS256" => Some(32),
+        "HS384" => Some(48),
+        "HS512" => Some(64),
+        _ => None,
+    }
+}
+
+/// Decode base64url (or standard base64), with or without padding
+fn decode(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in value.trim_end_matches('=').bytes() {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' | b'+' => 62,
+            b'_' | b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(sextet);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A base64url modulus of `chars` characters starting with `lead`
+    fn modulus(lead: &str, chars: usize) -> String {
+        format!("{}{}", lead, "A".repeat(chars - lead.len()))
+    }
+
+    #[test]
+    fn test_rsa_modulus_size() {
+        assert_eq!(modulus_bits(&modulus("g", 342)), Some(2048));
+        assert_eq!(modulus_bits(&modulus("g", 171)), Some(1024));
+        assert_eq!(modulus_bits(&modulus("g", 683)), Some(4096));
+        // Only the significant bits count, not a leading zero byte some encoders add
+        assert_eq!(modulus_bits(&modulus("Q", 342)), Some(2047));
+        assert_eq!(modulus_bits(&modulus("AI", 343)), Some(2048));
+        assert_eq!(modulus_bits("not base64!"), None);
+    }
+
+    #[test]
+    fn test_jwks_keys() {
+        let jwks = format!(
+            r#"{{"keys": [
+  {{"kty": "RSA", "kid": "a", "use": "sig", "alg": "RS256", "n": "{}", "e": "AQAB"}},
+  {{"kty": "EC", "crv": "P-384", "x": "abc", "y": "def"}},
+  {{"kty": "OKP", "crv": "X25519", "x": "abc"}},
+  {{"kty": "oct", "alg": "HS256", "k": "c2VjcmV0"}},
+  {{"kty": "oct", "alg": "A128KW", "k": "c2VjcmV0"}}
+]}}"#,
+            modulus("g", 342)
+        );
+        let keys = scan(&jwks);
+        let found: Vec<_> = keys
+            .iter()
+            .map(|k| (k.key_type.as_str(), k.crypto_type.clone(), k.key_size))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("RSA", CryptoType::Rsa, Some(2048)),
+                ("EC", CryptoType::Ecdsa, Some(384)),
+                ("OKP", CryptoType::Ecdh, Some(256)),
+                ("oct", CryptoType::WeakJwtSecret, None),
+            ]
+        );
+        assert_eq!(&jwks[keys[0].start..keys[0].end], "RSA");
+        assert!(jwks[keys[0].object.clone()].contains("\"alg\": \"RS256\""));
+        assert_eq!(keys[3].secret_bytes, Some(6));
+    }
+
+    #[test]
+    fn test_encryption_keys_use_key_agreement() {
+        let keys = scan(r#"{ kty: 'EC', crv: 'P-256', use: 'enc', x: 'abc' }"#);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].crypto_type, CryptoType::Ecdh);
+        assert!(keys[0].encryption);
+    }
+}
@@ -0,0 +1,341 @@
+//! Language Detection
+//!
+//! Chooses the language of a file from its modeline, file name, extension,
+//! `#!` interpreter line and, when nothing else applies, syntax that is
+//! characteristic of one language. Each heuristic yields a candidate with a
+//! confidence; the most confident candidate wins, and ties go to the more
+//! reliable method.
+
+use crate::types::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+// Confidence per heuristic
+const MODELINE: f32 = 0.95;
+const FILENAME: f32 = 0.95;
+const EXTENSION: f32 = 0.9;
+const AMBIGUOUS_EXTENSION: f32 = 0.7;
+const SHEBANG: f32 = 0.85;
+
+/// Lines at the start and end of a file searched for modelines
+const MODELINE_LINES: usize = 5;
+
+lazy_static! {
+    // `vim: set ft=python:`, `vi: filetype=sh`, `ex: syntax=cpp`
+    static ref VIM_MODELINE: Regex = Regex::new(
+        r"(?:^|\s)(?:vim?|ex)(?:[<=>]?\d+)?:.*?\b(?:ft|filetype|syn|syntax)=([\w+#-]+)"
+    ).expect("VIM_MODELINE: Invalid regex - this is a compile-time bug");
+
+    // `-*- python -*-`, `-*- mode: ruby; coding: utf-8 -*-`
+    static ref EMACS_MODELINE: Regex = Regex::new(
+        r"-\*-\s*(?:.*?\bmode:\s*)?([\w+#-]+)\s*(?:;.*)?-\*-"
+    ).expect("EMACS_MODELINE: Invalid regex - this is a compile-time bug");
+
+    // Objective-C syntax that distinguishes an `.h` header from C/C++
+    static ref OBJC_SYNTAX: Regex = Regex::new(
+        r"(?m)^\s*(?:@(?:interface|protocol|implementation)\s+\w+|#\s*import\s*<(?:Foundation|UIKit|AppKit|Cocoa)/)"
+    ).expect("OBJC_SYNTAX: Invalid regex - this is a compile-time bug");
+
+    // Syntax characteristic of one language, with the confidence it gives
+    static ref CONTENT_HINTS: Vec<(Regex, Language, f32)> = [
+        (r"^\s*<\?php\b", Language::Php, 0.8),
+        (r"(?m)^\s*pragma\s+solidity\b", Language::Solidity, 0.8),
+        (r"(?m)^\s*@(?:interface|implementation)\s+\w+", Language::ObjectiveC, 0.7),
+        (r"(?m)^\s*using\s+System(?:\.[\w.]+)?\s*;", Language::Csharp, 0.7),
+        (r"(?m)^\s*import\s+(?:Foundation|UIKit|SwiftUI|CryptoKit)\s*$", Language::Swift, 0.7),
+        (r"(?ms)^package\s+\w+\s*$.*^func\s", Language::Go, 0.7),
+        (r"(?m)^import\s+(?:static\s+)?(?:java|javax)\.[\w.*]+;", Language::Java, 0.6),
+        (r"(?m)^FROM\s+\S+(?:\s+AS\s+\w+)?\s*$", Language::Shell, 0.6),
+        (r"(?m)^\s*use\s+(?:std|crate|super)::", Language::Rust, 0.6),
+        (r"(?m)^\s*(?:from\s+[\w.]+\s+import\s|import\s+[\w.]+(?:\s+as\s+\w+)?\s*$)", Language::Python, 0.5),
+        (r"(?m)^\s*def\s+\w+\s*\(.*\)\s*(?:->\s*[^:]+)?:\s*$", Language::Python, 0.5),
+        (r"(?m)^\s*#\s*include\s*[<\x22]", Language::Cpp, 0.5),
+        (r#"(?m)(?:\brequire\(\s*['"]|^\s*module\.exports\b|^\s*import\s.*\sfrom\s+['"])"#, Language::JavaScript, 0.5),
+        (r#"(?m)^\s*require(?:_relative)?\s+['"][\w./-]+['"]\s*$"#, Language::Ruby, 0.5),
+        (r"(?m)^\s*(?:set\s+-[euxo]+|export\s+[A-Za-z_]\w*=)", Language::Shell, 0.4),
+        // A JWK, a JWK Set or an OpenID discovery document
+        (r#"(?s)^\s*\{.*"(?:kty|jwks_uri)"\s*:"#, Language::Json, 0.7),
+    ]
+    .into_iter()
+    .map(|(pattern, language, confidence)| {
+        let regex = Regex::new(pattern)
+            .expect("CONTENT_HINTS: Invalid regex - this is a compile-time bug");
+        (regex, language, confidence)
+    })
+    .collect();
+}
+
+impl Language {
+    /// Detect the language of a file from its path and content. Returns
+    /// `None` when no heuristic recognizes it; pass an empty path to detect
+    /// from content alone.
+    pub fn detect(path: &Path, content: &str) -> Option<LanguageDetection> {
+        let found = |language, confidence, method| LanguageDetection {
+            language,
+            confidence,
+            method,
+        };
+        let candidates = [
+            modeline(content).map(|l| found(l, MODELINE, DetectionMethod::Modeline)),
+            from_file_name(path).map(|l| found(l, FILENAME, DetectionMethod::Filename)),
+            from_extension(path, content)
+                .map(|(l, confidence)| found(l, confidence, DetectionMethod::Extension)),
+            shebang(content).map(|l| found(l, SHEBANG, DetectionMethod::Shebang)),
+            from_content(content)
+                .map(|(l, confidence)| found(l, confidence, DetectionMethod::Content)),
+        ];
+
+        // Candidates are in method order, so the first of equals wins
+        candidates.into_iter().flatten().reduce(|best, c| {
+            if c.confidence > best.confidence {
+                c
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Language implied by a file's name or extension alone
+    pub fn from_path(path: &Path) -> Option<Self> {
+        from_file_name(path).or_else(|| from_extension(path, "").map(|(language, _)| language))
+    }
+}
+
+fn from_file_name(path: &Path) -> Option<Language> {
+    let name = path.file_name()?.to_str()?;
+    match name {
+        "Dockerfile" | "Containerfile" | "Makefile" | "makefile" | "GNUmakefile" => {
+            Some(Language::Shell)
+        }
+        "Rakefile" | "Gemfile" => Some(Language::Ruby),
+        // JSON Web Key Sets and OpenID discovery documents name JOSE algorithms
+        "jwks.json" | "openid-configuration" => Some(Language::Json),
+        _ if name.starts_with("Dockerfile.") => Some(Language::Shell),
+        _ => None,
+    }
+}
+
+/// Language for a file extension; `.h` headers are Objective-C when the
+/// content uses Objective-C syntax, and other `.json` files than JWKs, JWK
+/// Sets and OpenID discovery documents are not scanned
+fn from_extension(path: &Path, content: &str) -> Option<(Language, f32)> {
+    let language = match path.extension()?.to_str()? {
+        "js" | "mjs" | "cjs" | "jsx" => Language::JavaScript,
+        "ts" | "mts" | "cts" | "tsx" => Language::TypeScript,
+        "py" | "pyw" | "pyi" => Language::Python,
+        "rs" => Language::Rust,
+        "java" => Language::Java,
+        "go" => Language::Go,
+        "h" if OBJC_SYNTAX.is_match(content) => return Some((Language::ObjectiveC, EXTENSION)),
+        "h" => return Some((Language::Cpp, AMBIGUOUS_EXTENSION)),
+        "cpp" | "cc" | "cxx" | "c" | "hpp" | "hh" | "hxx" => Language::Cpp,
+        "cs" => Language::Csharp,
+        "kt" | "kts" => Language::Kotlin,
+        "scala" | "sc" => Language::Scala,
+        "php" | "phtml" => Language::Php,
+        "rb" | "rake" | "gemspec" => Language::Ruby,
+        "swift" => Language::Swift,
+        "m" | "mm" => Language::ObjectiveC,
+        "sh" | "bash" | "zsh" | "dockerfile" | "mk" => Language::Shell,
+        "sol" => Language::Solidity,
+        "jwk" | "jwks" => Language::Json,
+        "json" => {
+            return from_content(content)
+                .filter(|&(language, _)| language == Language::Json)
+                .map(|(language, _)| (language, EXTENSION));
+        }
+        _ => return None,
+    };
+    Some((language, EXTENSION))
+}
+
+/// Language named by a Vim or Emacs modeline near the start or end of the file
+fn modeline(content: &str) -> Option<Language> {
+    let lines: Vec<&str> = content.lines().collect();
+    let tail = lines
+        .len()
+        .saturating_sub(MODELINE_LINES)
+        .max(MODELINE_LINES);
+    lines
+        .iter()
+        .take(MODELINE_LINES)
+        .chain(lines.iter().skip(tail))
+        .find_map(|line| {
+            let caps = VIM_MODELINE
+                .captures(line)
+                .or_else(|| EMACS_MODELINE.captures(line))?;
+            modeline_language(&caps[1])
+        })
+}
+
+/// Map a Vim file type or Emacs mode name to a language
+fn modeline_language(name: &str) -> Option<Language> {
+    let name = name.to_lowercase();
+    match name.trim_end_matches("-mode") {
+        "shell-script" | "dash" | "ksh" | "make" => Some(Language::Shell),
+        "js2" | "javascriptreact" | "jsx" => Some(Language::JavaScript),
+        "typescriptreact" | "tsx" => Some(Language::TypeScript),
+        "objcpp" | "objc++" => Some(Language::ObjectiveC),
+        "python3" => Some(Language::Python),
+        other => Language::from_string(other),
+    }
+}
+
+/// Language of the interpreter named by a `#!` line, looking through `env`
+fn shebang(content: &str) -> Option<Language> {
+    let line = content.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        // `env -S node --flags`, `env NODE_ENV=production node`
+        interpreter = words.find(|w| !w.starts_with('-') && !w.contains('='))?;
+    }
+
+    // `python3.12`, `php8`
+    match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "python" | "pypy" => Some(Language::Python),
+        "node" | "nodejs" | "bun" | "deno" => Some(Language::JavaScript),
+        "ts-node" | "tsx" => Some(Language::TypeScript),
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "ash" | "make" => Some(Language::Shell),
+        "ruby" | "jruby" => Some(Language::Ruby),
+        "php" => Some(Language::Php),
+        "swift" => Some(Language::Swift),
+        "scala" => Some(Language::Scala),
+        "kotlin" | "kscript" => Some(Language::Kotlin),
+        "java" => Some(Language::Java),
+        "rust-script" => Some(Language::Rust),
+        _ => None,
+    }
+}
+
+/// Most confident content hint that matches
+fn from_content(content: &str) -> Option<(Language, f32)> {
+    CONTENT_HINTS
+        .iter()
+        .filter(|(regex, _, _)| regex.is_match(content))
+        .map(|&(_, language, confidence)| (language, confidence))
+        .reduce(|best, c| if c.1 > best.1 { c } else { best })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(path: &str, content: &str) -> Option<(Language, DetectionMethod)> {
+        Language::detect(Path::new(path), content).map(|d| (d.language, d.method))
+    }
+
+    #[test]
+    fn test_extensions_and_file_names() {
+        for (path, language) in [
+            ("src/app.mjs", Language::JavaScript),
+            ("src/app.cjs", Language::JavaScript),
+            ("src/App.jsx", Language::JavaScript),
+            ("src/App.tsx", Language::TypeScript),
+            ("tools/gui.pyw", Language::Python),
+            ("contracts/Vault.sol", Language::Solidity),
+            ("Dockerfile.prod", Language::Shell),
+            ("Rakefile", Language::Ruby),
+            (".well-known/jwks.json", Language::Json),
+            ("keys/signing.jwk", Language::Json),
+        ] {
+            assert_eq!(
+                Language::from_path(Path::new(path)),
+                Some(language),
+                "{path}"
+            );
+        }
+        assert_eq!(Language::from_path(Path::new("notes.txt")), None);
+        assert_eq!(Language::from_path(Path::new("bin/deploy")), None);
+    }
+
+    #[test]
+    fn test_shebang() {
+        assert_eq!(
+            detect("bin/deploy", "#!/usr/bin/env python3\nimport ssl\n"),
+            Some((Language::Python, DetectionMethod::Shebang))
+        );
+        assert_eq!(
+            detect(
+                "bin/serve",
+                "#!/usr/bin/env -S NODE_ENV=production node --harmony\n"
+            ),
+            Some((Language::JavaScript, DetectionMethod::Shebang))
+        );
+        assert_eq!(
+            detect("bin/setup", "#!/bin/bash\nset -e\n").map(|d| d.0),
+            Some(Language::Shell)
+        );
+        assert_eq!(detect("bin/run", "#!/usr/bin/perl\nprint 1;\n"), None);
+
+        // The extension is more specific than a `node` interpreter line
+        assert_eq!(
+            detect("cli.ts", "#!/usr/bin/env node\nconst x: number = 1;\n"),
+            Some((Language::TypeScript, DetectionMethod::Extension))
+        );
+    }
+
+    #[test]
+    fn test_modelines() {
+        assert_eq!(
+            detect("hooks/pre-commit", "# vim: set ft=sh:\necho ok\n"),
+            Some((Language::Shell, DetectionMethod::Modeline))
+        );
+        assert_eq!(
+            detect(
+                "lib/keys.h",
+                "// -*- mode: objc; indent-tabs-mode: nil -*-\n"
+            ),
+            Some((Language::ObjectiveC, DetectionMethod::Modeline))
+        );
+        assert_eq!(
+            detect("tool", "#!/bin/sh\nexec ruby \"$0\"\n# vim: ft=ruby\n").map(|d| d.0),
+            Some(Language::Ruby)
+        );
+
+        // An encoding declaration is not a mode
+        assert_eq!(
+            detect("gen.py", "# -*- coding: utf-8 -*-\n"),
+            Some((Language::Python, DetectionMethod::Extension))
+        );
+    }
+
+    #[test]
+    fn test_content_hints() {
+        assert_eq!(
+            detect("", "<?php\necho md5($x);\n"),
+            Some((Language::Php, DetectionMethod::Content))
+        );
+        assert_eq!(
+            detect("", "pragma solidity ^0.8.0;\ncontract A {}\n").map(|d| d.0),
+            Some(Language::Solidity)
+        );
+        assert_eq!(
+            detect("", "package main\n\nfunc main() {}\n").map(|d| d.0),
+            Some(Language::Go)
+        );
+        assert_eq!(detect("", "Just some prose.\n"), None);
+
+        // Objective-C headers share the `.h` extension with C/C++
+        assert_eq!(
+            detect("Keys.h", "#import <Foundation/Foundation.h>\n").map(|d| d.0),
+            Some(Language::ObjectiveC)
+        );
+        let header = Language::detect(Path::new("keys.h"), "#include <stdint.h>\n").unwrap();
+        assert_eq!(header.language, Language::Cpp);
+        assert!(header.confidence < EXTENSION);
+
+        // Only JWKs, JWK Sets and OpenID discovery documents among `.json` files
+        let jwks = "{\n  \"keys\": [\n    { \"kty\": \"EC\", \"crv\": \"P-256\" }\n  ]\n}\n";
+        assert_eq!(
+            detect("config/keys.json", jwks),
+            Some((Language::Json, DetectionMethod::Extension))
+        );
+        assert_eq!(
+            detect("", jwks),
+            Some((Language::Json, DetectionMethod::Content))
+        );
+        assert_eq!(detect("package.json", "{ \"name\": \"app\" }\n"), None);
+    }
+}
@@ -0,0 +1,1266 @@
+//! Lightweight per-language lexer
+//!
+//! Classifies every byte of a source file as code, comment, string literal or
+//! docstring so detectors can tell real crypto usage apart from prose that only
+//! mentions an algorithm. This is not a full tokenizer: it only understands the
+//! comment and string delimiters of each supported language, the interpolation
+//! holes inside strings (which are code) and JavaScript regex literals.
+
+use crate::types::{Language, SpanKind};
+
+/// A contiguous region of source text with a single classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub kind: SpanKind,
+    /// Byte offset of the first byte of the span
+    pub start: usize,
+    /// Byte offset one past the last byte of the span
+    pub end: usize,
+}
+
+/// String literal delimiter description
+struct StringDelimiter {
+    open: &'static str,
+    close: &'static str,
+    /// Backslash escapes the next byte
+    escapes: bool,
+    /// Literal may span multiple lines
+    multiline: bool,
+    /// Interpolation holes, whose contents are code
+    hole: Option<Hole>,
+}
+
+/// An interpolation hole such as `${...}` inside a string literal
+struct Hole {
+    open: &'static str,
+    /// Byte closing the hole; it nests with the last byte of `open`
+    close: u8,
+    /// Prefix characters of which the literal needs one to interpolate
+    /// (`f"..."`, `$"..."`); empty if every literal does
+    markers: &'static [u8],
+}
+
+const fn delim(
+    open: &'static str,
+    close: &'static str,
+    escapes: bool,
+    multiline: bool,
+) -> StringDelimiter {
+    StringDelimiter {
+        open,
+        close,
+        escapes,
+        multiline,
+        hole: None,
+    }
+}
+
+const fn interpolated(delimiter: StringDelimiter, hole: Hole) -> StringDelimiter {
+    StringDelimiter {
+        hole: Some(hole),
+        ..delimiter
+    }
+}
+
+/// Python f-strings: `f"{expr}"`, with `{{` for a literal brace
+const PYTHON_HOLE: Hole = Hole {
+    open: "{",
+    close: b'}',
+    markers: b"fF",
+};
+
+/// C# interpolated strings: `$"{expr}"`
+const CSHARP_HOLE: Hole = Hole {
+    open: "{",
+    close: b'}',
+    markers: b"$",
+};
+
+/// JavaScript template literals: `` `${expr}` ``
+const TEMPLATE_HOLE: Hole = Hole {
+    open: "${",
+    close: b'}',
+    markers: b"",
+};
+
+/// Ruby double-quoted strings and backticks: `"#{expr}"`
+const RUBY_HOLE: Hole = Hole {
+    open: "#{",
+    close: b'}',
+    markers: b"",
+};
+
+/// Swift string interpolation: `"\(expr)"`
+const SWIFT_HOLE: Hole = Hole {
+    open: "\\(",
+    close: b')',
+    markers: b"",
+};
+
+/// Comment and string syntax for a language
+struct Syntax {
+    line_comments: &'static [&'static str],
+    doc_line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    doc_block_comment: Option<&'static str>,
+    nested_block_comments: bool,
+    /// Delimiters in priority order (longest opener first)
+    strings: &'static [StringDelimiter],
+    /// Rust raw strings (`r"..."`, `r#"..."#`)
+    raw_hash_strings: bool,
+    /// Rust `'a'` char literals vs `'a` lifetimes
+    rust_char_literals: bool,
+    /// Python triple-quoted strings in statement position are docstrings
+    python_docstrings: bool,
+    /// `<...>` header names after `#include` and `#import` are literals
+    header_names: bool,
+    /// JavaScript `/.../` regular expression literals
+    regex_literals: bool,
+}
+
+const RUST_STRINGS: &[StringDelimiter] = &[delim("\"", "\"", true, true)];
+
+const JS_STRINGS: &[StringDelimiter] = &[
+    interpolated(delim("`", "`", true, true), TEMPLATE_HOLE),
+    delim("\"", "\"", true, false),
+    delim("'", "'", true, false),
+];
+
+const PYTHON_STRINGS: &[StringDelimiter] = &[
+    interpolated(delim("\"\"\"", "\"\"\"", true, true), PYTHON_HOLE),
+    interpolated(delim("'''", "'''", true, true), PYTHON_HOLE),
+    interpolated(delim("\"", "\"", true, false), PYTHON_HOLE),
+    interpolated(delim("'", "'", true, false), PYTHON_HOLE),
+];
+
+const JAVA_STRINGS: &[StringDelimiter] = &[
+    delim("\"\"\"", "\"\"\"", true, true),
+    delim("\"", "\"", true, false),
+    delim("'", "'", true, false),
+];
+
+/// Kotlin and Scala: `"""` raw strings take no escapes
+const JVM_RAW_STRINGS: &[StringDelimiter] = &[
+    delim("\"\"\"", "\"\"\"", false, true),
+    delim("\"", "\"", true, false),
+    delim("'", "'", true, false),
+];
+
+/// PHP: every quote style may span lines; backticks run shell commands
+const SCRIPT_STRINGS: &[StringDelimiter] = &[
+    delim("\"", "\"", true, true),
+    delim("'", "'", true, true),
+    delim("`", "`", true, true),
+];
+
+/// Ruby: as PHP, with `#{...}` holes in double quotes and backticks
+const RUBY_STRINGS: &[StringDelimiter] = &[
+    interpolated(delim("\"", "\"", true, true), RUBY_HOLE),
+    delim("'", "'", true, true),
+    interpolated(delim("`", "`", true, true), RUBY_HOLE),
+];
+
+/// Swift: `"""` multi-line strings and `#"..."#` raw strings
+const SWIFT_STRINGS: &[StringDelimiter] = &[
+    delim("#\"\"\"", "\"\"\"#", false, true),
+    interpolated(delim("\"\"\"", "\"\"\"", true, true), SWIFT_HOLE),
+    delim("#\"", "\"#", false, false),
+    interpolated(delim("\"", "\"", true, false), SWIFT_HOLE),
+];
+
+/// Objective-C: C strings plus `@"..."` NSString literals
+const OBJC_STRINGS: &[StringDelimiter] = &[
+    delim("@\"", "\"", true, false),
+    delim("\"", "\"", true, false),
+    delim("'", "'", true, false),
+];
+
+/// Shell: single quotes take no escapes; both quote styles may span lines
+const SHELL_STRINGS: &[StringDelimiter] =
+    &[delim("\"", "\"", true, true), delim("'", "'", false, true)];
+
+const GO_STRINGS: &[StringDelimiter] = &[
+    delim("`", "`", false, true),
+    delim("\"", "\"", true, false),
+    delim("'", "'", true, false),
+];
+
+const C_STYLE_STRINGS: &[StringDelimiter] =
+    &[delim("\"", "\"", true, false), delim("'", "'", true, false)];
+
+const CSHARP_STRINGS: &[StringDelimiter] = &[
+    interpolated(delim("\"\"\"", "\"\"\"", false, true), CSHARP_HOLE),
+    interpolated(delim("@\"", "\"", false, true), CSHARP_HOLE),
+    interpolated(delim("\"", "\"", true, false), CSHARP_HOLE),
+    delim("'", "'", true, false),
+];
+
+fn syntax_for(language: Language) -> Syntax {
+    match language {
+        Language::Rust => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &["///", "//!"],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: true,
+            strings: RUST_STRINGS,
+            raw_hash_strings: true,
+            rust_char_literals: true,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::JavaScript | Language::TypeScript | Language::Json => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &[],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: false,
+            strings: JS_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: true,
+        },
+        Language::Python => Syntax {
+            line_comments: &["#"],
+            doc_line_comments: &[],
+            block_comment: None,
+            doc_block_comment: None,
+            nested_block_comments: false,
+            strings: PYTHON_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: true,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Java => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &[],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: false,
+            strings: JAVA_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Go => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &[],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: None,
+            nested_block_comments: false,
+            strings: GO_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Cpp | Language::Solidity => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &["///", "//!"],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: false,
+            strings: C_STYLE_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: true,
+            regex_literals: false,
+        },
+        Language::Csharp => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &["///"],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: false,
+            strings: CSHARP_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Php => Syntax {
+            line_comments: &["//", "#"],
+            doc_line_comments: &[],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: false,
+            strings: SCRIPT_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Ruby => Syntax {
+            line_comments: &["#"],
+            doc_line_comments: &[],
+            block_comment: Some(("=begin", "=end")),
+            doc_block_comment: None,
+            nested_block_comments: false,
+            strings: RUBY_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Swift => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &["///"],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: true,
+            strings: SWIFT_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::ObjectiveC => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &["///"],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: false,
+            strings: OBJC_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: true,
+            regex_literals: false,
+        },
+        Language::Shell => Syntax {
+            line_comments: &["#"],
+            doc_line_comments: &[],
+            block_comment: None,
+            doc_block_comment: None,
+            nested_block_comments: false,
+            strings: SHELL_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+        Language::Kotlin | Language::Scala => Syntax {
+            line_comments: &["//"],
+            doc_line_comments: &[],
+            block_comment: Some(("/*", "*/")),
+            doc_block_comment: Some("/**"),
+            nested_block_comments: true,
+            strings: JVM_RAW_STRINGS,
+            raw_hash_strings: false,
+            rust_char_literals: false,
+            python_docstrings: false,
+            header_names: false,
+            regex_literals: false,
+        },
+    }
+}
+
+/// Source text split into classified spans
+#[derive(Debug, Clone)]
+pub struct LexedSource<'a> {
+    source: &'a str,
+    spans: Vec<Span>,
+}
+
+impl<'a> LexedSource<'a> {
+    /// Lex `source` using the comment and string rules of `language`
+    pub fn new(source: &'a str, language: Language) -> Self {
+        let spans = lex(source, &syntax_for(language));
+        Self { source, spans }
+    }
+
+    /// All spans in source order; together they cover the whole input
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// The span containing a byte offset, if any
+    pub fn span_at(&self, offset: usize) -> Option<&Span> {
+        let idx = self.spans.partition_point(|s| s.end <= offset);
+        self.spans.get(idx).filter(|s| s.start <= offset)
+    }
+
+    /// Classification of the byte at `offset` (offsets past the end are code)
+    pub fn kind_at(&self, offset: usize) -> SpanKind {
+        self.span_at(offset).map(|s| s.kind).unwrap_or_default()
+    }
+
+    /// Text of a span
+    pub fn text(&self, span: &Span) -> &'a str {
+        &self.source[span.start..span.end]
+    }
+
+    /// Copy of the source with every non-code span blanked out unless `keep`
+    /// returns true for it. Blanked bytes become spaces (newlines are kept) so
+    /// byte offsets and line numbers still line up with the original.
+    pub fn scan_text(&self, keep: impl Fn(&Span) -> bool) -> String {
+        let mut bytes = self.source.as_bytes().to_vec();
+        for span in &self.spans {
+            if span.kind == SpanKind::Code || keep(span) {
+                continue;
+            }
+            for b in &mut bytes[span.start..span.end] {
+                if *b != b'\n' && *b != b'\r' {
+                    *b = b' ';
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+impl Syntax {
+    /// Bytes a comment or string can start with; most bytes are none of them
+    fn openers(&self) -> [bool; 256] {
+        let mut openers = [false; 256];
+        let delimiters = self
+            .line_comments
+            .iter()
+            .chain(self.doc_line_comments)
+            .chain(self.block_comment.as_ref().map(|(open, _)| open))
+            .chain(self.strings.iter().map(|d| &d.open));
+        for delimiter in delimiters {
+            if let Some(&first) = delimiter.as_bytes().first() {
+                openers[usize::from(first)] = true;
+            }
+        }
+        if self.raw_hash_strings {
+            openers[usize::from(b'b')] = true;
+            openers[usize::from(b'r')] = true;
+        }
+        if self.rust_char_literals {
+            openers[usize::from(b'\'')] = true;
+        }
+        if self.header_names {
+            openers[usize::from(b'<')] = true;
+        }
+        openers
+    }
+}
+
+fn lex(source: &str, syntax: &Syntax) -> Vec<Span> {
+    let mut lexer = Lexer {
+        bytes: source.as_bytes(),
+        syntax,
+        openers: syntax.openers(),
+        spans: Vec::new(),
+    };
+    lexer.code(0, None);
+    lexer.spans
+}
+
+struct Lexer<'a> {
+    bytes: &'a [u8],
+    syntax: &'a Syntax,
+    openers: [bool; 256],
+    spans: Vec<Span>,
+}
+
+impl Lexer<'_> {
+    fn push(&mut self, kind: SpanKind, start: usize, end: usize) {
+        if start < end {
+            self.spans.push(Span { kind, start, end });
+        }
+    }
+
+    /// Lex code from `start` to the end of input or, inside an interpolation
+    /// hole, to the `close` byte of the `(open, close)` pair that ends it.
+    /// Returns the offset it stopped at.
+    fn code(&mut self, start: usize, hole: Option<(u8, u8)>) -> usize {
+        let bytes = self.bytes;
+        let mut code_start = start;
+        let mut depth = 0usize;
+        let mut i = start;
+
+        while i < bytes.len() {
+            if let Some((open, close)) = hole {
+                if bytes[i] == open {
+                    depth += 1;
+                } else if bytes[i] == close {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+            }
+            if !self.openers[usize::from(bytes[i])] {
+                i += 1;
+                continue;
+            }
+            if let Some(delimiter) = interpolated_string_at(bytes, i, self.syntax) {
+                self.push(SpanKind::Code, code_start, i);
+                i = self.interpolated_string(i, delimiter);
+                code_start = i;
+                continue;
+            }
+            match non_code_at(bytes, i, self.syntax) {
+                Some((kind, end)) => {
+                    self.push(SpanKind::Code, code_start, i);
+                    self.push(kind, i, end);
+                    i = end;
+                    code_start = end;
+                }
+                None => i += 1,
+            }
+        }
+
+        self.push(SpanKind::Code, code_start, i);
+        i
+    }
+
+    /// Lex a string literal starting at `i` whose holes are code; the text
+    /// around each hole, including its delimiters, is string literal
+    fn interpolated_string(&mut self, i: usize, d: &StringDelimiter) -> usize {
+        let bytes = self.bytes;
+        let Some(hole) = &d.hole else {
+            return i;
+        };
+        let mut segment = i;
+        let mut j = i + d.open.len();
+
+        while j < bytes.len() {
+            let rest = &bytes[j..];
+            if rest.starts_with(hole.open.as_bytes()) {
+                // `{{` is a literal brace in f-strings and C# interpolated strings
+                if hole.open == "{" && rest.get(1) == Some(&b'{') {
+                    j += 2;
+                    continue;
+                }
+                let inner = j + hole.open.len();
+                self.push(SpanKind::StringLiteral, segment, inner);
+                let nest = hole.open.as_bytes()[hole.open.len() - 1];
+                let end = self.code(inner, Some((nest, hole.close)));
+                segment = end;
+                j = (end + 1).min(bytes.len());
+            } else if d.escapes && bytes[j] == b'\\' {
+                j += 2;
+            } else if rest.starts_with(d.close.as_bytes()) {
+                j += d.close.len();
+                self.push(SpanKind::StringLiteral, segment, j);
+                return j;
+            } else if bytes[j] == b'\n' && !d.multiline {
+                self.push(SpanKind::StringLiteral, segment, j);
+                return j;
+            } else {
+                j += 1;
+            }
+        }
+        let end = bytes.len();
+        self.push(SpanKind::StringLiteral, segment, end);
+        end
+    }
+}
+
+/// The delimiter of a string literal starting at `i` if it has interpolation
+/// holes; other literals are handled by `non_code_at`
+fn interpolated_string_at<'s>(
+    bytes: &[u8],
+    i: usize,
+    syntax: &'s Syntax,
+) -> Option<&'s StringDelimiter> {
+    let d = syntax
+        .strings
+        .iter()
+        .find(|d| bytes[i..].starts_with(d.open.as_bytes()))?;
+    let hole = d.hole.as_ref()?;
+    if hole.markers.is_empty() {
+        return Some(d);
+    }
+    // `f"`, `rf'`, `$"`, `$@"`: a short prefix right before the quote
+    let prefix_len = bytes[..i]
+        .iter()
+        .rev()
+        .take_while(|&&b| b.is_ascii_alphabetic() || b == b'$' || b == b'@')
+        .count();
+    let prefix = &bytes[i - prefix_len..i];
+    let standalone = i == prefix_len || !is_word_byte(bytes[i - prefix_len - 1]);
+    (prefix_len <= 3 && standalone && prefix.iter().any(|b| hole.markers.contains(b))).then_some(d)
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// If a comment or string starts at `i`, return its kind and end offset
+fn non_code_at(bytes: &[u8], i: usize, syntax: &Syntax) -> Option<(SpanKind, usize)> {
+    let rest = &bytes[i..];
+
+    for prefix in syntax.doc_line_comments {
+        // `////` and longer runs are ordinary comments in every C-like language
+        if rest.starts_with(prefix.as_bytes()) && rest.get(prefix.len()) != Some(&b'/') {
+            return Some((SpanKind::DocString, line_end(bytes, i)));
+        }
+    }
+
+    for prefix in syntax.line_comments {
+        if rest.starts_with(prefix.as_bytes()) {
+            return Some((SpanKind::Comment, line_end(bytes, i)));
+        }
+    }
+
+    if let Some((open, close)) = syntax.block_comment
+        && rest.starts_with(open.as_bytes())
+    {
+        let end = block_comment_end(bytes, i, open, close, syntax.nested_block_comments);
+        let is_doc = syntax.doc_block_comment.is_some_and(|doc| {
+            rest.starts_with(doc.as_bytes()) && !rest[doc.len()..].starts_with(b"/")
+        });
+        let kind = if is_doc {
+            SpanKind::DocString
+        } else {
+            SpanKind::Comment
+        };
+        return Some((kind, end));
+    }
+
+    if syntax.regex_literals
+        && rest.first() == Some(&b'/')
+        && regex_allowed(bytes, i)
+        && let Some(end) = regex_literal_end(bytes, i)
+    {
+        return Some((SpanKind::StringLiteral, end));
+    }
+
+    if syntax.raw_hash_strings
+        && let Some(end) = rust_raw_string_end(bytes, i)
+    {
+        return Some((SpanKind::StringLiteral, end));
+    }
+
+    if syntax.rust_char_literals && rest.first() == Some(&b'\'') {
+        return rust_char_literal_end(bytes, i).map(|end| (SpanKind::StringLiteral, end));
+    }
+
+    if syntax.header_names && rest.first() == Some(&b'<') && after_include(bytes, i) {
+        let end = bytes[i..]
+            .iter()
+            .position(|&b| b == b'>' || b == b'\n')
+            .map_or(bytes.len(), |p| i + p + usize::from(bytes[i + p] == b'>'));
+        return Some((SpanKind::StringLiteral, end));
+    }
+
+    for d in syntax.strings {
+        if rest.starts_with(d.open.as_bytes()) {
+            let end = string_end(bytes, i + d.open.len(), d);
+            let kind =
+                if syntax.python_docstrings && d.open.len() == 3 && starts_statement(bytes, i) {
+                    SpanKind::DocString
+                } else {
+                    SpanKind::StringLiteral
+                };
+            return Some((kind, end));
+        }
+    }
+
+    None
+}
+
+/// Offset of the newline ending the line that contains `i` (or end of input)
+fn line_end(bytes: &[u8], i: usize) -> usize {
+    bytes[i..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|p| i + p)
+        .unwrap_or(bytes.len())
+}
+
+fn block_comment_end(bytes: &[u8], i: usize, open: &str, close: &str, nested: bool) -> usize {
+    let mut depth = 0usize;
+    let mut j = i;
+    while j < bytes.len() {
+        if bytes[j..].starts_with(open.as_bytes()) && (nested || depth == 0) {
+            depth += 1;
+            j += open.len();
+        } else if bytes[j..].starts_with(close.as_bytes()) {
+            depth -= 1;
+            j += close.len();
+            if depth == 0 {
+                return j;
+            }
+        } else {
+            j += 1;
+        }
+    }
+    bytes.len()
+}
+
+fn string_end(bytes: &[u8], mut j: usize, d: &StringDelimiter) -> usize {
+    while j < bytes.len() {
+        if d.escapes && bytes[j] == b'\\' {
+            j += 2;
+        } else if bytes[j..].starts_with(d.close.as_bytes()) {
+            return j + d.close.len();
+        } else if bytes[j] == b'\n' && !d.multiline {
+            // Unterminated single-line literal: stop at the end of the line
+            return j;
+        } else {
+            j += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// `r"..."`, `r#"..."#`, `br##"..."##` and friends
+fn rust_raw_string_end(bytes: &[u8], i: usize) -> Option<usize> {
+    let mut j = i;
+    if bytes.get(j) == Some(&b'b') {
+        j += 1;
+    }
+    if bytes.get(j) != Some(&b'r') {
+        return None;
+    }
+    // Must not be the tail of an identifier such as `bar"`
+    if i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_') {
+        return None;
+    }
+    j += 1;
+    let hashes = bytes[j..].iter().take_while(|&&b| b == b'#').count();
+    j += hashes;
+    if bytes.get(j) != Some(&b'"') {
+        return None;
+    }
+    j += 1;
+    while j < bytes.len() {
+        let closing = &bytes[j + 1..(j + 1 + hashes).min(bytes.len())];
+        if bytes[j] == b'"' && closing.len() == hashes && closing.iter().all(|&b| b == b'#') {
+            return Some(j + 1 + hashes);
+        }
+        j += 1;
+    }
+    Some(bytes.len())
+}
+
+/// Whether a `/` at `i` starts a regular expression rather than dividing:
+/// true after an operator, an opening bracket, `,`, `;` or a keyword such as
+/// `return`, and at the start of input
+fn regex_allowed(bytes: &[u8], i: usize) -> bool {
+    let Some(prev) = bytes[..i].iter().rposition(|b| !b.is_ascii_whitespace()) else {
+        return true;
+    };
+    if b"(,=:[!&|?{};+-*%<>~^".contains(&bytes[prev]) {
+        return true;
+    }
+    let word_start = bytes[..=prev]
+        .iter()
+        .rposition(|&b| !is_word_byte(b))
+        .map_or(0, |p| p + 1);
+    matches!(
+        &bytes[word_start..=prev],
+        b"return"
+            | b"typeof"
+            | b"instanceof"
+            | b"in"
+            | b"of"
+            | b"new"
+            | b"delete"
+            | b"void"
+            | b"throw"
+            | b"case"
+            | b"do"
+            | b"else"
+            | b"yield"
+            | b"await"
+    )
+}
+
+/// End of a `/.../flags` regular expression literal starting at `i`, or
+/// `None` if it is not closed on its line
+fn regex_literal_end(bytes: &[u8], i: usize) -> Option<usize> {
+    let mut in_class = false;
+    let mut j = i + 1;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => j += 1,
+            b'\n' => return None,
+            b'[' => in_class = true,
+            b']' => in_class = false,
+            b'/' if !in_class => {
+                let flags = bytes[j + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_alphabetic())
+                    .count();
+                return Some(j + 1 + flags);
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    None
+}
+
+/// Distinguish `'x'` / `'\n'` char literals from `'a` lifetimes
+fn rust_char_literal_end(bytes: &[u8], i: usize) -> Option<usize> {
+    let body = bytes.get(i + 1)?;
+    if *body == b'\\' {
+        let close = bytes[i + 2..].iter().take(10).position(|&b| b == b'\'')?;
+        return Some(i + 2 + close + 1);
+    }
+    // Width of the UTF-8 encoded character following the quote
+    let width = match body {
+        b if *b < 0x80 => 1,
+        b if *b >= 0xF0 => 4,
+        b if *b >= 0xE0 => 3,
+        _ => 2,
+    };
+    (bytes.get(i + 1 + width) == Some(&b'\'')).then_some(i + 2 + width)
+}
+
+/// True when `#include` or `#import` is all that precedes offset `i` on its line
+fn after_include(bytes: &[u8], i: usize) -> bool {
+    let line_start = bytes[..i]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |p| p + 1);
+    let before = String::from_utf8_lossy(&bytes[line_start..i]);
+    before
+        .trim()
+        .strip_prefix('#')
+        .is_some_and(|directive| matches!(directive.trim_start(), "include" | "import"))
+}
+
+/// True when only whitespace precedes offset `i` on its line
+fn starts_statement(bytes: &[u8], i: usize) -> bool {
+    bytes[..i]
+        .iter()
+        .rev()
+        .take_while(|&&b| b != b'\n')
+        .all(|b| b.is_ascii_whitespace())
+}
+
+/// Whether `c` can appear in an identifier in `language`
+pub fn is_identifier_char(c: char, language: Language) -> bool {
+    c.is_alphanumeric()
+        || c == '_'
+        || (c == '$'
+            && matches!(
+                language,
+                Language::JavaScript
+                    | Language::TypeScript
+                    | Language::Java
+                    | Language::Scala
+                    | Language::Php
+                    | Language::Ruby
+            ))
+}
+
+/// Whether bytes `start..end` of `text` begin and end on identifier segment
+/// boundaries, so a match is a whole token or a snake_case / camelCase segment
+/// of one (`md5` in `md5_hex` or `getMd5`, but not `ecc` in `rebecca`).
+///
+/// Leading and trailing punctuation in the range (e.g. `.DES`) is ignored.
+/// Matches inside hex-looking tokens such as digests are rejected.
+pub fn on_segment_boundaries(text: &str, start: usize, end: usize, language: Language) -> bool {
+    let is_segment_char = |c: char| c.is_alphanumeric();
+    let range = &text[start..end];
+    let Some(lead) = range.find(is_segment_char) else {
+        return true;
+    };
+    let trail = range
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| is_segment_char(c))
+        .map_or(range.len(), |(i, c)| i + c.len_utf8());
+    let (start, end) = (start + lead, start + trail);
+
+    let mut inside = text[start..end].chars();
+    let (first, last) = match (inside.next(), inside.next_back()) {
+        (Some(first), Some(last)) => (first, last),
+        (Some(only), None) => (only, only),
+        _ => return true,
+    };
+    let second = text[start..end].chars().nth(1);
+    let prev = text[..start].chars().next_back();
+    let mut after = text[end..].chars();
+    let (next, next2) = (after.next(), after.next());
+
+    let before_ok = match prev {
+        None => true,
+        Some(p) if !is_segment_char(p) => true,
+        Some(p) => {
+            p.is_ascii_digit() != first.is_ascii_digit()
+                || (p.is_lowercase() && first.is_uppercase())
+                // Acronym followed by a word: `EC|Dsa`
+                || (p.is_uppercase()
+                    && first.is_uppercase()
+                    && second.is_some_and(char::is_lowercase))
+        }
+    };
+    let after_ok = match next {
+        None => true,
+        Some(n) if !is_segment_char(n) => true,
+        Some(n) => {
+            last.is_ascii_digit() != n.is_ascii_digit()
+                || (!last.is_uppercase() && n.is_uppercase())
+                // Acronym followed by a word: `RSA|Key`
+                || (last.is_uppercase()
+                    && n.is_uppercase()
+                    && next2.is_some_and(char::is_lowercase))
+        }
+    };
+
+    before_ok && after_ok && !in_hex_token(text, start, end, language)
+}
+
+/// Whether bytes `start..end` fall inside a token that looks like a hex digest or constant
+fn in_hex_token(text: &str, start: usize, end: usize, language: Language) -> bool {
+    let token_start = text[..start]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_identifier_char(c, language))
+        .last()
+        .map_or(start, |(i, _)| i);
+    let token_end = text[end..]
+        .char_indices()
+        .find(|&(_, c)| !is_identifier_char(c, language))
+        .map_or(text.len(), |(i, _)| end + i);
+    let token = &text[token_start..token_end];
+    let digits = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+        .unwrap_or(token);
+
+    digits.len() >= 8
+        && digits.chars().all(|c| c.is_ascii_hexdigit())
+        && digits.chars().any(|c| c.is_ascii_digit())
+        && digits.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str, language: Language) -> Vec<(SpanKind, String)> {
+        let lexed = LexedSource::new(source, language);
+        lexed
+            .spans()
+            .iter()
+            .filter(|s| s.kind != SpanKind::Code)
+            .map(|s| (s.kind, lexed.text(s).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_comments_and_strings() {
+        let source = "/// RSA docs\nlet s = \"MD5\"; // old md5\n/* a /* nested */ b */ x";
+        let found = kinds(source, Language::Rust);
+        assert_eq!(found[0], (SpanKind::DocString, "/// RSA docs".to_string()));
+        assert_eq!(found[1], (SpanKind::StringLiteral, "\"MD5\"".to_string()));
+        assert_eq!(found[2], (SpanKind::Comment, "// old md5".to_string()));
+        assert_eq!(
+            found[3],
+            (SpanKind::Comment, "/* a /* nested */ b */".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rust_lifetimes_are_code() {
+        let source = "fn f<'a>(x: &'a str) -> char { 'r' }";
+        let found = kinds(source, Language::Rust);
+        assert_eq!(found, vec![(SpanKind::StringLiteral, "'r'".to_string())]);
+    }
+
+    #[test]
+    fn test_rust_raw_string() {
+        let source = r###"let s = r#"say "RSA""#;"###;
+        let found = kinds(source, Language::Rust);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1, r###"r#"say "RSA""#"###);
+    }
+
+    #[test]
+    fn test_c_header_names() {
+        let source = "#include <openssl/rsa.h>\n# import \"des.h\"\nif (a <b && c > d) {}";
+        let found = kinds(source, Language::Cpp);
+        assert_eq!(
+            found,
+            vec![
+                (SpanKind::StringLiteral, "<openssl/rsa.h>".to_string()),
+                (SpanKind::StringLiteral, "\"des.h\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_docstring_vs_string() {
+        let source = "def f():\n    \"\"\"Uses MD5.\"\"\"\n    x = \"\"\"md5\"\"\"\n";
+        let found = kinds(source, Language::Python);
+        assert_eq!(found[0].0, SpanKind::DocString);
+        assert_eq!(found[1].0, SpanKind::StringLiteral);
+    }
+
+    #[test]
+    fn test_python_hash_comment() {
+        let found = kinds("x = 1  # md5 here", Language::Python);
+        assert_eq!(found, vec![(SpanKind::Comment, "# md5 here".to_string())]);
+    }
+
+    #[test]
+    fn test_javascript_template_and_escape() {
+        let source = "const a = `multi\nline`; const b = 'it\\'s'; /** doc */";
+        let found = kinds(source, Language::JavaScript);
+        assert_eq!(found[0].0, SpanKind::StringLiteral);
+        assert_eq!(found[1], (SpanKind::StringLiteral, "'it\\'s'".to_string()));
+        assert_eq!(found[2].0, SpanKind::DocString);
+    }
+
+    #[test]
+    fn test_go_raw_string_has_no_escapes() {
+        let found = kinds(r"x := `C:\path\` + y", Language::Go);
+        assert_eq!(
+            found,
+            vec![(SpanKind::StringLiteral, r"`C:\path\`".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_csharp_verbatim_and_doc() {
+        let source = "/// <summary>RSA</summary>\nvar p = @\"C:\\dir\\\";";
+        let found = kinds(source, Language::Csharp);
+        assert_eq!(found[0].0, SpanKind::DocString);
+        assert_eq!(
+            found[1],
+            (SpanKind::StringLiteral, "@\"C:\\dir\\\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_kotlin_raw_string_and_nested_comment() {
+        let source = "/* a /* b */ RSA */ val p = \"\"\"C:\\dir\\\"\"\" + \"\\\"q\\\"\"";
+        let found = kinds(source, Language::Kotlin);
+        assert_eq!(
+            found,
+            vec![
+                (SpanKind::Comment, "/* a /* b */ RSA */".to_string()),
+                (SpanKind::StringLiteral, "\"\"\"C:\\dir\\\"\"\"".to_string()),
+                (SpanKind::StringLiteral, "\"\\\"q\\\"\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_php_and_ruby_comments() {
+        let found = kinds("$h = md5($x); # legacy md5\n// md5 too", Language::Php);
+        assert_eq!(
+            found,
+            vec![
+                (SpanKind::Comment, "# legacy md5".to_string()),
+                (SpanKind::Comment, "// md5 too".to_string()),
+            ]
+        );
+
+        let source = "=begin\nDigest::MD5 was used here\n=end\nkey = 'RSA #{bits}'";
+        let found = kinds(source, Language::Ruby);
+        assert_eq!(found[0].0, SpanKind::Comment);
+        assert!(found[0].1.ends_with("=end"));
+        assert_eq!(
+            found[1],
+            (SpanKind::StringLiteral, "'RSA #{bits}'".to_string())
+        );
+    }
+
+    /// Code spans of `source`, as text
+    fn code(source: &str, language: Language) -> Vec<String> {
+        let lexed = LexedSource::new(source, language);
+        lexed
+            .spans()
+            .iter()
+            .filter(|s| s.kind == SpanKind::Code)
+            .map(|s| lexed.text(s).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_python_fstring_holes_are_code() {
+        let source = "log(f\"digest={hashlib.md5(x).hexdigest()} {{md5}}\")";
+        assert_eq!(
+            code(source, Language::Python),
+            ["log(f", "hashlib.md5(x).hexdigest()", ")"]
+        );
+        assert_eq!(
+            kinds(source, Language::Python),
+            vec![
+                (SpanKind::StringLiteral, "\"digest={".to_string()),
+                (SpanKind::StringLiteral, "} {{md5}}\"".to_string()),
+            ]
+        );
+
+        // Plain strings and other prefixes do not interpolate
+        assert_eq!(
+            code("x = \"{md5}\" + b'{sha1}'", Language::Python),
+            ["x = ", " + b"]
+        );
+        assert_eq!(
+            code("y = rf'{d[\"md5\"]}'", Language::Python),
+            ["y = rf", "d[", "]"]
+        );
+    }
+
+    #[test]
+    fn test_javascript_template_holes_are_code() {
+        let source = "const s = `id ${crypto.createHash('md5').digest('hex')}`;";
+        assert_eq!(
+            kinds(source, Language::JavaScript),
+            vec![
+                (SpanKind::StringLiteral, "`id ${".to_string()),
+                (SpanKind::StringLiteral, "'md5'".to_string()),
+                (SpanKind::StringLiteral, "'hex'".to_string()),
+                (SpanKind::StringLiteral, "}`".to_string()),
+            ]
+        );
+
+        // Nested templates and braces inside the hole
+        let source = "`a ${f({ k: `b ${md5(x)}` })} c`";
+        assert_eq!(
+            code(source, Language::JavaScript),
+            ["f({ k: ", "md5(x)", " })"]
+        );
+    }
+
+    #[test]
+    fn test_csharp_interpolated_string_holes_are_code() {
+        let source = "var s = $\"{SHA1.Create().Hash}\" + \"{MD5}\";";
+        assert_eq!(
+            code(source, Language::Csharp),
+            ["var s = $", "SHA1.Create().Hash", " + ", ";"]
+        );
+        assert_eq!(
+            code("var p = $@\"{DES.Create()}\\\";", Language::Csharp),
+            ["var p = $", "DES.Create()", ";"]
+        );
+    }
+
+    #[test]
+    fn test_ruby_interpolation_holes_are_code() {
+        let source = "puts \"sum #{Digest::MD5.hexdigest(x)}\", `echo #{cmd}`, '#{sha1}'";
+        assert_eq!(
+            code(source, Language::Ruby),
+            ["puts ", "Digest::MD5.hexdigest(x)", ", ", "cmd", ", "]
+        );
+        // An escaped `#{` is text
+        assert_eq!(code("s = \"\\#{md5}\"", Language::Ruby), ["s = "]);
+    }
+
+    #[test]
+    fn test_swift_interpolation_holes_are_code() {
+        let source = "print(\"hash \\(Insecure.MD5.hash(data: d)) \\n\")";
+        assert_eq!(
+            code(source, Language::Swift),
+            ["print(", "Insecure.MD5.hash(data: d)", ")"]
+        );
+        // Raw strings do not interpolate
+        assert_eq!(code("let r = #\"\\(md5)\"#", Language::Swift), ["let r = "]);
+    }
+
+    #[test]
+    fn test_javascript_regex_literals() {
+        let source = "const re = /rsa\"/i;\nconst h = crypto.createHash('sha1');";
+        assert_eq!(
+            kinds(source, Language::JavaScript),
+            vec![
+                (SpanKind::StringLiteral, "/rsa\"/i".to_string()),
+                (SpanKind::StringLiteral, "'sha1'".to_string()),
+            ]
+        );
+
+        // Character classes may hold `/`; after a value `/` divides
+        assert_eq!(
+            kinds("s.split(/[/']/); x = a / b / c;", Language::JavaScript),
+            vec![(SpanKind::StringLiteral, "/[/']/".to_string())]
+        );
+        assert!(kinds("return n / 2 + f(m) / 4", Language::JavaScript).is_empty());
+        assert_eq!(
+            kinds("return /md5'/.test(s)", Language::JavaScript),
+            vec![(SpanKind::StringLiteral, "/md5'/".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_swift_and_objc_strings() {
+        let source = "let s = #\"CC_MD5 \"quoted\"\"#\n/* a /* nested */ MD5 */ x";
+        let found = kinds(source, Language::Swift);
+        assert_eq!(
+            found,
+            vec![
+                (
+                    SpanKind::StringLiteral,
+                    "#\"CC_MD5 \"quoted\"\"#".to_string()
+                ),
+                (SpanKind::Comment, "/* a /* nested */ MD5 */".to_string()),
+            ]
+        );
+
+        let found = kinds("NSString *alg = @\"SHA1\"; // sha1", Language::ObjectiveC);
+        assert_eq!(
+            found,
+            vec![
+                (SpanKind::StringLiteral, "@\"SHA1\"".to_string()),
+                (SpanKind::Comment, "// sha1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unterminated_string_stops_at_newline() {
+        let found = kinds("x = \"open\nRSA.generate(1024)", Language::Java);
+        assert_eq!(found, vec![(SpanKind::StringLiteral, "\"open".to_string())]);
+    }
+
+    #[test]
+    fn test_scan_text_preserves_offsets() {
+        let source = "a = 1 // ünïcode md5\nb = 'md5'";
+        let lexed = LexedSource::new(source, Language::JavaScript);
+        let masked = lexed.scan_text(|_| false);
+        assert_eq!(masked.len(), source.len());
+        assert_eq!(masked.lines().count(), source.lines().count());
+        assert!(!masked.contains("md5"));
+        assert_eq!(
+            lexed.kind_at(source.find("'md5'").unwrap()),
+            SpanKind::StringLiteral
+        );
+        assert_eq!(lexed.kind_at(0), SpanKind::Code);
+    }
+
+    #[test]
+    fn test_segment_boundaries() {
+        let check = |text: &str, needle: &str| {
+            let start = text.find(needle).unwrap();
+            on_segment_boundaries(text, start, start + needle.len(), Language::Java)
+        };
+
+        // Whole tokens and snake/camel/acronym segments
+        assert!(check("md5(data)", "md5"));
+        assert!(check("md5_hex", "md5"));
+        assert!(check("getMd5Digest", "Md5"));
+        assert!(check("HmacSHA1", "SHA1"));
+        assert!(check("MD5withRSA", "MD5"));
+        assert!(check("RSAKeyPair", "RSA"));
+        assert!(check("ECDsa.Create()", "Dsa"));
+        assert!(check("RSA2048", "RSA"));
+        assert!(check("obj.DES.new", ".DES."));
+
+        // Substrings of other words
+        assert!(!check("rebecca", "ecc"));
+        assert!(!check("parsable", "rsa"));
+        assert!(!check("ECDSA", "DSA"));
+        assert!(!check("ADHERENCE", "DHE"));
+        assert!(!check("obj.description", ".des"));
+
+        // Hex digests and constants
+        assert!(!check("d41d8cd98f00b204e9800998ecc8427e", "ecc"));
+        assert!(!check("0x7ecc3a91", "ecc"));
+    }
+}
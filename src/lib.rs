@@ -4,14 +4,26 @@
 pub mod algorithm_database;
 pub mod audit;
 pub mod canadian_compliance;
+pub mod commands;
 pub mod compliance;
+pub mod confidence;
 pub mod detector;
+pub mod jca;
+pub mod jose;
+pub mod language_detection;
+pub mod lexer;
 pub mod parser;
+pub mod pqc;
+pub mod randomness;
 pub mod remediation;
+pub mod rules;
+pub mod scope;
+pub mod strength;
+pub mod symbols;
 pub mod types;
 
 // Re-export public API
-pub use audit::{AuditError, analyze, score_vulnerability};
+pub use audit::{AuditError, Scanner, ScannerConfig, analyze, score_vulnerability};
 pub use canadian_compliance::{
     export_itsg33_json, export_unified_json, generate_itsg33_report, generate_unified_report,
 };
@@ -20,9 +32,15 @@ pub use compliance::{
 };
 pub use parser::{ParseError, parse_file};
 pub use remediation::{CodeFix, RemediationResult, RemediationSummary, generate_remediations};
+pub use rules::{RulePack, RulePackError};
+pub use scope::ScopeIndex;
+pub use symbols::{ProjectIndex, SymbolTable};
 pub use types::{
-    AuditResult, AuditStats, CryptoType, ITSG33Report, Language, OscalAssessmentResults,
-    SC13AssessmentReport, SecurityClassification, Severity, UnifiedComplianceReport, Vulnerability,
+    AuditResult, AuditStats, ConfidenceSignal, CryptoCategory, CryptoType, DetectionMethod,
+    EnclosingScope, Explanation, ITSG33Report, Language, LanguageDetection, OscalAssessmentResults,
+    PqcFamily, QuantumSafeFinding, Resolution, SC13AssessmentReport, SecurityClassification,
+    SecurityLevel, Severity, SignalKind, SourceSpan, SpanKind, Transformation,
+    UnifiedComplianceReport, Vulnerability,
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -44,6 +62,66 @@ pub fn audit_code(source: &str, language: &str) -> Result<JsValue, JsValue> {
     serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+// WASM-compatible language detection from a file path and its content
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn detect_language(file_path: &str, source: &str) -> Result<JsValue, JsValue> {
+    let detection = Language::detect(std::path::Path::new(file_path), source);
+
+    serde_wasm_bindgen::to_value(&detection).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// WASM-compatible audit of a file whose language is detected from its path and content
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn audit_file(source: &str, file_path: &str) -> Result<JsValue, JsValue> {
+    let detection = Language::detect(std::path::Path::new(file_path), source)
+        .ok_or_else(|| JsValue::from_str(&AuditError::LanguageNotDetected.to_string()))?;
+    let result = audit::analyze(source, &detection.language.to_string())
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// WASM-compatible audit that drops findings below a confidence threshold
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn audit_code_with_min_confidence(
+    source: &str,
+    language: &str,
+    min_confidence: f32,
+) -> Result<JsValue, JsValue> {
+    let result = Scanner::default()
+        .with_min_confidence(min_confidence)
+        .analyze(source, language)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// WASM-compatible audit with an additional JSON rule pack
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn audit_code_with_rules(
+    source: &str,
+    language: &str,
+    rule_pack_json: &str,
+) -> Result<JsValue, JsValue> {
+    let pack =
+        RulePack::from_json(rule_pack_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let mut scanner = Scanner::default();
+    scanner
+        .add_rule_pack(&pack)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let result = scanner
+        .analyze(source, language)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 // WASM-compatible SC-13 compliance report generation
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
@@ -1,20 +1,26 @@
 //! Multi-language source code parser for crypto pattern detection
 //!
-//! Supports: Rust, JavaScript, TypeScript, Python, Java, Go
+//! Supports: Rust, JavaScript, TypeScript, Python, Java, Go, C/C++, C#, Kotlin, Scala,
+//! PHP, Ruby, Swift, Objective-C, shell (scripts, Dockerfiles, Makefiles), Solidity
 
+use crate::commands;
+use crate::lexer::LexedSource;
 use crate::types::*;
 use lazy_static::lazy_static;
-use regex::Regex;
+use regex::{Captures, Match, Regex};
+use std::ops::Range;
 
 // Lazy-compiled regex patterns for parsing
 lazy_static! {
     // Rust patterns
     static ref RUST_USE_RE: Regex = Regex::new(r"^\s*use\s+([^;]+);")
         .expect("RUST_USE_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref RUST_FN_CALL_RE: Regex = Regex::new(r"(\w+(?:::\w+)?)\s*\(")
+    static ref RUST_FN_CALL_RE: Regex = Regex::new(r"(\w+(?:(?:::|\.)\w+)*)\s*\(")
         .expect("RUST_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref RUST_STRUCT_RE: Regex = Regex::new(r"^\s*(?:pub\s+)?struct\s+(\w+)")
+    static ref RUST_STRUCT_RE: Regex = Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:struct|enum|trait|union)\s+(\w+)")
         .expect("RUST_STRUCT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUST_ASSIGN_RE: Regex = Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?(?:const|static|let)\s+(?:mut\s+)?(\w+)\s*(?::[^=]+)?=\s*([^=].*)$")
+        .expect("RUST_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
 
     // JavaScript/TypeScript patterns
     static ref JS_IMPORT_RE: Regex = Regex::new(r#"^\s*import\s+(?:.*?from\s+)?['"]([^'"]+)['"]"#)
@@ -23,10 +29,12 @@ lazy_static! {
         .expect("JS_REQUIRE_RE: Invalid regex pattern - this is a compile-time bug");
     static ref JS_FN_CALL_RE: Regex = Regex::new(r"(\w+)\s*\(")
         .expect("JS_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref JS_CLASS_RE: Regex = Regex::new(r"^\s*class\s+(\w+)")
+    static ref JS_CLASS_RE: Regex = Regex::new(r"^\s*(?:export\s+(?:default\s+)?)?(?:abstract\s+)?(?:class|interface)\s+([\w$]+)")
         .expect("JS_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref JS_FUNCTION_RE: Regex = Regex::new(r"^\s*(?:async\s+)?function\s+(\w+)")
+    static ref JS_FUNCTION_RE: Regex = Regex::new(r"^\s*(?:export\s+(?:default\s+)?)?(?:async\s+)?function\b\s*\*?\s*([\w$]+)")
         .expect("JS_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref JS_ASSIGN_RE: Regex = Regex::new(r"^(?:export\s+)?(?:(?:const|let|var)\s+)?([\w$]+)\s*(?::[^=]+)?=\s*([^=>].*)$")
+        .expect("JS_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
 
     // Python patterns
     static ref PY_IMPORT_RE: Regex = Regex::new(r"^\s*(?:import|from)\s+([\w.]+)")
@@ -35,26 +43,218 @@ lazy_static! {
         .expect("PY_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
     static ref PY_CLASS_RE: Regex = Regex::new(r"^\s*class\s+(\w+)")
         .expect("PY_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref PY_FUNCTION_RE: Regex = Regex::new(r"^\s*def\s+(\w+)")
+    static ref PY_FUNCTION_RE: Regex = Regex::new(r"^\s*(?:async\s+)?def\s+(\w+)")
         .expect("PY_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PY_ASSIGN_RE: Regex = Regex::new(r"^(\w+)\s*(?::[^=]+)?=\s*([^=].*)$")
+        .expect("PY_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
 
     // Java patterns
     static ref JAVA_IMPORT_RE: Regex = Regex::new(r"^\s*import\s+([\w.]+);")
         .expect("JAVA_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
     static ref JAVA_FN_CALL_RE: Regex = Regex::new(r"(\w+)\s*\(")
         .expect("JAVA_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref JAVA_CLASS_RE: Regex = Regex::new(r"^\s*(?:public\s+)?class\s+(\w+)")
+    static ref JAVA_CLASS_RE: Regex = Regex::new(r"^\s*(?:(?:public|protected|private|abstract|final|static)\s+)*(?:class|interface|enum)\s+(\w+)")
         .expect("JAVA_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref JAVA_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|static|final)\s+)*(?:[\w.<>\[\]]+\s+)?(\w+)\s*=\s*([^=].*)$")
+        .expect("JAVA_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref JAVA_CONSTANT_RE: Regex = Regex::new(r"^(?:(?:public|protected)\s+)?(?:static\s+final|final\s+static)\s")
+        .expect("JAVA_CONSTANT_RE: Invalid regex pattern - this is a compile-time bug");
 
     // Go patterns
     static ref GO_IMPORT_RE: Regex = Regex::new(r#"^\s*import\s+(?:\(|"([^"]+)")"#)
         .expect("GO_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
     static ref GO_FN_CALL_RE: Regex = Regex::new(r"(\w+)\s*\(")
         .expect("GO_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref GO_STRUCT_RE: Regex = Regex::new(r"^\s*type\s+(\w+)\s+struct")
+    static ref GO_STRUCT_RE: Regex = Regex::new(r"^\s*type\s+(\w+)\s+(?:struct|interface)")
         .expect("GO_STRUCT_RE: Invalid regex pattern - this is a compile-time bug");
-    static ref GO_FUNCTION_RE: Regex = Regex::new(r"^\s*func\s+(\w+)")
+    // `func (k *KeyStore) Rotate(` records the receiver type as `KeyStore.Rotate`
+    static ref GO_FUNCTION_RE: Regex = Regex::new(r"^\s*func\s+(?:\(\s*(?:\w+\s+)?\*?\s*(\w+)(?:\[[^\]]*\])?\s*\)\s*)?(\w+)")
         .expect("GO_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref GO_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:const|var)\s+)?(\w+)(?:\s+[\w.\[\]*]+)?\s*:?=\s*([^=].*)$")
+        .expect("GO_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref GO_PACKAGE_RE: Regex = Regex::new(r"^package\s+(\w+)")
+        .expect("GO_PACKAGE_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // C/C++ patterns
+    static ref CPP_INCLUDE_RE: Regex = Regex::new(r#"^#\s*include\s*[<"]([^>"]+)[>"]"#)
+        .expect("CPP_INCLUDE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CPP_FUNCTION_RE: Regex = Regex::new(r"^((?:[\w:<>,]+[\s*&]+)+)([A-Za-z_~][\w:~]*)\s*\(")
+        .expect("CPP_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CPP_FN_CALL_RE: Regex = Regex::new(r"([A-Za-z_]\w*(?:(?:::|\.|->)[A-Za-z_]\w*)*)\s*\(")
+        .expect("CPP_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CPP_CLASS_RE: Regex = Regex::new(r"^(?:class|struct)\s+(\w+)\s*(?:[:{]|$)")
+        .expect("CPP_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CPP_DEFINE_RE: Regex = Regex::new(r"^#\s*define\s+(\w+)\s+\(?([^()]+?)\)?\s*$")
+        .expect("CPP_DEFINE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CPP_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:static|const|constexpr|inline|extern|unsigned|signed|volatile)\s+)*(?:[\w:<>]+[\s*&]+)?(\w+)\s*(?:\[\s*\])?\s*=\s*([^=].*)$")
+        .expect("CPP_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Kotlin patterns
+    static ref KOTLIN_IMPORT_RE: Regex = Regex::new(r"^import\s+(\w+(?:\.\w+)*(?:\.\*)?)")
+        .expect("KOTLIN_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref KOTLIN_CLASS_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|internal|open|abstract|sealed|data|enum|inner|annotation|value|final)\s+)*(?:class|interface|object)\s+(\w+)")
+        .expect("KOTLIN_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref KOTLIN_FUNCTION_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|internal|open|abstract|override|suspend|inline|operator|infix)\s+)*fun\s+(?:<[^>]*>\s*)?(?:[\w.]+\.)?(\w+)\s*\(")
+        .expect("KOTLIN_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref KOTLIN_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|internal|const|override|lateinit)\s+)*(?:val|var)\s+(\w+)(?:\s*:\s*[\w.<>?]+)?\s*=\s*([^=].*)$")
+        .expect("KOTLIN_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Scala patterns
+    static ref SCALA_IMPORT_RE: Regex = Regex::new(r"^import\s+([\w.]+?)(?:\.\{([^}]*)\}|\._|\.\*)?\s*$")
+        .expect("SCALA_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SCALA_CLASS_RE: Regex = Regex::new(r"^(?:(?:private|protected|final|sealed|abstract|implicit|case|open)\s+)*(class|trait|object|enum)\s+(\w+)")
+        .expect("SCALA_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SCALA_FUNCTION_RE: Regex = Regex::new(r"^(?:(?:private|protected|final|override|implicit|inline)(?:\[\w+\])?\s+)*def\s+(\w+)")
+        .expect("SCALA_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SCALA_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:private|protected|final|override|lazy|implicit)(?:\[\w+\])?\s+)*(?:val|var)\s+(\w+)(?:\s*:\s*[\w.\[\]]+)?\s*=\s*([^=>].*)$")
+        .expect("SCALA_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // PHP patterns
+    static ref PHP_USE_RE: Regex = Regex::new(r"^use\s+\\?([\w\\]+)(?:\s+as\s+\w+)?\s*;")
+        .expect("PHP_USE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_REQUIRE_RE: Regex = Regex::new(r#"^(?:require|include)(?:_once)?\s*\(?\s*['"]([^'"]+)['"]"#)
+        .expect("PHP_REQUIRE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_CLASS_RE: Regex = Regex::new(r"^(?:(?:abstract|final|readonly)\s+)*(?:class|interface|trait|enum)\s+(\w+)")
+        .expect("PHP_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_FUNCTION_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|static|abstract|final)\s+)*function\s+&?(\w+)\s*\(")
+        .expect("PHP_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_FN_CALL_RE: Regex = Regex::new(r"(\$?[A-Za-z_\\][\w\\]*(?:(?:->|::)[A-Za-z_]\w*)*)\s*\(")
+        .expect("PHP_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|static|readonly|var)\s+)*(?:\??[\w\\]+\s+)?(\$\w+)\s*=\s*([^=>].*)$")
+        .expect("PHP_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_CONST_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|final)\s+)*const\s+(?:[\w\\]+\s+)?(\w+)\s*=\s*([^=>].*)$")
+        .expect("PHP_CONST_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PHP_DEFINE_RE: Regex = Regex::new(r#"^define\s*\(\s*['"](\w+)['"]\s*,\s*(.+?)\s*\)\s*;"#)
+        .expect("PHP_DEFINE_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Ruby patterns
+    static ref RUBY_REQUIRE_RE: Regex = Regex::new(r#"^require(?:_relative)?\s*\(?\s*['"]([^'"]+)['"]"#)
+        .expect("RUBY_REQUIRE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUBY_CLASS_RE: Regex = Regex::new(r"^(class|module)\s+([A-Z][\w:]*)")
+        .expect("RUBY_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUBY_FUNCTION_RE: Regex = Regex::new(r"^def\s+(?:self\.)?(\w+[?!=]?)")
+        .expect("RUBY_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUBY_FN_CALL_RE: Regex = Regex::new(r"([A-Za-z_][\w:]*(?:\.[A-Za-z_]\w*[?!]?)*)\s*\(")
+        .expect("RUBY_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUBY_BARE_CALL_RE: Regex = Regex::new(r#"\b([A-Za-z_][\w:]*(?:\.[A-Za-z_]\w*[?!]?)+)[ \t]+(["'@$\w\[]|:\w)"#)
+        .expect("RUBY_BARE_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    // Statement modifiers and blocks that end an argument list without parentheses
+    static ref RUBY_MODIFIER_RE: Regex = Regex::new(r"^[ \t]+(?:if|unless|while|until|do|and|or|then|rescue)\b")
+        .expect("RUBY_MODIFIER_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUBY_ASSIGN_RE: Regex = Regex::new(r"^@{0,2}(\w+)\s*=\s*([^=~>].*)$")
+        .expect("RUBY_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Kotlin and Scala calls
+    static ref JVM_FN_CALL_RE: Regex = Regex::new(r"([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\(")
+        .expect("JVM_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // C# patterns
+    static ref CSHARP_USING_RE: Regex = Regex::new(r"^(?:global\s+)?using\s+(?:static\s+)?(?:\w+\s*=\s*)?([\w.]+)\s*;")
+        .expect("CSHARP_USING_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CSHARP_CLASS_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|internal|static|sealed|abstract|partial|readonly)\s+)*(?:class|struct|interface|record|enum)\s+(\w+)")
+        .expect("CSHARP_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CSHARP_FN_CALL_RE: Regex = Regex::new(r"([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\(")
+        .expect("CSHARP_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CSHARP_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:public|private|protected|internal|static|readonly|const|new)\s+)*(?:[\w.<>\[\]?]+\s+)?(\w+)\s*=\s*([^=>].*)$")
+        .expect("CSHARP_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref CSHARP_CONSTANT_RE: Regex = Regex::new(r"^(?:(?:public|internal|protected)\s+)*(?:const|static\s+readonly|readonly\s+static)\s")
+        .expect("CSHARP_CONSTANT_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Literal or identifier right-hand side, with an optional trailing comment
+    static ref ASSIGNED_VALUE_RE: Regex = Regex::new(
+        r#"^(?:(\d[\d_]*)(?:[iu](?:8|16|32|64|128|size)|[lL])?|"([^"\\]*)"|'([^'\\]*)'|`([^`\\$]*)`|([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)*))\s*[;,]?\s*(?:(?://|#).*)?$"#
+    ).expect("ASSIGNED_VALUE_RE: Invalid regex pattern - this is a compile-time bug");
+}
+
+// A second block keeps `lazy_static!` under the macro recursion limit
+lazy_static! {
+    // Swift patterns
+    static ref SWIFT_IMPORT_RE: Regex = Regex::new(r"^(?:@\w+\s+)*import\s+(?:(?:typealias|struct|class|enum|protocol|let|var|func)\s+)?([\w.]+)")
+        .expect("SWIFT_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SWIFT_CLASS_RE: Regex = Regex::new(r"^(?:(?:public|private|fileprivate|internal|open|final|indirect)\s+)*(?:class|struct|enum|actor|protocol|extension)\s+(\w+)")
+        .expect("SWIFT_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SWIFT_FUNCTION_RE: Regex = Regex::new(r"^(?:(?:@\w+|public|private|fileprivate|internal|open|final|static|class|override|mutating|nonisolated)\s+)*func\s+(\w+)")
+        .expect("SWIFT_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SWIFT_FN_CALL_RE: Regex = Regex::new(r"([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\(")
+        .expect("SWIFT_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SWIFT_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:public|private|fileprivate|internal|open|static|class|final|lazy|nonisolated)\s+)*(?:let|var)\s+(\w+)(?:\s*:\s*[\w.<>?\[\]]+)?\s*=\s*([^=].*)$")
+        .expect("SWIFT_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Objective-C patterns (C functions and macros use the C/C++ patterns)
+    static ref OBJC_IMPORT_RE: Regex = Regex::new(r#"^(?:#\s*(?:import|include)\s*[<"]|@import\s+)([\w./+-]+)"#)
+        .expect("OBJC_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref OBJC_CLASS_RE: Regex = Regex::new(r"^@(?:interface|implementation|protocol)\s+(\w+)")
+        .expect("OBJC_CLASS_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref OBJC_METHOD_RE: Regex = Regex::new(r"^[-+]\s*\([^)]*\)\s*(\w+)")
+        .expect("OBJC_METHOD_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref OBJC_MESSAGE_RE: Regex = Regex::new(r"\[\s*([A-Za-z_]\w*)\s+([A-Za-z_]\w*)")
+        .expect("OBJC_MESSAGE_RE: Invalid regex pattern - this is a compile-time bug");
+    // Shell, Dockerfile and Makefile patterns
+    static ref SHELL_SOURCE_RE: Regex = Regex::new(r"^(?:source|\.|-?include|FROM(?:\s+--\S+)*)\s+(\S+)")
+        .expect("SHELL_SOURCE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SHELL_FUNCTION_RE: Regex = Regex::new(r"^(?:function\s+([\w-]+)|([\w-]+)\s*\(\s*\))")
+        .expect("SHELL_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref MAKE_TARGET_RE: Regex = Regex::new(r"^([\w./%-]+)\s*:(?:[^=]|$)")
+        .expect("MAKE_TARGET_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SHELL_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:export|readonly|local|declare(?:\s+-\w+)*|ENV|ARG|override)\s+)?([A-Za-z_]\w*)(?:=|\s*(?:::?|\?|\+)?=\s*)(.*)$")
+        .expect("SHELL_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SHELL_VALUE_RE: Regex = Regex::new(r#"^(?:(\d+)|"([^"$`\\]*)"|'([^']*)'|"?\$(?:\{(\w+)\}|\((\w+)\)|(\w+))"?|([\w./:@+-]+))\s*(?:#.*)?$"#)
+        .expect("SHELL_VALUE_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // A keyword starting the next part of a message send: ` length:`
+    static ref OBJC_SELECTOR_PART_RE: Regex = Regex::new(r"^\s+([A-Za-z_]\w*)\s*:")
+        .expect("OBJC_SELECTOR_PART_RE: Invalid regex pattern - this is a compile-time bug");
+
+    static ref OBJC_ASSIGN_RE: Regex = Regex::new(r"^(?:(?:static|const|extern|unsigned|signed|volatile)\s+)*(?:[\w<>]+[\s*]+)?(?:const\s+)?(\w+)\s*=\s*@?([^=].*)$")
+        .expect("OBJC_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Solidity patterns
+    static ref SOLIDITY_IMPORT_RE: Regex = Regex::new(r#"^import\s+(?:[^'";]*\bfrom\s+)?["']([^"']+)["']"#)
+        .expect("SOLIDITY_IMPORT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SOLIDITY_CONTRACT_RE: Regex = Regex::new(r"^(?:abstract\s+)?(?:contract|library|interface)\s+(\w+)")
+        .expect("SOLIDITY_CONTRACT_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SOLIDITY_FUNCTION_RE: Regex = Regex::new(r"^(?:function|modifier)\s+(\w+)")
+        .expect("SOLIDITY_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SOLIDITY_FN_CALL_RE: Regex = Regex::new(r"([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\(")
+        .expect("SOLIDITY_FN_CALL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref SOLIDITY_ASSIGN_RE: Regex = Regex::new(r"^(?:[\w.]+(?:\[\d*\])?\s+)?(?:(?:public|private|internal|constant|immutable|override|memory|storage|calldata)\s+)*(\w+)\s*=\s*([^=>].*)$")
+        .expect("SOLIDITY_ASSIGN_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Call argument patterns
+    static ref ARG_NAME_RE: Regex = Regex::new(r"^([A-Za-z_]\w*)\s*[=:]")
+        .expect("ARG_NAME_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref ARG_INTEGER_RE: Regex = Regex::new(r"^(?:0[xX]([0-9a-fA-F_]+)|(\d[\d_]*))(?:[uUlL]{1,3}|_?[ui](?:8|16|32|64|128|size))?$")
+        .expect("ARG_INTEGER_RE: Invalid regex pattern - this is a compile-time bug");
+    // Negative or floating-point number, with an optional type suffix
+    static ref ARG_NUMBER_RE: Regex = Regex::new(r"^(-\s*)?(\d[\d_]*(?:\.\d[\d_]*)?(?:[eE][+-]?\d+)?|\.\d[\d_]*(?:[eE][+-]?\d+)?)(?:[fFdDmMlL]|_?f(?:32|64))?$")
+        .expect("ARG_NUMBER_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref ARG_IDENTIFIER_RE: Regex = Regex::new(r"^\$?[A-Za-z_][\w$]*(?:(?:\.|::|->)\$?[A-Za-z_][\w$]*)*$")
+        .expect("ARG_IDENTIFIER_RE: Invalid regex pattern - this is a compile-time bug");
+
+    // Declarations for scope tracking
+    static ref RUST_FUNCTION_RE: Regex = Regex::new(r#"^(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*fn\s+(\w+)"#)
+        .expect("RUST_FUNCTION_RE: Invalid regex pattern - this is a compile-time bug");
+    // `impl<T> Signer for KeyPair<T>` is attributed to `KeyPair`
+    static ref RUST_IMPL_RE: Regex = Regex::new(r"^(?:unsafe\s+)?impl(?:<[^{]*?>)?\s+(?:[\w:]+(?:<[^{]*?>)?\s+for\s+)?([\w:]+)")
+        .expect("RUST_IMPL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref RUST_MOD_RE: Regex = Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{")
+        .expect("RUST_MOD_RE: Invalid regex pattern - this is a compile-time bug");
+    // Class members and object-literal methods: `async sign(data) {`
+    static ref JS_METHOD_RE: Regex = Regex::new(r"^(?:(?:static|async|get|set|public|private|protected|readonly|override)\s+)*\*?\s*([A-Za-z_$][\w$]*)\s*(?:<[^>]*>)?\s*\([^()]*\)\s*(?::\s*[^{=]+)?\{")
+        .expect("JS_METHOD_RE: Invalid regex pattern - this is a compile-time bug");
+    // `const sign = async (data) => {` and `let hash = function (data) {`
+    static ref JS_ARROW_RE: Regex = Regex::new(r"^(?:export\s+)?(?:const|let|var)\s+([\w$]+)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:function\b|(?:\([^)]*\)|[\w$]+)\s*(?::\s*[^=]+)?=>)")
+        .expect("JS_ARROW_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref TS_NAMESPACE_RE: Regex = Regex::new(r"^(?:export\s+)?(?:declare\s+)?(?:namespace|module)\s+([\w.]+)\s*\{")
+        .expect("TS_NAMESPACE_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref PACKAGE_RE: Regex = Regex::new(r"^package\s+([\w.]+)\s*(?:;|\{|$)")
+        .expect("PACKAGE_RE: Invalid regex pattern - this is a compile-time bug");
+    // Java and C# methods and constructors: `public static KeyPair generate(int bits) {`,
+    // including one-line and `=>` expression bodies
+    static ref METHOD_DECL_RE: Regex = Regex::new(r"^(?:@\w+(?:\([^)]*\))?\s+|\[[^\]]*\]\s*)*((?:(?:public|protected|private|internal|static|final|abstract|synchronized|native|virtual|override|sealed|async|extern|unsafe|partial|default|strictfp)\s+)*)(?:<[^>]+>\s+)?(?:([\w.<>\[\],?]+)\s+)?(\w+)\s*\((?:[^;]*$|[^;]*\)\s*(?:=>|\{))")
+        .expect("METHOD_DECL_RE: Invalid regex pattern - this is a compile-time bug");
+    static ref NAMESPACE_RE: Regex = Regex::new(r"^(?:inline\s+)?namespace\s+([\w.:\\]+)")
+        .expect("NAMESPACE_RE: Invalid regex pattern - this is a compile-time bug");
 }
 
 /// Parser errors
@@ -76,13 +276,183 @@ pub enum ParseError {
 // Input validation constants
 const MAX_PARSE_SIZE: usize = 5 * 1024 * 1024; // 5MB (smaller than audit since parsing is more expensive)
 
+// Call argument limits; longer argument lists are truncated, longer spans treated as unterminated
+const MAX_CALL_ARGUMENTS: usize = 32;
+const MAX_ARGUMENT_LIST_LEN: usize = 4096;
+
+/// C# keywords that look like a call when followed by `(`
+const CSHARP_KEYWORDS: &[&str] = &[
+    "if",
+    "for",
+    "foreach",
+    "while",
+    "switch",
+    "using",
+    "lock",
+    "catch",
+    "return",
+    "nameof",
+    "typeof",
+    "sizeof",
+    "default",
+    "checked",
+    "unchecked",
+    "when",
+    "fixed",
+];
+
+/// Kotlin and Scala keywords that look like a call when followed by `(`
+const JVM_KEYWORDS: &[&str] = &[
+    "if",
+    "for",
+    "while",
+    "when",
+    "match",
+    "catch",
+    "return",
+    "throw",
+    "synchronized",
+    "fun",
+    "def",
+];
+
+/// PHP keywords and language constructs that look like a call when followed by `(`
+const PHP_KEYWORDS: &[&str] = &[
+    "if", "elseif", "while", "for", "foreach", "switch", "match", "array", "list", "isset",
+    "empty", "unset", "function", "fn", "return", "echo", "print", "catch", "declare",
+];
+
+/// Ruby keywords that look like a call when followed by `(`
+const RUBY_KEYWORDS: &[&str] = &[
+    "if", "unless", "while", "until", "case", "when", "return", "and", "or", "not", "defined",
+    "puts", "super",
+];
+
+/// Swift keywords and declarations that look like a call when followed by `(`
+const SWIFT_KEYWORDS: &[&str] = &[
+    "if",
+    "guard",
+    "while",
+    "switch",
+    "return",
+    "catch",
+    "func",
+    "init",
+    "subscript",
+    "case",
+    "throw",
+    "repeat",
+];
+
+/// Solidity keywords and declarations that look like a call when followed by `(`
+const SOLIDITY_KEYWORDS: &[&str] = &[
+    "if",
+    "for",
+    "while",
+    "return",
+    "returns",
+    "function",
+    "modifier",
+    "event",
+    "error",
+    "mapping",
+    "constructor",
+    "catch",
+];
+
+/// C/C++ keywords that look like a call or declaration when followed by `(`
+const CPP_KEYWORDS: &[&str] = &[
+    "if",
+    "for",
+    "while",
+    "switch",
+    "return",
+    "sizeof",
+    "catch",
+    "else",
+    "new",
+    "delete",
+    "throw",
+    "case",
+    "do",
+    "alignof",
+    "decltype",
+    "static_assert",
+];
+
+/// Keywords that can precede `name(` on a line that does not declare a function
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "if",
+    "else",
+    "for",
+    "foreach",
+    "while",
+    "do",
+    "switch",
+    "case",
+    "return",
+    "new",
+    "throw",
+    "catch",
+    "await",
+    "yield",
+    "using",
+    "lock",
+    "function",
+    "typeof",
+    "sizeof",
+    "synchronized",
+    "super",
+    "this",
+];
+
 /// Main parsing function - dispatches to language-specific parsers
 pub fn parse_file(source: &str, language: &str) -> Result<ParsedSource, ParseError> {
     // Validate language
     let lang = Language::from_string(language)
         .ok_or_else(|| ParseError::UnsupportedLanguage(language.to_string()))?;
+    validate_source(source)?;
+
+    let code =
+        LexedSource::new(source, lang).scan_text(|span| span.kind == SpanKind::StringLiteral);
+    parse_code(source, &code, lang)
+}
+
+/// Parse `source` given `code`, the same text with its comments blanked out
+/// (as `LexedSource::scan_text` keeping string literals produces it), which
+/// call argument lists are read from
+pub(crate) fn parse_code(
+    source: &str,
+    code: &str,
+    lang: Language,
+) -> Result<ParsedSource, ParseError> {
+    validate_source(source)?;
+    let calls = CallText::new(code);
+
+    match lang {
+        Language::Rust => parse_rust(source, &calls),
+        Language::JavaScript => parse_javascript(source, &calls),
+        Language::TypeScript => parse_typescript(source, &calls),
+        Language::Python => parse_python(source, &calls),
+        Language::Java => parse_java(source, &calls),
+        Language::Go => parse_go(source, &calls),
+        Language::Cpp => parse_cpp(source, &calls),
+        Language::Csharp => parse_csharp(source, &calls),
+        Language::Kotlin => parse_kotlin(source, &calls),
+        Language::Scala => parse_scala(source, &calls),
+        Language::Php => parse_php(source, &calls),
+        Language::Ruby => parse_ruby(source, &calls),
+        Language::Swift => parse_swift(source, &calls),
+        Language::ObjectiveC => parse_objc(source, &calls),
+        Language::Shell => parse_shell(source, &calls),
+        Language::Solidity => parse_solidity(source, &calls),
+        // JSON documents hold no imports, declarations or calls
+        Language::Json => Ok(ParsedSource::new(Language::Json)),
+    }
+}
 
-    // Validate source size
+/// Validate source size
+fn validate_source(source: &str) -> Result<(), ParseError> {
     if source.is_empty() {
         return Err(ParseError::InvalidSource);
     }
@@ -91,20 +461,54 @@ pub fn parse_file(source: &str, language: &str) -> Result<ParsedSource, ParseErr
     if source_size > MAX_PARSE_SIZE {
         return Err(ParseError::SourceTooLarge(source_size, MAX_PARSE_SIZE));
     }
+    Ok(())
+}
 
-    match lang {
-        Language::Rust => parse_rust(source),
-        Language::JavaScript => parse_javascript(source),
-        Language::TypeScript => parse_typescript(source),
-        Language::Python => parse_python(source),
-        Language::Java => parse_java(source),
-        Language::Go => parse_go(source),
-        _ => Err(ParseError::UnsupportedLanguage(language.to_string())),
-    }
+/// Record `trimmed` as an assignment if it binds a name to a literal or
+/// another identifier; anything more complex is ignored. Returns the new
+/// assignment so callers can mark it exported.
+fn push_assignment<'a>(
+    parsed: &'a mut ParsedSource,
+    assign_re: &Regex,
+    trimmed: &str,
+    line_num: usize,
+) -> Option<&'a mut Assignment> {
+    let caps = assign_re.captures(trimmed)?;
+    let (name, rhs) = (caps.get(1)?, caps.get(2)?);
+    let value_caps = ASSIGNED_VALUE_RE.captures(rhs.as_str().trim())?;
+
+    let value = if let Some(digits) = value_caps.get(1) {
+        let n = digits.as_str().replace('_', "").parse().ok()?;
+        AssignedValue::Literal(ConstantValue::Integer(n))
+    } else if let Some(text) = (2..=4).find_map(|i| value_caps.get(i)) {
+        AssignedValue::Literal(ConstantValue::String(text.as_str().to_string()))
+    } else {
+        let text = value_caps.get(5)?.as_str();
+        keyword_literal(text).map_or_else(
+            || AssignedValue::Identifier(text.to_string()),
+            AssignedValue::Literal,
+        )
+    };
+
+    let name = name.as_str().to_string();
+    parsed.ast_nodes.push(AstNode {
+        node_type: NodeType::VariableDeclaration,
+        line: line_num,
+        column: 0,
+        content: name.clone(),
+    });
+    parsed.assignments.push(Assignment {
+        name,
+        value,
+        line: line_num,
+        exported: false,
+        qualifier: None,
+    });
+    parsed.assignments.last_mut()
 }
 
 /// Parse Rust source code
-fn parse_rust(source: &str) -> Result<ParsedSource, ParseError> {
+fn parse_rust(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
     let mut parsed = ParsedSource::new(Language::Rust);
 
     for (line_num, line) in source.lines().enumerate() {
@@ -115,6 +519,10 @@ fn parse_rust(source: &str) -> Result<ParsedSource, ParseError> {
             continue;
         }
 
+        if let Some(assignment) = push_assignment(&mut parsed, &RUST_ASSIGN_RE, trimmed, line_num) {
+            assignment.exported = trimmed.starts_with("pub");
+        }
+
         if let Some(caps) = RUST_USE_RE.captures(trimmed)
             && let Some(import_match) = caps.get(1)
         {
@@ -139,17 +547,36 @@ fn parse_rust(source: &str) -> Result<ParsedSource, ParseError> {
             });
         }
 
+        if let Some(caps) = RUST_IMPL_RE.captures(trimmed) {
+            let self_type = caps[1].rsplit("::").next().unwrap_or_default();
+            push_declaration(
+                &mut parsed,
+                NodeType::ClassDeclaration,
+                self_type,
+                line,
+                line_num,
+            );
+        } else if let Some(caps) = RUST_MOD_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        }
+        if let Some(caps) = RUST_FUNCTION_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::FunctionDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        }
+
         for caps in RUST_FN_CALL_RE.captures_iter(trimmed) {
-            if let Some(fn_match) = caps.get(1) {
-                let fn_name = fn_match.as_str().to_string();
-                let column = line.find(&fn_name).unwrap_or(0);
-                parsed.function_calls.push(FunctionCall {
-                    name: fn_name.clone(),
-                    line: line_num,
-                    column,
-                    args: vec![],
-                });
-            }
+            calls.push(&mut parsed, line, line_num, &caps);
         }
     }
 
@@ -157,12 +584,12 @@ fn parse_rust(source: &str) -> Result<ParsedSource, ParseError> {
 }
 
 /// Parse JavaScript source code
-fn parse_javascript(source: &str) -> Result<ParsedSource, ParseError> {
+fn parse_javascript(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
     let mut parsed = ParsedSource::new(Language::JavaScript);
 
     let import_re = Regex::new(r#"^\s*import\s+.*from\s+['"]([^'"]+)['"]"#).unwrap();
     let require_re = Regex::new(r#"require\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap();
-    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)?)\s*\(").unwrap();
+    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)*)\s*\(").unwrap();
 
     for (line_num, line) in source.lines().enumerate() {
         let line_num = line_num + 1;
@@ -172,6 +599,41 @@ fn parse_javascript(source: &str) -> Result<ParsedSource, ParseError> {
             continue;
         }
 
+        if let Some(assignment) = push_assignment(&mut parsed, &JS_ASSIGN_RE, trimmed, line_num) {
+            assignment.exported = trimmed.starts_with("export ");
+        }
+
+        if let Some(caps) = JS_CLASS_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ClassDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        } else if let Some(caps) = TS_NAMESPACE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        } else if let Some(caps) = JS_FUNCTION_RE
+            .captures(trimmed)
+            .or_else(|| JS_ARROW_RE.captures(trimmed))
+            .or_else(|| JS_METHOD_RE.captures(trimmed))
+            && !STATEMENT_KEYWORDS.contains(&&caps[1])
+        {
+            push_declaration(
+                &mut parsed,
+                NodeType::FunctionDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        }
+
         if let Some(caps) = import_re.captures(trimmed) {
             let import = caps.get(1).unwrap().as_str().to_string();
             parsed.imports.push(import.clone());
@@ -195,32 +657,25 @@ fn parse_javascript(source: &str) -> Result<ParsedSource, ParseError> {
         }
 
         for caps in fn_call_re.captures_iter(trimmed) {
-            let fn_name = caps.get(1).unwrap().as_str().to_string();
-            let column = line.find(&fn_name).unwrap_or(0);
-            parsed.function_calls.push(FunctionCall {
-                name: fn_name.clone(),
-                line: line_num,
-                column,
-                args: vec![],
-            });
+            calls.push(&mut parsed, line, line_num, &caps);
         }
     }
 
     Ok(parsed)
 }
 
-fn parse_typescript(source: &str) -> Result<ParsedSource, ParseError> {
-    let mut result = parse_javascript(source)?;
+fn parse_typescript(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut result = parse_javascript(source, calls)?;
     result.language = Language::TypeScript;
     Ok(result)
 }
 
-fn parse_python(source: &str) -> Result<ParsedSource, ParseError> {
+fn parse_python(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
     let mut parsed = ParsedSource::new(Language::Python);
 
     let import_re = Regex::new(r"^\s*import\s+(.+)").unwrap();
     let from_import_re = Regex::new(r"^\s*from\s+([^\s]+)\s+import").unwrap();
-    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)?)\s*\(").unwrap();
+    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)*)\s*\(").unwrap();
 
     for (line_num, line) in source.lines().enumerate() {
         let line_num = line_num + 1;
@@ -230,6 +685,29 @@ fn parse_python(source: &str) -> Result<ParsedSource, ParseError> {
             continue;
         }
 
+        if let Some(assignment) = push_assignment(&mut parsed, &PY_ASSIGN_RE, trimmed, line_num) {
+            // Module-level names can be imported by other modules
+            assignment.exported = !line.starts_with(char::is_whitespace);
+        }
+
+        if let Some(caps) = PY_CLASS_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ClassDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        } else if let Some(caps) = PY_FUNCTION_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::FunctionDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        }
+
         if let Some(caps) = from_import_re.captures(trimmed) {
             let import = caps.get(1).unwrap().as_str().to_string();
             parsed.imports.push(import.clone());
@@ -251,25 +729,19 @@ fn parse_python(source: &str) -> Result<ParsedSource, ParseError> {
         }
 
         for caps in fn_call_re.captures_iter(trimmed) {
-            let fn_name = caps.get(1).unwrap().as_str().to_string();
-            let column = line.find(&fn_name).unwrap_or(0);
-            parsed.function_calls.push(FunctionCall {
-                name: fn_name.clone(),
-                line: line_num,
-                column,
-                args: vec![],
-            });
+            calls.push(&mut parsed, line, line_num, &caps);
         }
     }
 
     Ok(parsed)
 }
 
-fn parse_java(source: &str) -> Result<ParsedSource, ParseError> {
+fn parse_java(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
     let mut parsed = ParsedSource::new(Language::Java);
+    let mut current_class = None;
 
     let import_re = Regex::new(r"^\s*import\s+([^;]+);").unwrap();
-    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)?)\s*\(").unwrap();
+    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)*)\s*\(").unwrap();
 
     for (line_num, line) in source.lines().enumerate() {
         let line_num = line_num + 1;
@@ -279,6 +751,42 @@ fn parse_java(source: &str) -> Result<ParsedSource, ParseError> {
             continue;
         }
 
+        if let Some(caps) = PACKAGE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+            continue;
+        }
+
+        if let Some(caps) = JAVA_CLASS_RE.captures(trimmed) {
+            let class = caps[1].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: class.clone(),
+            });
+            current_class = Some(class);
+        } else if let Some(name) = method_declaration(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::FunctionDeclaration,
+                name,
+                line,
+                line_num,
+            );
+        }
+
+        // `static final` fields are referenced elsewhere as `Class.FIELD`
+        if let Some(assignment) = push_assignment(&mut parsed, &JAVA_ASSIGN_RE, trimmed, line_num) {
+            assignment.exported = JAVA_CONSTANT_RE.is_match(trimmed);
+            assignment.qualifier = current_class.clone();
+        }
+
         if let Some(caps) = import_re.captures(trimmed) {
             let import = caps.get(1).unwrap().as_str().trim().to_string();
             parsed.imports.push(import.clone());
@@ -291,25 +799,20 @@ fn parse_java(source: &str) -> Result<ParsedSource, ParseError> {
         }
 
         for caps in fn_call_re.captures_iter(trimmed) {
-            let fn_name = caps.get(1).unwrap().as_str().to_string();
-            let column = line.find(&fn_name).unwrap_or(0);
-            parsed.function_calls.push(FunctionCall {
-                name: fn_name.clone(),
-                line: line_num,
-                column,
-                args: vec![],
-            });
+            calls.push(&mut parsed, line, line_num, &caps);
         }
     }
 
     Ok(parsed)
 }
 
-fn parse_go(source: &str) -> Result<ParsedSource, ParseError> {
+fn parse_go(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
     let mut parsed = ParsedSource::new(Language::Go);
+    let mut package = None;
+    let mut in_decl_block = false;
 
     let import_re = Regex::new(r#"^\s*import\s+"([^"]+)""#).unwrap();
-    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)?)\s*\(").unwrap();
+    let fn_call_re = Regex::new(r"(\w+(?:\.\w+)*)\s*\(").unwrap();
 
     for (line_num, line) in source.lines().enumerate() {
         let line_num = line_num + 1;
@@ -319,6 +822,58 @@ fn parse_go(source: &str) -> Result<ParsedSource, ParseError> {
             continue;
         }
 
+        if let Some(caps) = GO_PACKAGE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+            package = Some(caps[1].to_string());
+        }
+
+        if let Some(caps) = GO_STRUCT_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ClassDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        } else if let Some(caps) = GO_FUNCTION_RE.captures(trimmed) {
+            let name = match caps.get(1) {
+                Some(receiver) => format!("{}.{}", receiver.as_str(), &caps[2]),
+                None => caps[2].to_string(),
+            };
+            push_declaration(
+                &mut parsed,
+                NodeType::FunctionDeclaration,
+                &name,
+                line,
+                line_num,
+            );
+        }
+
+        // Package-level declarations, either single-line or in a `const (...)` block
+        let top_level = !line.starts_with(char::is_whitespace);
+        if top_level && (trimmed.starts_with("const (") || trimmed.starts_with("var (")) {
+            in_decl_block = true;
+        } else if top_level && trimmed.starts_with(')') {
+            in_decl_block = false;
+        }
+        let package_level = in_decl_block
+            || (top_level && (trimmed.starts_with("const ") || trimmed.starts_with("var ")));
+
+        if let Some(assignment) = push_assignment(&mut parsed, &GO_ASSIGN_RE, trimmed, line_num) {
+            // Only capitalized package-level names are visible to other packages
+            assignment.exported = package_level
+                && assignment
+                    .name
+                    .starts_with(|c: char| c.is_ascii_uppercase());
+            assignment.qualifier = package.clone();
+        }
+
         if let Some(caps) = import_re.captures(trimmed) {
             let import = caps.get(1).unwrap().as_str().to_string();
             parsed.imports.push(import.clone());
@@ -331,55 +886,1819 @@ fn parse_go(source: &str) -> Result<ParsedSource, ParseError> {
         }
 
         for caps in fn_call_re.captures_iter(trimmed) {
-            let fn_name = caps.get(1).unwrap().as_str().to_string();
-            let column = line.find(&fn_name).unwrap_or(0);
-            parsed.function_calls.push(FunctionCall {
-                name: fn_name.clone(),
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parse C or C++ source code
+fn parse_cpp(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Cpp);
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty()
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*')
+        {
+            continue;
+        }
+
+        if let Some(caps) = CPP_INCLUDE_RE.captures(trimmed) {
+            let import = caps[1].to_string();
+            parsed.imports.push(import.clone());
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::Import,
+                line: line_num,
+                column: 0,
+                content: import,
+            });
+            continue;
+        }
+
+        // Macros are visible to every file that includes the header
+        if trimmed.starts_with('#') {
+            if let Some(assignment) =
+                push_assignment(&mut parsed, &CPP_DEFINE_RE, trimmed, line_num)
+            {
+                assignment.exported = true;
+            }
+            continue;
+        }
+
+        if let Some(assignment) = push_assignment(&mut parsed, &CPP_ASSIGN_RE, trimmed, line_num) {
+            assignment.exported =
+                !line.starts_with(char::is_whitespace) && !trimmed.starts_with("static");
+        }
+
+        if let Some(caps) = NAMESPACE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+        }
+
+        if let Some(caps) = CPP_CLASS_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
                 line: line_num,
-                column,
-                args: vec![],
+                column: 0,
+                content: caps[1].to_string(),
             });
         }
+
+        // `int RSA_keygen(int bits) {`, but not `return f(x);`
+        let mut declared = None;
+        if let Some(caps) = CPP_FUNCTION_RE.captures(trimmed) {
+            let first_word = caps[1].split_whitespace().next().unwrap_or_default();
+            let name = &caps[2];
+            if !CPP_KEYWORDS.contains(&first_word) && !CPP_KEYWORDS.contains(&name) {
+                parsed.ast_nodes.push(AstNode {
+                    node_type: NodeType::FunctionDeclaration,
+                    line: line_num,
+                    column: line.find(name).unwrap_or(0),
+                    content: name.to_string(),
+                });
+                declared = Some(name.to_string());
+            }
+        }
+
+        for caps in CPP_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            if CPP_KEYWORDS.contains(&fn_name) || declared.as_deref() == Some(fn_name) {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
     }
 
     Ok(parsed)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parse C# source code
+fn parse_csharp(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Csharp);
+    let mut current_class = None;
 
-    #[test]
-    fn test_parse_rust() {
-        let source = "use std::collections::HashMap;\nlet x = HashMap::new();";
-        let result = parse_file(source, "rust").unwrap();
-        assert_eq!(result.imports.len(), 1);
-        assert!(
-            result
-                .function_calls
-                .iter()
-                .any(|f| f.name.contains("HashMap"))
-        );
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+
+        if let Some(caps) = CSHARP_USING_RE.captures(trimmed) {
+            let import = caps[1].to_string();
+            parsed.imports.push(import.clone());
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::Import,
+                line: line_num,
+                column: 0,
+                content: import,
+            });
+            continue;
+        }
+
+        if let Some(caps) = NAMESPACE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+            continue;
+        }
+
+        if let Some(caps) = CSHARP_CLASS_RE.captures(trimmed) {
+            let class = caps[1].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: class.clone(),
+            });
+            current_class = Some(class);
+        } else if let Some(name) = method_declaration(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::FunctionDeclaration,
+                name,
+                line,
+                line_num,
+            );
+        }
+
+        // `const` and `static readonly` members are referenced elsewhere as `Class.Name`
+        if let Some(assignment) = push_assignment(&mut parsed, &CSHARP_ASSIGN_RE, trimmed, line_num)
+        {
+            assignment.exported = CSHARP_CONSTANT_RE.is_match(trimmed);
+            assignment.qualifier = current_class.clone();
+        }
+
+        for caps in CSHARP_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            if CSHARP_KEYWORDS.contains(&fn_name) {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
     }
 
-    #[test]
-    fn test_parse_javascript() {
-        let source = "import crypto from 'crypto';\ncrypto.createCipher('aes', key);";
-        let result = parse_file(source, "javascript").unwrap();
-        assert!(!result.imports.is_empty());
-        assert!(
-            result
-                .function_calls
-                .iter()
-                .any(|f| f.name.contains("crypto"))
-        );
+    Ok(parsed)
+}
+
+fn parse_kotlin(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Kotlin);
+    let mut current_class = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+
+        if let Some(caps) = PACKAGE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+            continue;
+        }
+
+        if let Some(caps) = KOTLIN_IMPORT_RE.captures(trimmed) {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        if let Some(caps) = KOTLIN_CLASS_RE.captures(trimmed) {
+            let class = caps[1].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: class.clone(),
+            });
+            current_class = Some(class);
+        }
+
+        if let Some(caps) = KOTLIN_FUNCTION_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+        }
+
+        // `const val` is referenced as `NAME` at top level or `Object.NAME` inside one
+        if let Some(assignment) = push_assignment(&mut parsed, &KOTLIN_ASSIGN_RE, trimmed, line_num)
+        {
+            assignment.exported =
+                trimmed.split_whitespace().any(|w| w == "const") && !trimmed.starts_with("private");
+            if line.starts_with(char::is_whitespace) {
+                assignment.qualifier = current_class.clone();
+            }
+        }
+
+        push_jvm_calls(&mut parsed, calls, line, trimmed, line_num);
     }
 
-    #[test]
-    fn test_parse_python() {
-        let source = "from Crypto.Cipher import AES\nAES.new(key, AES.MODE_CBC)";
-        let result = parse_file(source, "python").unwrap();
-        assert!(!result.imports.is_empty());
+    Ok(parsed)
+}
+
+fn parse_scala(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Scala);
+    let mut current_object = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+
+        if let Some(caps) = PACKAGE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+            continue;
+        }
+
+        // `import javax.crypto.{Cipher, KeyGenerator}` imports each selector
+        if let Some(caps) = SCALA_IMPORT_RE.captures(trimmed) {
+            let package = &caps[1];
+            match caps.get(2) {
+                Some(selectors) => {
+                    for selector in selectors.as_str().split(',') {
+                        let name = selector.split("=>").next().unwrap_or_default().trim();
+                        if !name.is_empty() {
+                            push_import(&mut parsed, format!("{}.{}", package, name), line_num);
+                        }
+                    }
+                }
+                None => push_import(&mut parsed, package.to_string(), line_num),
+            }
+            continue;
+        }
+
+        if let Some(caps) = SCALA_CLASS_RE.captures(trimmed) {
+            let name = caps[2].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: name.clone(),
+            });
+            // Only `object` members are reachable statically, as `Object.NAME`
+            current_object = (&caps[1] == "object").then_some(name);
+        }
+
+        if let Some(caps) = SCALA_FUNCTION_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+        }
+
+        if let Some(assignment) = push_assignment(&mut parsed, &SCALA_ASSIGN_RE, trimmed, line_num)
+        {
+            assignment.exported = current_object.is_some()
+                && (trimmed.starts_with("val ") || trimmed.starts_with("final val "));
+            assignment.qualifier = current_object.clone();
+        }
+
+        push_jvm_calls(&mut parsed, calls, line, trimmed, line_num);
+    }
+
+    Ok(parsed)
+}
+
+fn parse_php(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Php);
+    let mut current_class = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(caps) = PHP_USE_RE
+            .captures(trimmed)
+            .or_else(|| PHP_REQUIRE_RE.captures(trimmed))
+        {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        if let Some(caps) = NAMESPACE_RE.captures(trimmed) {
+            push_declaration(
+                &mut parsed,
+                NodeType::ModuleDeclaration,
+                &caps[1],
+                line,
+                line_num,
+            );
+            continue;
+        }
+
+        if let Some(caps) = PHP_CLASS_RE.captures(trimmed) {
+            let class = caps[1].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: class.clone(),
+            });
+            current_class = Some(class);
+        }
+
+        if let Some(caps) = PHP_FUNCTION_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+        }
+
+        // Class constants are referenced as `Class::NAME`, `define()`d ones globally
+        if let Some(assignment) = push_assignment(&mut parsed, &PHP_CONST_RE, trimmed, line_num) {
+            assignment.exported = !trimmed.starts_with("private");
+            assignment.qualifier = current_class.clone();
+        } else if let Some(assignment) =
+            push_assignment(&mut parsed, &PHP_DEFINE_RE, trimmed, line_num)
+        {
+            assignment.exported = true;
+        } else {
+            push_assignment(&mut parsed, &PHP_ASSIGN_RE, trimmed, line_num);
+        }
+
+        for caps in PHP_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            let declared = trimmed[..caps.get(0).map_or(0, |m| m.start())]
+                .trim_end()
+                .ends_with("function");
+            if PHP_KEYWORDS.contains(&fn_name.to_lowercase().as_str())
+                || declared
+                || fn_name == "define"
+            {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_ruby(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Ruby);
+    let mut current_class = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(caps) = RUBY_REQUIRE_RE.captures(trimmed) {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        if let Some(caps) = RUBY_CLASS_RE.captures(trimmed) {
+            let class = caps[2].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: if &caps[1] == "module" {
+                    NodeType::ModuleDeclaration
+                } else {
+                    NodeType::ClassDeclaration
+                },
+                line: line_num,
+                column: 0,
+                content: class.clone(),
+            });
+            current_class = Some(class);
+        }
+
+        if let Some(caps) = RUBY_FUNCTION_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+        }
+
+        // Capitalized names are constants, referenced as `Module::NAME`
+        if let Some(assignment) = push_assignment(&mut parsed, &RUBY_ASSIGN_RE, trimmed, line_num) {
+            assignment.exported = assignment
+                .name
+                .starts_with(|c: char| c.is_ascii_uppercase());
+            if assignment.exported {
+                assignment.qualifier = current_class.clone();
+            }
+        }
+
+        for caps in RUBY_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            let declared = trimmed[..caps.get(0).map_or(0, |m| m.start())]
+                .trim_end()
+                .ends_with("def");
+            if RUBY_KEYWORDS.contains(&fn_name) || declared {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
+
+        // `Digest::MD5.hexdigest "x"` passes its arguments without parentheses
+        for caps in RUBY_BARE_CALL_RE.captures_iter(trimmed) {
+            let callee = caps.get(1).map_or(0..0, |m| m.range());
+            let declared = trimmed[..callee.start].trim_end().ends_with("def");
+            if declared || RUBY_MODIFIER_RE.is_match(&trimmed[callee.end..]) {
+                continue;
+            }
+            calls.push_bare(&mut parsed, line, line_num, &caps);
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_swift(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Swift);
+    let mut current_type = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+
+        if let Some(caps) = SWIFT_IMPORT_RE.captures(trimmed) {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        if let Some(caps) = SWIFT_CLASS_RE.captures(trimmed) {
+            let name = caps[1].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: name.clone(),
+            });
+            current_type = Some(name);
+        }
+
+        let mut declared = None;
+        if let Some(caps) = SWIFT_FUNCTION_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+            declared = Some(caps[1].to_string());
+        }
+
+        // Top-level `let`s are visible module-wide, `static let`s as `Type.name`
+        if let Some(assignment) = push_assignment(&mut parsed, &SWIFT_ASSIGN_RE, trimmed, line_num)
+        {
+            let words: Vec<_> = trimmed.split_whitespace().collect();
+            let is_static = words.contains(&"static");
+            assignment.exported = words.contains(&"let")
+                && !words.contains(&"private")
+                && !words.contains(&"fileprivate")
+                && (is_static || !line.starts_with(char::is_whitespace));
+            if is_static {
+                assignment.qualifier = current_type.clone();
+            }
+        }
+
+        for caps in SWIFT_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            if SWIFT_KEYWORDS.contains(&fn_name) || declared.as_deref() == Some(fn_name) {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_objc(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::ObjectiveC);
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty()
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*')
+        {
+            continue;
+        }
+
+        if let Some(caps) = OBJC_IMPORT_RE.captures(trimmed) {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        // Macros are visible to every file that imports the header
+        if trimmed.starts_with('#') {
+            if let Some(assignment) =
+                push_assignment(&mut parsed, &CPP_DEFINE_RE, trimmed, line_num)
+            {
+                assignment.exported = true;
+            }
+            continue;
+        }
+
+        if let Some(caps) = OBJC_CLASS_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+            continue;
+        }
+
+        let mut declared = None;
+        if let Some(caps) = OBJC_METHOD_RE.captures(trimmed).or_else(|| {
+            CPP_FUNCTION_RE
+                .captures(trimmed)
+                .filter(|_| !trimmed.ends_with(';'))
+        }) {
+            let name = caps.iter().flatten().last().map_or("", |m| m.as_str());
+            let first_word = trimmed.split_whitespace().next().unwrap_or_default();
+            if !CPP_KEYWORDS.contains(&first_word) && !CPP_KEYWORDS.contains(&name) {
+                parsed.ast_nodes.push(AstNode {
+                    node_type: NodeType::FunctionDeclaration,
+                    line: line_num,
+                    column: line.find(name).unwrap_or(0),
+                    content: name.to_string(),
+                });
+                declared = Some(name.to_string());
+            }
+        }
+
+        if let Some(assignment) = push_assignment(&mut parsed, &OBJC_ASSIGN_RE, trimmed, line_num) {
+            assignment.exported =
+                !line.starts_with(char::is_whitespace) && !trimmed.starts_with("static");
+        }
+
+        // C function calls, then `[Receiver selector...]` message sends
+        for caps in CPP_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            if CPP_KEYWORDS.contains(&fn_name) || declared.as_deref() == Some(fn_name) {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
+        let indent = line.len() - line.trim_start().len();
+        for caps in OBJC_MESSAGE_RE.captures_iter(trimmed) {
+            let (Some(receiver), Some(selector)) = (caps.get(1), caps.get(2)) else {
+                continue;
+            };
+            let start = calls.line_start(line_num) + indent + selector.end();
+            parsed.function_calls.push(FunctionCall {
+                name: format!("{}.{}", receiver.as_str(), selector.as_str()),
+                receiver: Some(receiver.as_str().to_string()),
+                line: line_num,
+                column: indent + receiver.start(),
+                args: message_arguments(calls.code.get(start..).unwrap_or_default(), start),
+            });
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parse a shell script, Dockerfile or Makefile. Calls are the command words
+/// of each simple command; `export`ed variables are visible to other files.
+fn parse_shell(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Shell);
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(caps) = SHELL_SOURCE_RE.captures(trimmed) {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        let declared = SHELL_FUNCTION_RE
+            .captures(trimmed)
+            .or_else(|| MAKE_TARGET_RE.captures(line));
+        if let Some(caps) = declared {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps
+                    .iter()
+                    .skip(1)
+                    .flatten()
+                    .next()
+                    .map_or("", |m| m.as_str())
+                    .to_string(),
+            });
+            continue;
+        }
+
+        // Unquoted words are strings in shell: `TYPE=rsa` binds "rsa", not a variable
+        if let Some(caps) = SHELL_ASSIGN_RE.captures(trimmed)
+            && let Some(value) = SHELL_VALUE_RE.captures(caps[2].trim())
+        {
+            let value = if let Some(digits) = value.get(1) {
+                digits
+                    .as_str()
+                    .parse()
+                    .ok()
+                    .map(|n| AssignedValue::Literal(ConstantValue::Integer(n)))
+            } else if let Some(text) = [2, 3, 7].iter().find_map(|&i| value.get(i)) {
+                Some(AssignedValue::Literal(ConstantValue::String(
+                    text.as_str().to_string(),
+                )))
+            } else {
+                (4..=6)
+                    .find_map(|i| value.get(i))
+                    .map(|name| AssignedValue::Identifier(name.as_str().to_string()))
+            };
+            if let Some(value) = value {
+                let name = caps[1].to_string();
+                parsed.ast_nodes.push(AstNode {
+                    node_type: NodeType::VariableDeclaration,
+                    line: line_num,
+                    column: 0,
+                    content: name.clone(),
+                });
+                parsed.assignments.push(Assignment {
+                    name,
+                    value,
+                    line: line_num,
+                    exported: trimmed.starts_with("export"),
+                    qualifier: None,
+                });
+                continue;
+            }
+        }
+
+        let line_start = calls.line_start(line_num);
+        for command in commands::command_lines(line) {
+            parsed.function_calls.push(FunctionCall {
+                name: command.name,
+                receiver: None,
+                line: line_num,
+                column: command.column,
+                args: command
+                    .args
+                    .into_iter()
+                    .map(|(text, written, start)| shell_argument(text, written, line_start + start))
+                    .collect(),
+            });
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_solidity(source: &str, calls: &CallText) -> Result<ParsedSource, ParseError> {
+    let mut parsed = ParsedSource::new(Language::Solidity);
+    let mut current_contract = None;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty()
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*')
+            || trimmed.starts_with("pragma")
+        {
+            continue;
+        }
+
+        if let Some(caps) = SOLIDITY_IMPORT_RE.captures(trimmed) {
+            push_import(&mut parsed, caps[1].to_string(), line_num);
+            continue;
+        }
+
+        if let Some(caps) = SOLIDITY_CONTRACT_RE.captures(trimmed) {
+            let name = caps[1].to_string();
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::ClassDeclaration,
+                line: line_num,
+                column: 0,
+                content: name.clone(),
+            });
+            current_contract = Some(name);
+            continue;
+        }
+
+        let mut declared = None;
+        if let Some(caps) = SOLIDITY_FUNCTION_RE.captures(trimmed) {
+            parsed.ast_nodes.push(AstNode {
+                node_type: NodeType::FunctionDeclaration,
+                line: line_num,
+                column: 0,
+                content: caps[1].to_string(),
+            });
+            declared = Some(caps[1].to_string());
+        }
+
+        // File-level constants are importable as is, contract and library
+        // constants as `Contract.NAME`
+        if declared.is_none()
+            && let Some(assignment) =
+                push_assignment(&mut parsed, &SOLIDITY_ASSIGN_RE, trimmed, line_num)
+        {
+            let words: Vec<_> = trimmed.split_whitespace().collect();
+            assignment.exported = words.contains(&"constant") && !words.contains(&"private");
+            if assignment.exported && line.starts_with(char::is_whitespace) {
+                assignment.qualifier = current_contract.clone();
+            }
+        }
+
+        for caps in SOLIDITY_FN_CALL_RE.captures_iter(trimmed) {
+            let fn_name = &caps[1];
+            if SOLIDITY_KEYWORDS.contains(&fn_name) || declared.as_deref() == Some(fn_name) {
+                continue;
+            }
+            calls.push(&mut parsed, line, line_num, &caps);
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn push_declaration(
+    parsed: &mut ParsedSource,
+    node_type: NodeType,
+    name: &str,
+    line: &str,
+    line_num: usize,
+) {
+    parsed.ast_nodes.push(AstNode {
+        node_type,
+        line: line_num,
+        column: line.find(name).unwrap_or(0),
+        content: name.to_string(),
+    });
+}
+
+/// Name declared by a Java or C# method or constructor signature; a bare
+/// `name(` needs a modifier or return type so calls are not mistaken for it
+fn method_declaration(trimmed: &str) -> Option<&str> {
+    let caps = METHOD_DECL_RE.captures(trimmed)?;
+    let name = caps.get(3)?.as_str();
+    let return_type = caps.get(2).map(|m| m.as_str());
+    let has_modifiers = caps.get(1).is_some_and(|m| !m.as_str().is_empty());
+    let keyword = |word: &str| STATEMENT_KEYWORDS.contains(&word);
+    if keyword(name)
+        || return_type.is_some_and(keyword)
+        || !(has_modifiers || return_type.is_some())
+    {
+        return None;
+    }
+    Some(name)
+}
+
+fn push_import(parsed: &mut ParsedSource, import: String, line_num: usize) {
+    parsed.imports.push(import.clone());
+    parsed.ast_nodes.push(AstNode {
+        node_type: NodeType::Import,
+        line: line_num,
+        column: 0,
+        content: import,
+    });
+}
+
+/// Record the calls on a Kotlin or Scala line, skipping keywords and declarations
+fn push_jvm_calls(
+    parsed: &mut ParsedSource,
+    calls: &CallText,
+    line: &str,
+    trimmed: &str,
+    line_num: usize,
+) {
+    for caps in JVM_FN_CALL_RE.captures_iter(trimmed) {
+        let whole = caps.get(0).map_or(0, |m| m.start());
+        let fn_name = &caps[1];
+        let declared = trimmed[..whole].trim_end().ends_with("fun")
+            || trimmed[..whole].trim_end().ends_with("def");
+        if JVM_KEYWORDS.contains(&fn_name) || declared {
+            continue;
+        }
+        calls.push(parsed, line, line_num, &caps);
+    }
+}
+
+/// Type a shell word: digits are integers, a lone `$VAR` is a reference and
+/// anything else without expansions is a string, quoted or not
+fn shell_argument(text: String, written: String, offset: usize) -> CallArgument {
+    let value = if let Some(var) = SHELL_VALUE_RE
+        .captures(&written)
+        .and_then(|caps| (4..=6).find_map(|i| caps.get(i)))
+    {
+        ArgumentValue::Identifier(var.as_str().to_string())
+    } else if written.contains('$') && !written.starts_with('\'') {
+        ArgumentValue::Expression
+    } else if let Some(n) = text
+        .parse()
+        .ok()
+        .filter(|_| text.bytes().all(|b| b.is_ascii_digit()))
+    {
+        ArgumentValue::Literal(ConstantValue::Integer(n))
+    } else {
+        ArgumentValue::Literal(ConstantValue::String(text.clone()))
+    };
+    CallArgument {
+        name: None,
+        value,
+        text: written,
+        offset,
+    }
+}
+
+/// Comment-blanked source that call argument lists are read from, so a list
+/// may span several lines
+struct CallText<'a> {
+    code: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> CallText<'a> {
+    fn new(code: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(code.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        CallText { code, line_starts }
+    }
+
+    /// Byte offset at which line `line_num` (1-based) starts
+    fn line_start(&self, line_num: usize) -> usize {
+        self.line_starts
+            .get(line_num.wrapping_sub(1))
+            .copied()
+            .unwrap_or(self.code.len())
+    }
+
+    /// Record the call a `*_FN_CALL_RE` match on `line`'s trimmed text found:
+    /// the callee in group 1, with its argument list opening at the match's end
+    fn push(&self, parsed: &mut ParsedSource, line: &str, line_num: usize, caps: &Captures) {
+        let (Some(whole), Some(callee)) = (caps.get(0), caps.get(1)) else {
+            return;
+        };
+        let indent = line.len() - line.trim_start().len();
+        let open = self.line_start(line_num) + indent + whole.end() - 1;
+        let args = call_arguments(self.code.get(open..).unwrap_or_default(), open, false);
+        self.record(parsed, line_num, indent, callee, args);
+    }
+
+    /// Record a call without parentheses matched on `line`'s trimmed text:
+    /// the callee in group 1, with its first argument at group 2
+    fn push_bare(&self, parsed: &mut ParsedSource, line: &str, line_num: usize, caps: &Captures) {
+        let (Some(callee), Some(first)) = (caps.get(1), caps.get(2)) else {
+            return;
+        };
+        let indent = line.len() - line.trim_start().len();
+        let start = self.line_start(line_num) + indent + first.start();
+        let args = call_arguments(self.code.get(start..).unwrap_or_default(), start, true);
+        self.record(parsed, line_num, indent, callee, args);
+    }
+
+    fn record(
+        &self,
+        parsed: &mut ParsedSource,
+        line_num: usize,
+        indent: usize,
+        callee: Match,
+        args: Vec<CallArgument>,
+    ) {
+        parsed.function_calls.push(FunctionCall {
+            name: callee.as_str().to_string(),
+            receiver: receiver_of(callee.as_str()),
+            line: line_num,
+            column: indent + callee.start(),
+            args,
+        });
+    }
+}
+
+/// The part of a qualified callee before its last `.`, `::` or `->`
+fn receiver_of(name: &str) -> Option<String> {
+    let split = [".", "::", "->"]
+        .iter()
+        .filter_map(|sep| name.rfind(sep))
+        .max()?;
+    Some(name[..split].to_string()).filter(|receiver| !receiver.is_empty())
+}
+
+/// Split an argument list at top-level commas. `text` starts at the list's
+/// opening `(`, or at the first argument of a call without parentheses
+/// (`bare`), and `offset` is where it starts in the source. A parenthesized
+/// list that is not closed yields nothing; a bare one ends with its line.
+fn call_arguments(text: &str, offset: usize, bare: bool) -> Vec<CallArgument> {
+    let mut args = Vec::new();
+    let mut depth = usize::from(bare);
+    let mut quote = None;
+    let mut piece_start = usize::from(!bare);
+    let mut chars = text.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        if i > MAX_ARGUMENT_LIST_LEN {
+            return if bare { args } else { Vec::new() };
+        }
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    push_argument(&mut args, text, piece_start..i, offset);
+                    return args;
+                }
+            }
+            (None, ',') if depth == 1 => {
+                push_argument(&mut args, text, piece_start..i, offset);
+                piece_start = i + 1;
+            }
+            (None, '\n' | ';') if bare && depth == 1 => {
+                push_argument(&mut args, text, piece_start..i, offset);
+                return args;
+            }
+            (None, ' ' | '\t') if bare && depth == 1 && RUBY_MODIFIER_RE.is_match(&text[i..]) => {
+                push_argument(&mut args, text, piece_start..i, offset);
+                return args;
+            }
+            _ => {}
+        }
+    }
+    if bare {
+        push_argument(&mut args, text, piece_start..text.len(), offset);
+        return args;
+    }
+    Vec::new()
+}
+
+/// Arguments of an Objective-C message send, one per selector part. `text`
+/// starts after the first selector keyword (`dataWithBytes` in
+/// `[NSData dataWithBytes:b length:16]`) and `offset` is where it starts in
+/// the source; arguments after the first are named by their keyword.
+fn message_arguments(text: &str, offset: usize) -> Vec<CallArgument> {
+    let Some(rest) = text.trim_start().strip_prefix(':') else {
+        return Vec::new();
+    };
+    let mut args = Vec::new();
+    let mut keyword = None;
+    let mut piece_start = text.len() - rest.len();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut chars = text.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        if i > MAX_ARGUMENT_LIST_LEN {
+            return Vec::new();
+        }
+        if i < piece_start {
+            continue;
+        }
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ']') if depth == 0 => {
+                push_message_argument(&mut args, text, piece_start..i, offset, keyword);
+                return args;
+            }
+            (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+            (None, c) if depth == 0 && c.is_whitespace() => {
+                if let Some(caps) = OBJC_SELECTOR_PART_RE.captures(&text[i..]) {
+                    push_message_argument(&mut args, text, piece_start..i, offset, keyword);
+                    keyword = Some(caps[1].to_string());
+                    piece_start = i + caps[0].len();
+                }
+            }
+            _ => {}
+        }
+    }
+    Vec::new()
+}
+
+fn push_message_argument(
+    args: &mut Vec<CallArgument>,
+    text: &str,
+    piece: Range<usize>,
+    offset: usize,
+    keyword: Option<String>,
+) {
+    let count = args.len();
+    push_argument(args, text, piece, offset);
+    if let Some(arg) = args.get_mut(count) {
+        arg.name = keyword;
+    }
+}
+
+fn push_argument(args: &mut Vec<CallArgument>, text: &str, piece: Range<usize>, offset: usize) {
+    let written = &text[piece.clone()];
+    let leading = written.len() - written.trim_start().len();
+    let written = written.trim();
+    if !written.is_empty() && args.len() < MAX_CALL_ARGUMENTS {
+        args.push(classify_argument(written, offset + piece.start + leading));
+    }
+}
+
+/// Split off a keyword or label (`key_size=`, `keySize:`) and type the value;
+/// `offset` is where `piece` starts in the source
+fn classify_argument(piece: &str, offset: usize) -> CallArgument {
+    let named = ARG_NAME_RE.captures(piece).filter(|caps| {
+        // `==`, `=>` and `::` continue an expression rather than name an argument
+        let rest = &piece[caps[0].len()..];
+        if caps[0].ends_with('=') {
+            !rest.starts_with(['=', '>'])
+        } else {
+            !rest.starts_with(':')
+        }
+    });
+    let (name, value_start) = match named {
+        Some(caps) => {
+            let rest = &piece[caps[0].len()..];
+            (
+                Some(caps[1].to_string()),
+                piece.len() - rest.trim_start().len(),
+            )
+        }
+        None => (None, 0),
+    };
+    let text = &piece[value_start..];
+
+    let value = if let Some(s) = string_literal(text) {
+        ArgumentValue::Literal(ConstantValue::String(s.to_string()))
+    } else if let Some(caps) = ARG_INTEGER_RE.captures(text) {
+        let n = match (caps.get(1), caps.get(2)) {
+            (Some(hex), _) => u64::from_str_radix(&hex.as_str().replace('_', ""), 16).ok(),
+            (_, Some(dec)) => dec.as_str().replace('_', "").parse().ok(),
+            _ => None,
+        };
+        n.map_or(ArgumentValue::Expression, |n| {
+            ArgumentValue::Literal(ConstantValue::Integer(n))
+        })
+    } else if let Some(caps) = ARG_NUMBER_RE.captures(text) {
+        let n = caps[2].replace('_', "").parse::<f64>().ok();
+        let sign = if caps.get(1).is_some() { -1.0 } else { 1.0 };
+        n.map_or(ArgumentValue::Expression, |n| {
+            ArgumentValue::Literal(ConstantValue::Number(sign * n))
+        })
+    } else if let Some(literal) = keyword_literal(text) {
+        ArgumentValue::Literal(literal)
+    } else if ARG_IDENTIFIER_RE.is_match(text) {
+        ArgumentValue::Identifier(text.to_string())
+    } else {
+        ArgumentValue::Expression
+    };
+
+    CallArgument {
+        name,
+        value,
+        text: text.to_string(),
+        offset: offset + value_start,
+    }
+}
+
+/// Boolean and null keywords across the supported languages, including
+/// Python's `True`/`None` and Objective-C's `YES`/`NO`/`nil`
+fn keyword_literal(text: &str) -> Option<ConstantValue> {
+    match text {
+        "true" | "True" | "TRUE" | "YES" => Some(ConstantValue::Boolean(true)),
+        "false" | "False" | "FALSE" | "NO" => Some(ConstantValue::Boolean(false)),
+        "null" | "NULL" | "nil" | "Nil" | "None" | "nullptr" | "undefined" => {
+            Some(ConstantValue::Null)
+        }
+        _ => None,
+    }
+}
+
+/// Contents of a single string literal without interpolation, such as
+/// `"md5"`, `'md5'`, `b"md5"`, `@"md5"` or `"""md5"""`
+fn string_literal(text: &str) -> Option<&str> {
+    let body = text.trim_start_matches(['b', 'B', 'r', 'R', 'u', 'U', '@']);
+    if text.len() - body.len() > 2 {
+        return None;
+    }
+    let quote = body
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    let triple = quote.to_string().repeat(3);
+    let is_triple = body.len() >= 6 && body.starts_with(&triple) && body.ends_with(&triple);
+    let inner = if is_triple {
+        &body[3..body.len() - 3]
+    } else if body.len() >= 2 && body.ends_with(quote) {
+        &body[1..body.len() - 1]
+    } else {
+        return None;
+    };
+
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'(') => return None,
+            '\\' => {
+                chars.next();
+            }
+            // `"a" + "b"` is two literals, not one
+            c if c == quote && !is_triple => return None,
+            '$' | '#' if quote != '\'' && chars.peek() == Some(&'{') => return None,
+            '$' if quote == '"' && chars.peek().is_some_and(|n| n.is_alphabetic() || *n == '_') => {
+                return None;
+            }
+            _ => {}
+        }
+    }
+    Some(inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rust() {
+        let source = "use std::collections::HashMap;\nlet x = HashMap::new();";
+        let result = parse_file(source, "rust").unwrap();
+        assert_eq!(result.imports.len(), 1);
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name.contains("HashMap"))
+        );
+    }
+
+    #[test]
+    fn test_parse_javascript() {
+        let source = "import crypto from 'crypto';\ncrypto.createCipher('aes', key);";
+        let result = parse_file(source, "javascript").unwrap();
+        assert!(!result.imports.is_empty());
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name.contains("crypto"))
+        );
+    }
+
+    #[test]
+    fn test_parse_python() {
+        let source = "from Crypto.Cipher import AES\nAES.new(key, AES.MODE_CBC)";
+        let result = parse_file(source, "python").unwrap();
+        assert!(!result.imports.is_empty());
+    }
+
+    #[test]
+    fn test_call_receivers_and_arguments() {
+        let source = "const hash = crypto.createHash('md5'); // createHash('sha256')\n";
+        let result = parse_file(source, "javascript").unwrap();
+        let call = &result.function_calls[0];
+        assert_eq!(call.name, "crypto.createHash");
+        assert_eq!(call.receiver.as_deref(), Some("crypto"));
+        assert_eq!(call.method(), "createHash");
+        assert_eq!(call.column, 13);
+        assert_eq!(call.args.len(), 1);
+        assert_eq!(call.string_argument(0), Some("md5"));
+
+        let source = r#"key = rsa.generate_private_key(
+    public_exponent=0x10001,  # F4, not (3)
+    key_size=KEY_SIZE, backend=default_backend(),
+)
+digest = hashlib.new("md5", f"{prefix}", b"salt" + pepper)
+"#;
+        let result = parse_file(source, "python").unwrap();
+        let generate = &result.function_calls[0];
+        assert_eq!(generate.receiver.as_deref(), Some("rsa"));
+        assert_eq!(generate.args.len(), 3);
+        assert_eq!(generate.args[0].name.as_deref(), Some("public_exponent"));
+        assert_eq!(
+            generate.args[0].value,
+            ArgumentValue::Literal(ConstantValue::Integer(65537))
+        );
+        assert_eq!(
+            generate.args[1].value,
+            ArgumentValue::Identifier("KEY_SIZE".to_string())
+        );
+        assert_eq!(generate.args[2].text, "default_backend()");
+        assert_eq!(generate.args[2].value, ArgumentValue::Expression);
+
+        let new = result
+            .function_calls
+            .iter()
+            .find(|f| f.name == "hashlib.new")
+            .unwrap();
+        assert_eq!(new.string_argument(0), Some("md5"));
+        assert_eq!(new.args[1].value, ArgumentValue::Expression);
+        assert_eq!(new.args[2].value, ArgumentValue::Expression);
+
+        // Argument offsets point into the source, past any keyword
+        let source = "h = hashlib.new(name='md5')\n";
+        let result = parse_file(source, "python").unwrap();
+        let arg = &result.function_calls[0].args[0];
+        assert_eq!(&source[arg.offset..arg.offset + arg.text.len()], "'md5'");
+
+        let source = "key = OpenSSL::PKey::RSA.new(1024)\n";
+        let result = parse_file(source, "ruby").unwrap();
+        let call = &result.function_calls[0];
+        assert_eq!(call.receiver.as_deref(), Some("OpenSSL::PKey::RSA"));
+        assert_eq!(call.method(), "new");
+        assert_eq!(
+            call.args[0].value,
+            ArgumentValue::Literal(ConstantValue::Integer(1024))
+        );
+
+        // Calls without parentheses end with the line or a statement modifier
+        let source = "h = Digest::MD5.hexdigest \"x\", salt if legacy\nkeys.each do |k|\n";
+        let result = parse_file(source, "ruby").unwrap();
+        assert_eq!(result.function_calls.len(), 1);
+        let call = &result.function_calls[0];
+        assert_eq!(call.receiver.as_deref(), Some("Digest::MD5"));
+        assert_eq!(call.column, 4);
+        assert_eq!(call.string_argument(0), Some("x"));
+        assert_eq!(call.args[1].text, "salt");
+        assert_eq!(call.args.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_cpp() {
+        let source = r#"#include <openssl/rsa.h>
+#include "keys.h"
+#define KEY_BITS 1024
+
+static int make_key(RSA *rsa) {
+    /* legacy size */
+    return RSA_generate_key_ex(rsa, KEY_BITS, e, NULL);
+}
+"#;
+        let result = parse_file(source, "c").unwrap();
+        assert_eq!(result.imports, vec!["openssl/rsa.h", "keys.h"]);
+        assert!(
+            result
+                .ast_nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::FunctionDeclaration && n.content == "make_key")
+        );
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "RSA_generate_key_ex" && f.line == 7)
+        );
+        assert!(!result.function_calls.iter().any(|f| f.name == "return"));
+        assert_eq!(result.assignments[0].name, "KEY_BITS");
+        assert!(result.assignments[0].exported);
+    }
+
+    #[test]
+    fn test_parse_csharp() {
+        let source = r#"using System.Security.Cryptography;
+using static System.Console;
+
+public sealed class KeyFactory
+{
+    public const int KeySize = 1024;
+    private static readonly string HashName = "SHA1";
+
+    public RSA Create() => RSA.Create(KeySize);
+}
+"#;
+        let result = parse_file(source, "csharp").unwrap();
+        assert_eq!(
+            result.imports,
+            vec!["System.Security.Cryptography", "System.Console"]
+        );
+        assert!(
+            result
+                .ast_nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::ClassDeclaration && n.content == "KeyFactory")
+        );
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "RSA.Create" && f.line == 9)
+        );
+
+        let key_size = &result.assignments[0];
+        assert_eq!(key_size.name, "KeySize");
+        assert!(key_size.exported);
+        assert_eq!(key_size.qualifier.as_deref(), Some("KeyFactory"));
+        assert!(!result.assignments[1].exported);
+    }
+
+    #[test]
+    fn test_parse_kotlin() {
+        let source = r#"import javax.crypto.Cipher
+import java.security.*
+
+const val KEY_SIZE = 2048
+
+object CryptoConfig {
+    const val CIPHER = "DES/ECB/PKCS5Padding"
+    private const val SECRET = "x"
+}
+
+fun cipher(): Cipher = Cipher.getInstance(CryptoConfig.CIPHER)
+"#;
+        let result = parse_file(source, "kotlin").unwrap();
+        assert_eq!(
+            result.imports,
+            vec!["javax.crypto.Cipher", "java.security.*"]
+        );
+        assert!(
+            result
+                .ast_nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::ClassDeclaration && n.content == "CryptoConfig")
+        );
+        assert!(
+            result
+                .ast_nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::FunctionDeclaration && n.content == "cipher")
+        );
+        let calls: Vec<_> = result
+            .function_calls
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(calls, vec!["Cipher.getInstance"]);
+
+        let exported: Vec<_> = result
+            .assignments
+            .iter()
+            .map(|a| (a.name.as_str(), a.exported, a.qualifier.as_deref()))
+            .collect();
+        assert_eq!(
+            exported,
+            vec![
+                ("KEY_SIZE", true, None),
+                ("CIPHER", true, Some("CryptoConfig")),
+                ("SECRET", false, Some("CryptoConfig")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_scala() {
+        let source = r#"import java.security.{KeyPairGenerator, Signature => Sig}
+import javax.crypto._
+
+object Settings {
+  val Transformation = "DESede/CBC/PKCS5Padding"
+  private val Hidden = "x"
+}
+
+class Service {
+  def signer(): Sig = Signature.getInstance("SHA1withRSA")
+}
+"#;
+        let result = parse_file(source, "scala").unwrap();
+        assert_eq!(
+            result.imports,
+            vec![
+                "java.security.KeyPairGenerator",
+                "java.security.Signature",
+                "javax.crypto"
+            ]
+        );
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "Signature.getInstance" && f.line == 10)
+        );
+        assert!(!result.function_calls.iter().any(|f| f.name == "signer"));
+
+        let transformation = &result.assignments[0];
+        assert!(transformation.exported);
+        assert_eq!(transformation.qualifier.as_deref(), Some("Settings"));
+        assert!(!result.assignments[1].exported);
+    }
+
+    #[test]
+    fn test_parse_php() {
+        let source = r#"<?php
+use phpseclib3\Crypt\RSA;
+require_once 'vendor/autoload.php';
+
+define('KEY_BITS', 1024);
+
+class Vault {
+    const CIPHER = 'des-ede3-cbc';
+    private const SECRET = 'x';
+
+    public function keys() {
+        $bits = KEY_BITS;
+        return openssl_pkey_new(['private_key_bits' => $bits]);
+    }
+}
+"#;
+        let result = parse_file(source, "php").unwrap();
+        assert_eq!(
+            result.imports,
+            vec!["phpseclib3\\Crypt\\RSA", "vendor/autoload.php"]
+        );
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "openssl_pkey_new" && f.line == 13)
+        );
+        assert!(!result.function_calls.iter().any(|f| f.name == "keys"));
+        assert!(!result.function_calls.iter().any(|f| f.name == "define"));
+
+        let names: Vec<_> = result.assignments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["KEY_BITS", "CIPHER", "SECRET", "$bits"]);
+        assert!(result.assignments[0].exported);
+        assert_eq!(result.assignments[1].qualifier.as_deref(), Some("Vault"));
+        assert!(result.assignments[1].exported);
+        assert!(!result.assignments[2].exported);
+        assert!(!result.assignments[3].exported);
+    }
+
+    #[test]
+    fn test_parse_ruby() {
+        let source = r#"require 'openssl'
+
+module Legacy
+  KEY_BITS = 1024
+
+  def self.key
+    bits = KEY_BITS
+    OpenSSL::PKey::RSA.new(bits)
+  end
+end
+"#;
+        let result = parse_file(source, "ruby").unwrap();
+        assert_eq!(result.imports, vec!["openssl"]);
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "OpenSSL::PKey::RSA.new" && f.line == 8)
+        );
+
+        let key_bits = &result.assignments[0];
+        assert_eq!(key_bits.name, "KEY_BITS");
+        assert!(key_bits.exported);
+        assert_eq!(key_bits.qualifier.as_deref(), Some("Legacy"));
+        assert!(!result.assignments[1].exported);
+    }
+
+    #[test]
+    fn test_parse_shell() {
+        let source = r#"#!/bin/sh
+. ./env.sh
+export KEY_TYPE=dsa
+BITS="$DEFAULT_BITS"
+
+make_keys() {
+    sudo ssh-keygen -t "$KEY_TYPE" && openssl genrsa 2048
+}
+"#;
+        let result = parse_file(source, "shell").unwrap();
+        assert_eq!(result.imports, vec!["./env.sh"]);
+
+        let key_type = &result.assignments[0];
+        assert_eq!(key_type.name, "KEY_TYPE");
+        assert!(key_type.exported);
+        assert!(matches!(
+            &key_type.value,
+            AssignedValue::Literal(ConstantValue::String(s)) if s == "dsa"
+        ));
+        assert!(matches!(
+            &result.assignments[1].value,
+            AssignedValue::Identifier(name) if name == "DEFAULT_BITS"
+        ));
+        assert!(!result.assignments[1].exported);
+
+        let calls: Vec<&str> = result
+            .function_calls
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(calls, vec!["ssh-keygen", "openssl"]);
+        let ssh_keygen = &result.function_calls[0];
+        assert_eq!(ssh_keygen.string_argument(0), Some("-t"));
+        assert_eq!(
+            ssh_keygen.args[1].value,
+            ArgumentValue::Identifier("KEY_TYPE".to_string())
+        );
+        assert_eq!(
+            result.function_calls[1].args[1].value,
+            ArgumentValue::Literal(ConstantValue::Integer(2048))
+        );
+        assert!(
+            result
+                .ast_nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::FunctionDeclaration && n.content == "make_keys")
+        );
+    }
+
+    #[test]
+    fn test_parse_solidity() {
+        let source = r#"pragma solidity ^0.8.24;
+import {ECDSA} from "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+import "./Registry.sol";
+
+uint256 constant CHAIN_ID = 1;
+
+library Keys {
+    bytes32 public constant DOMAIN = "forwarder";
+    uint256 private constant SALT = 7;
+
+    function signer(bytes32 digest, uint8 v, bytes32 r, bytes32 s) internal pure returns (address) {
+        return ecrecover(digest, v, r, s);
+    }
+}
+"#;
+        let result = parse_file(source, "solidity").unwrap();
+        assert_eq!(
+            result.imports,
+            vec![
+                "@openzeppelin/contracts/utils/cryptography/ECDSA.sol",
+                "./Registry.sol"
+            ]
+        );
+
+        let chain_id = &result.assignments[0];
+        assert_eq!(chain_id.name, "CHAIN_ID");
+        assert!(chain_id.exported);
+        assert_eq!(chain_id.qualifier, None);
+
+        let domain = &result.assignments[1];
+        assert_eq!(domain.name, "DOMAIN");
+        assert_eq!(domain.qualifier.as_deref(), Some("Keys"));
+        assert!(!result.assignments[2].exported);
+
+        let calls: Vec<&str> = result
+            .function_calls
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(calls, vec!["ecrecover"]);
+    }
+
+    #[test]
+    fn test_parse_swift() {
+        let source = r#"import CryptoKit
+@testable import App
+
+let defaultBits = 2048
+
+enum KeyConfig {
+    static let rsaBits = 1024
+    private static let hidden = 1
+}
+
+struct Signer {
+    func sign(_ data: Data) throws -> Data {
+        let key = P256.Signing.PrivateKey()
+        return try key.signature(for: data).rawRepresentation
+    }
+}
+"#;
+        let result = parse_file(source, "swift").unwrap();
+        assert_eq!(result.imports, vec!["CryptoKit", "App"]);
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "P256.Signing.PrivateKey" && f.line == 13)
+        );
+        assert!(!result.function_calls.iter().any(|f| f.name == "sign"));
+
+        let names: Vec<_> = result.assignments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["defaultBits", "rsaBits", "hidden"]);
+        assert!(result.assignments[0].exported);
+        assert_eq!(result.assignments[0].qualifier, None);
+        assert!(result.assignments[1].exported);
+        assert_eq!(
+            result.assignments[1].qualifier.as_deref(),
+            Some("KeyConfig")
+        );
+        assert!(!result.assignments[2].exported);
+    }
+
+    #[test]
+    fn test_parse_objc() {
+        let source = r#"#import <CommonCrypto/CommonCrypto.h>
+@import Security;
+#define KEY_BITS 1024
+
+NSString *const kDigestName = @"SHA1";
+static const int kRounds = 3;
+
+@implementation Hasher
+- (NSData *)digest:(NSData *)data {
+    CC_SHA1(data.bytes, (CC_LONG)data.length, out);
+    return [NSData dataWithBytes:out length:20];
+}
+@end
+"#;
+        let result = parse_file(source, "objc").unwrap();
+        assert_eq!(
+            result.imports,
+            vec!["CommonCrypto/CommonCrypto.h", "Security"]
+        );
+        assert!(
+            result
+                .function_calls
+                .iter()
+                .any(|f| f.name == "CC_SHA1" && f.line == 10)
+        );
+        let message = result
+            .function_calls
+            .iter()
+            .find(|f| f.name == "NSData.dataWithBytes")
+            .unwrap();
+        assert_eq!(message.line, 11);
+        assert_eq!(message.args.len(), 2);
+        assert_eq!(message.args[0].name, None);
+        assert_eq!(
+            message.args[0].value,
+            ArgumentValue::Identifier("out".to_string())
+        );
+        assert_eq!(message.args[1].name.as_deref(), Some("length"));
+        assert_eq!(
+            message.args[1].value,
+            ArgumentValue::Literal(ConstantValue::Integer(20))
+        );
+        let arg = &message.args[1];
+        assert_eq!(&source[arg.offset..arg.offset + arg.text.len()], "20");
+        assert!(
+            result
+                .ast_nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::FunctionDeclaration && n.content == "digest")
+        );
+
+        let names: Vec<_> = result.assignments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["KEY_BITS", "kDigestName", "kRounds"]);
+        assert!(result.assignments[0].exported);
+        assert!(matches!(
+            &result.assignments[1].value,
+            AssignedValue::Literal(ConstantValue::String(s)) if s == "SHA1"
+        ));
+        assert!(result.assignments[1].exported);
+        assert!(!result.assignments[2].exported);
+    }
+
+    #[test]
+    fn test_literal_argument_kinds() {
+        use ConstantValue::{Boolean, Null, Number};
+        let cases = [
+            (
+                "rust",
+                "let k = derive(-1, 0.5, true, None);",
+                "derive",
+                Null,
+            ),
+            ("javascript", "derive(-1, 0.5, true, null);", "derive", Null),
+            (
+                "typescript",
+                "derive(-1, 0.5, true, undefined);",
+                "derive",
+                Null,
+            ),
+            ("python", "k = derive(-1, 0.5, True, None)", "derive", Null),
+            ("java", "k = derive(-1, 0.5f, true, null);", "derive", Null),
+            ("go", "k := derive(-1, 0.5, true, nil)", "derive", Null),
+            (
+                "cpp",
+                "auto k = derive(-1, 0.5, true, nullptr);",
+                "derive",
+                Null,
+            ),
+            (
+                "csharp",
+                "var k = Derive(-1, 0.5m, true, null);",
+                "Derive",
+                Null,
+            ),
+            (
+                "kotlin",
+                "val k = derive(-1, 0.5, true, null)",
+                "derive",
+                Null,
+            ),
+            (
+                "scala",
+                "val k = derive(-1, 0.5, true, null)",
+                "derive",
+                Null,
+            ),
+            ("php", "$k = derive(-1, 0.5, TRUE, NULL);", "derive", Null),
+            ("ruby", "k = derive(-1, 0.5, true, nil)", "derive", Null),
+            (
+                "swift",
+                "let k = derive(-1, 0.5, true, nil)",
+                "derive",
+                Null,
+            ),
+            (
+                "objc",
+                "id k = [KDF derive:-1 salt:0.5 strict:YES label:nil];",
+                "KDF.derive",
+                Null,
+            ),
+            (
+                "solidity",
+                "uint k = derive(-1, 5e-1, true, false);",
+                "derive",
+                Boolean(false),
+            ),
+        ];
+        for (language, source, name, last) in cases {
+            let result = parse_file(source, language).unwrap();
+            let call = result
+                .function_calls
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap_or_else(|| panic!("no {} call in {}", name, language));
+            let values: Vec<_> = call.args.iter().map(|a| a.value.clone()).collect();
+            assert_eq!(
+                values,
+                vec![
+                    ArgumentValue::Literal(Number(-1.0)),
+                    ArgumentValue::Literal(Number(0.5)),
+                    ArgumentValue::Literal(Boolean(true)),
+                    ArgumentValue::Literal(last),
+                ],
+                "{}",
+                language
+            );
+        }
+
+        let result = parse_file("const ALG = null;\nlet strict = true;\n", "javascript").unwrap();
+        assert_eq!(
+            result.assignments[0].value,
+            AssignedValue::Literal(ConstantValue::Null)
+        );
+        assert_eq!(
+            result.assignments[1].value,
+            AssignedValue::Literal(ConstantValue::Boolean(true))
+        );
     }
 
     #[test]
@@ -0,0 +1,265 @@
+//! Post-Quantum Inventory
+//!
+//! Finds uses of the NIST post-quantum standards, ML-KEM (FIPS 203), ML-DSA
+//! (FIPS 204) and SLH-DSA (FIPS 205), with their parameter sets, and of the
+//! hybrid key exchanges and composite signatures that pair them with a
+//! classical algorithm (`X25519MLKEM768`, `mlkem768x25519-sha256`,
+//! `MLDSA65-ECDSA-P256`). These are recorded as `QuantumSafeFinding`s next to
+//! the vulnerabilities, so reports can show how far a codebase has migrated.
+
+use crate::lexer;
+use crate::types::*;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    // Every name below contains one of these; most statements contain none
+    static ref ANY_FAMILY: Regex = Regex::new(r"(?i)ml[-_]?(?:kem|dsa)|slh[-_]?dsa")
+        .expect("ANY_FAMILY: Invalid regex - this is a compile-time bug");
+
+    // Hybrid key exchanges: TLS groups `X25519MLKEM768` and `SecP256r1MLKEM768`,
+    // OpenSSH `mlkem768x25519-sha256`
+    static ref HYBRID_KEM: Regex = Regex::new(
+        r"(?i)(x25519|x448|secp256r1|secp384r1|p-?256|p-?384)[-_]?ml[-_]?kem[-_]?(768|1024)|ml[-_]?kem[-_]?(768|1024)[-_]?(x25519|x448|p-?256|p-?384)"
+    ).expect("HYBRID_KEM: Invalid regex - this is a compile-time bug");
+
+    // Composite signatures: `MLDSA65-ECDSA-P256`, `id-MLDSA87-Ed448`
+    static ref COMPOSITE_SIGNATURE: Regex = Regex::new(
+        r"(?i)ml[-_]?dsa[-_]?(44|65|87)[-_](rsa|ecdsa|ed25519|ed448)"
+    ).expect("COMPOSITE_SIGNATURE: Invalid regex - this is a compile-time bug");
+
+    // `ML-KEM-768`, `MLKEM768`, `ml_kem_768`, `MlKem768`, Go `mlkem.GenerateKey768`
+    static ref ML_KEM: Regex = Regex::new(
+        r"(?i)ml[-_]?kem(?:[-_]?(512|768|1024)|\.(?:GenerateKey|NewEncapsulationKey|NewDecapsulationKey)(768|1024))?"
+    ).expect("ML_KEM: Invalid regex - this is a compile-time bug");
+
+    // `ML-DSA-65`, `MLDSA65`, `ml_dsa_65`, `MlDsa65`
+    static ref ML_DSA: Regex = Regex::new(
+        r"(?i)ml[-_]?dsa(?:[-_]?(44|65|87))?"
+    ).expect("ML_DSA: Invalid regex - this is a compile-time bug");
+
+    // `SLH-DSA-SHA2-128s`, `slh_dsa_shake_256f`
+    static ref SLH_DSA: Regex = Regex::new(
+        r"(?i)slh[-_]?dsa(?:[-_]?(sha2|shake)[-_]?(128|192|256)([sf]))?"
+    ).expect("SLH_DSA: Invalid regex - this is a compile-time bug");
+}
+
+/// Post-quantum algorithms used in one line or statement of comment-stripped
+/// source, with spans relative to it
+pub fn detect(statement: &str, line_num: usize, language: Language) -> Vec<QuantumSafeFinding> {
+    if !ANY_FAMILY.is_match(statement) {
+        return Vec::new();
+    }
+    let mut found: Vec<QuantumSafeFinding> = Vec::new();
+    let taken = |found: &[QuantumSafeFinding], start: usize, end: usize| {
+        found
+            .iter()
+            .any(|f| f.span.byte_start < end && start < f.span.byte_end)
+    };
+
+    let hybrids = [
+        (PqcFamily::MlKem, &*HYBRID_KEM),
+        (PqcFamily::MlDsa, &*COMPOSITE_SIGNATURE),
+    ];
+    for (family, regex) in hybrids {
+        for caps in regex.captures_iter(statement) {
+            let Some(whole) = caps.get(0) else { continue };
+            if !lexer::on_segment_boundaries(statement, whole.start(), whole.end(), language) {
+                continue;
+            }
+            // (size, classical half): the classical half comes first in TLS group
+            // names and last in OpenSSH and composite signature names
+            let parts = match family {
+                PqcFamily::MlKem => caps
+                    .get(2)
+                    .zip(caps.get(1))
+                    .or_else(|| caps.get(3).zip(caps.get(4))),
+                _ => caps.get(1).zip(caps.get(2)),
+            };
+            let Some((size, classical)) = parts else {
+                continue;
+            };
+            let mut finding = finding(statement, line_num, whole.start(), whole.end(), family);
+            set_parameters(&mut finding, size.as_str(), None);
+            finding.hybrid_with = Some(classical_name(classical.as_str()));
+            found.push(finding);
+        }
+    }
+
+    let families = [
+        (PqcFamily::MlKem, &*ML_KEM),
+        (PqcFamily::MlDsa, &*ML_DSA),
+        (PqcFamily::SlhDsa, &*SLH_DSA),
+    ];
+    for (family, regex) in families {
+        for caps in regex.captures_iter(statement) {
+            let Some(whole) = caps.get(0) else { continue };
+            if taken(&found, whole.start(), whole.end())
+                || !lexer::on_segment_boundaries(statement, whole.start(), whole.end(), language)
+            {
+                continue;
+            }
+            let mut finding = finding(statement, line_num, whole.start(), whole.end(), family);
+            if let Some(size) = size_group(&caps, family) {
+                let slh_variant = (family == PqcFamily::SlhDsa)
+                    .then(|| caps.get(1).zip(caps.get(3)))
+                    .flatten()
+                    .map(|(hash, variant)| (hash.as_str(), variant.as_str()));
+                set_parameters(&mut finding, size, slh_variant);
+            }
+            found.push(finding);
+        }
+    }
+
+    // A bare family name next to one of its parameter sets adds nothing
+    let named_sets: Vec<PqcFamily> = found
+        .iter()
+        .filter(|f| f.parameter_set.is_some())
+        .map(|f| f.family)
+        .collect();
+    found.retain(|f| f.parameter_set.is_some() || !named_sets.contains(&f.family));
+    found.sort_by_key(|f| f.span.byte_start);
+    found
+}
+
+fn finding(
+    statement: &str,
+    line_num: usize,
+    start: usize,
+    end: usize,
+    family: PqcFamily,
+) -> QuantumSafeFinding {
+    QuantumSafeFinding {
+        family,
+        parameter_set: None,
+        security_category: None,
+        hybrid_with: None,
+        line: line_num,
+        column: start,
+        span: SourceSpan::from_byte_range(statement, start, end),
+        context: statement.trim().to_string(),
+        scope: EnclosingScope::default(),
+    }
+}
+
+/// The capture holding the parameter set's size: `768` in `ML-KEM-768`
+fn size_group<'t>(caps: &Captures<'t>, family: PqcFamily) -> Option<&'t str> {
+    let group = match family {
+        PqcFamily::MlKem => caps.get(1).or_else(|| caps.get(2)),
+        PqcFamily::MlDsa => caps.get(1),
+        PqcFamily::SlhDsa => caps.get(2),
+    };
+    group.map(|m| m.as_str())
+}
+
+/// Fill in the parameter set name and NIST security category from its size
+/// and, for SLH-DSA, its hash and `s`/`f` variant
+fn set_parameters(finding: &mut QuantumSafeFinding, size: &str, slh: Option<(&str, &str)>) {
+    finding.security_category = match (finding.family, size) {
+        (PqcFamily::MlKem, "512") | (PqcFamily::SlhDsa, "128") => Some(1),
+        (PqcFamily::MlDsa, "44") => Some(2),
+        (PqcFamily::MlKem, "768") | (PqcFamily::MlDsa, "65") | (PqcFamily::SlhDsa, "192") => {
+            Some(3)
+        }
+        (PqcFamily::MlKem, "1024") | (PqcFamily::MlDsa, "87") | (PqcFamily::SlhDsa, "256") => {
+            Some(5)
+        }
+        _ => None,
+    };
+    finding.parameter_set = Some(match slh {
+        Some((hash, variant)) => format!(
+            "{}-{}-{}{}",
+            finding.family,
+            hash.to_ascii_uppercase(),
+            size,
+            variant.to_ascii_lowercase()
+        ),
+        None => format!("{}-{}", finding.family, size),
+    });
+}
+
+/// Canonical name of the classical half of a hybrid
+fn classical_name(name: &str) -> String {
+    match name.to_ascii_lowercase().replace('-', "").as_str() {
+        "x25519" => "X25519",
+        "x448" => "X448",
+        "secp256r1" | "p256" => "P-256",
+        "secp384r1" | "p384" => "P-384",
+        "rsa" => "RSA",
+        "ecdsa" => "ECDSA",
+        "ed25519" => "Ed25519",
+        "ed448" => "Ed448",
+        _ => return name.to_string(),
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(statement: &str, language: Language) -> Vec<String> {
+        detect(statement, 1, language)
+            .iter()
+            .map(|f| f.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parameter_sets_and_categories() {
+        let found = detect(
+            "kem = oqs.KeyEncapsulation(\"ML-KEM-768\")",
+            1,
+            Language::Python,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].parameter_set.as_deref(), Some("ML-KEM-768"));
+        assert_eq!(found[0].security_category, Some(3));
+        assert_eq!(found[0].column, 28);
+
+        assert_eq!(
+            names("dk, err := mlkem.GenerateKey1024()", Language::Go),
+            ["ML-KEM-1024"]
+        );
+        assert_eq!(
+            names("let sk = MlDsa65::generate();", Language::Rust),
+            ["ML-DSA-65"]
+        );
+        assert_eq!(
+            names("sig = slh_dsa_sha2_128s.sign(msg)", Language::Python),
+            ["SLH-DSA-SHA2-128s"]
+        );
+    }
+
+    #[test]
+    fn test_hybrids_name_their_classical_half() {
+        let found = detect("groups = X25519MLKEM768:X25519", 1, Language::Shell);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].hybrid_with.as_deref(), Some("X25519"));
+        assert_eq!(found[0].to_string(), "X25519 + ML-KEM-768");
+
+        assert_eq!(
+            names("KexAlgorithms mlkem768x25519-sha256", Language::Shell),
+            ["X25519 + ML-KEM-768"]
+        );
+        assert_eq!(
+            names("alg = \"MLDSA65-ECDSA-P256\"", Language::Python),
+            ["ECDSA + ML-DSA-65"]
+        );
+    }
+
+    #[test]
+    fn test_bare_family_dropped_next_to_parameter_set() {
+        assert_eq!(
+            names("ML_KEM params = ML_KEM_512", Language::Python),
+            ["ML-KEM-512"]
+        );
+        assert_eq!(names("import mldsa", Language::Python), ["ML-DSA"]);
+    }
+
+    #[test]
+    fn test_no_match_inside_identifiers() {
+        assert!(detect("let htmlKem = render();", 1, Language::Rust).is_empty());
+        assert!(detect("xmldsaver.save()", 1, Language::Python).is_empty());
+    }
+}
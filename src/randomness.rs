@@ -0,0 +1,175 @@
+//! Weak Randomness
+//!
+//! A non-cryptographic generator (`Math.random()`, Python's `random`,
+//! `java.util.Random`, Go's `math/rand`, C's `rand()`) or a fixed seed is a
+//! concern on its own, but it undermines every algorithm once its output
+//! becomes a key, IV, nonce, salt or token. `escalate` follows a weak random
+//! value through the rest of its function and raises the finding's severity
+//! when it reaches one of those.
+
+use crate::lexer;
+use crate::types::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Names of values that must be unpredictable
+    static ref KEY_MATERIAL: Regex = Regex::new(
+        r"(?i)key|iv|nonce|token|salt|secret|password|otp|csrf"
+    ).expect("KEY_MATERIAL: Invalid regex - this is a compile-time bug");
+
+    // `x = `, `x := `, `let x: [u8; 16] = `, `byte[] x = `
+    static ref ASSIGNED_NAME: Regex = Regex::new(
+        r"([A-Za-z_$][\w$]*)\s*(?::\s*[^=]+?)?\s*:?=(?:[^=>]|$)"
+    ).expect("ASSIGNED_NAME: Invalid regex - this is a compile-time bug");
+}
+
+/// Raise `vuln`, a weak random finding on `lines[vuln.line - 1]`, one severity
+/// level if its value is key material: named as such in the same statement,
+/// or assigned to a variable that a later statement in `function` (first and
+/// last line, 1-based) uses together with key material. `lines` are the
+/// comment- and string-blanked source lines.
+pub fn escalate(
+    vuln: &mut Vulnerability,
+    lines: &[&str],
+    function: Option<(usize, usize)>,
+    language: Language,
+) {
+    let Some(line) = lines.get(vuln.line - 1) else {
+        return;
+    };
+    let matched = vuln.column..vuln.column + (vuln.span.byte_end - vuln.span.byte_start);
+
+    let mut flow = key_material(line, language)
+        .into_iter()
+        .find(|(range, _)| range.end <= matched.start || matched.end <= range.start)
+        .map(|(_, name)| ResolutionStep {
+            name,
+            line: vuln.line,
+            file: None,
+        });
+
+    if flow.is_none()
+        && let Some((_, last)) = function
+        && let Some(variable) = ASSIGNED_NAME
+            .captures(&line[..matched.start.min(line.len())])
+            .and_then(|caps| caps.get(1))
+    {
+        let uses = Regex::new(&format!(
+            r"(^|[^\w$]){}($|[^\w$])",
+            regex::escape(variable.as_str())
+        ))
+        .expect("variable names are escaped");
+        flow = (vuln.line + 1..=last.min(lines.len())).find_map(|line_num| {
+            let line = lines[line_num - 1];
+            if !uses.is_match(line) {
+                return None;
+            }
+            let (_, name) = key_material(line, language).into_iter().next()?;
+            Some(ResolutionStep {
+                name,
+                line: line_num,
+                file: None,
+            })
+        });
+    }
+
+    let Some(step) = flow else {
+        return;
+    };
+    vuln.severity = match vuln.severity {
+        Severity::Low => Severity::Medium,
+        Severity::Medium => Severity::High,
+        Severity::High | Severity::Critical => Severity::Critical,
+    };
+    vuln.risk_score = (vuln.risk_score + 40).min(100);
+    vuln.message = format!(
+        "{}; the value reaches `{}` on line {}",
+        vuln.message, step.name, step.line
+    );
+    vuln.explanation.flows_into = Some(step);
+}
+
+/// Identifiers in `line` naming key material, with their byte ranges
+fn key_material(line: &str, language: Language) -> Vec<(std::ops::Range<usize>, String)> {
+    let is_identifier_char = |c: char| lexer::is_identifier_char(c, language);
+    KEY_MATERIAL
+        .find_iter(line)
+        .filter(|m| lexer::on_segment_boundaries(line, m.start(), m.end(), language))
+        .map(|m| {
+            let start = line[..m.start()]
+                .char_indices()
+                .rev()
+                .take_while(|&(_, c)| is_identifier_char(c))
+                .last()
+                .map_or(m.start(), |(i, _)| i);
+            let end = line[m.end()..]
+                .char_indices()
+                .find(|&(_, c)| !is_identifier_char(c))
+                .map_or(line.len(), |(i, _)| m.end() + i);
+            (start..end, line[start..end].to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector;
+
+    fn weak_random(
+        lines: &[&str],
+        line_num: usize,
+        function: Option<(usize, usize)>,
+    ) -> Vulnerability {
+        let pattern = detector::builtin_patterns()
+            .iter()
+            .find(|p| p.id == "js-math-random")
+            .unwrap();
+        let line = lines[line_num - 1];
+        let mut vuln = pattern
+            .detect(line, line_num, Language::JavaScript)
+            .remove(0);
+        escalate(&mut vuln, lines, function, Language::JavaScript);
+        vuln
+    }
+
+    #[test]
+    fn test_key_material_in_same_statement() {
+        let lines = ["const sessionToken = Math.random().toString(36);"];
+        let vuln = weak_random(&lines, 1, None);
+        assert_eq!(vuln.severity, Severity::High);
+        let step = vuln.explanation.flows_into.unwrap();
+        assert_eq!((step.name.as_str(), step.line), ("sessionToken", 1));
+    }
+
+    #[test]
+    fn test_flow_through_variable_within_function() {
+        let lines = [
+            "function makeIv() {",
+            "  const r = Math.random();",
+            "  const label = r * 10;",
+            "  const ivBytes = encode(r);",
+            "}",
+            "const other = ivFor(r);",
+        ];
+        let vuln = weak_random(&lines, 2, Some((1, 5)));
+        assert_eq!(vuln.severity, Severity::High);
+        let step = vuln.explanation.flows_into.unwrap();
+        assert_eq!((step.name.as_str(), step.line), ("ivBytes", 4));
+        assert!(vuln.message.ends_with("reaches `ivBytes` on line 4"));
+    }
+
+    #[test]
+    fn test_no_escalation_without_key_material() {
+        let lines = [
+            "function jitter() {",
+            "  const delay = Math.random() * 100;",
+            "  const keyboard = delay;",
+            "}",
+        ];
+        let vuln = weak_random(&lines, 2, Some((1, 4)));
+        assert_eq!(vuln.severity, Severity::Medium);
+        assert!(vuln.explanation.flows_into.is_none());
+    }
+}
@@ -2,7 +2,33 @@
 // Provides template-based code fixes for quantum-vulnerable algorithms
 
 use crate::types::{AuditResult, CryptoType, Vulnerability};
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+lazy_static! {
+    // Legacy .NET hash and cipher constructors/factories and their modern replacements
+    static ref DOTNET_REPLACEMENTS: Vec<(Regex, &'static str)> = [
+        (r"new\s+(?:MD5CryptoServiceProvider|MD5Cng|SHA1Managed|SHA1CryptoServiceProvider|SHA1Cng)\s*\(\s*\)", "SHA256.Create()"),
+        (r"\b(?:MD5|SHA1)\.Create\s*\(\s*\)", "SHA256.Create()"),
+        (r"\b(?:MD5|SHA1)\.HashData\b", "SHA256.HashData"),
+        (r"\bHMAC(?:MD5|SHA1)\b", "HMACSHA256"),
+        (r"\b(?:MD5|SHA1)\b", "SHA256"),
+        (r"new\s+(?:TripleDESCryptoServiceProvider|TripleDESCng|DESCryptoServiceProvider)\s*\(\s*\)", "Aes.Create()"),
+        (r"\b(?:TripleDES|DES)\.Create\s*\(\s*\)", "Aes.Create()"),
+        (r"\b(?:TripleDES|DES)\b", "Aes"),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| {
+        (
+            Regex::new(pattern)
+                .expect("DOTNET_REPLACEMENTS: Invalid regex - this is a compile-time bug"),
+            replacement,
+        )
+    })
+    .collect();
+}
 
 /// Validate file path for security
 fn validate_file_path(path: &str) -> Result<(), String> {
@@ -111,7 +137,15 @@ pub fn generate_remediations(audit_result: &AuditResult, file_path: &str) -> Rem
         };
     }
 
+    // Fixes rewrite the whole line, so repeated occurrences of the same
+    // algorithm on one line share a single fix
+    let mut seen = HashSet::new();
+
     for vuln in &audit_result.vulnerabilities {
+        if !seen.insert((vuln.line, &vuln.crypto_type)) {
+            continue;
+        }
+
         match vuln.crypto_type {
             CryptoType::Md5 => {
                 if let Some(fix) = remediate_md5(vuln, file_path) {
@@ -169,7 +203,9 @@ fn remediate_md5(vuln: &Vulnerability, file_path: &str) -> Option<CodeFix> {
     let old_code = vuln.context.trim().to_string();
 
     // Pattern matching for common MD5 usage patterns
-    let new_code = if old_code.contains("md5") && old_code.contains("hashlib") {
+    let new_code = if is_dotnet(vuln) {
+        dotnet_replacement(&old_code)
+    } else if old_code.contains("md5") && old_code.contains("hashlib") {
         // Python hashlib
         old_code.replace("md5", "sha256")
     } else if old_code.contains("MD5") && old_code.contains("crypto") {
@@ -200,7 +236,9 @@ fn remediate_md5(vuln: &Vulnerability, file_path: &str) -> Option<CodeFix> {
 fn remediate_sha1(vuln: &Vulnerability, file_path: &str) -> Option<CodeFix> {
     let old_code = vuln.context.trim().to_string();
 
-    let new_code = if old_code.contains("sha1") {
+    let new_code = if is_dotnet(vuln) {
+        dotnet_replacement(&old_code)
+    } else if old_code.contains("sha1") {
         old_code.replace("sha1", "sha256")
     } else if old_code.contains("SHA1") {
         old_code.replace("SHA1", "SHA256")
@@ -279,7 +317,9 @@ fn remediate_des_3des(vuln: &Vulnerability, file_path: &str) -> Option<CodeFix>
     let is_3des = matches!(vuln.crypto_type, CryptoType::TripleDes);
 
     // Pattern matching for common DES/3DES usage
-    let new_code = if old_code.contains("DES") || old_code.contains("des") {
+    let new_code = if is_dotnet(vuln) {
+        dotnet_replacement(&old_code)
+    } else if old_code.contains("DES") || old_code.contains("des") {
         // Replace with AES-256
         old_code
             .replace("TripleDES", "AES")
@@ -308,10 +348,25 @@ fn remediate_des_3des(vuln: &Vulnerability, file_path: &str) -> Option<CodeFix>
     })
 }
 
+/// Whether the finding came from one of the .NET API rules
+fn is_dotnet(vuln: &Vulnerability) -> bool {
+    vuln.rule_id.starts_with("dotnet-")
+}
+
+/// Rewrite legacy .NET hash and cipher types to their modern factories, e.g.
+/// `new SHA1Managed()` to `SHA256.Create()` and `TripleDES.Create()` to `Aes.Create()`
+fn dotnet_replacement(code: &str) -> String {
+    DOTNET_REPLACEMENTS
+        .iter()
+        .fold(code.to_string(), |code, (re, replacement)| {
+            re.replace_all(&code, *replacement).into_owned()
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Language, Severity};
+    use crate::types::{EnclosingScope, Explanation, Language, Severity, SourceSpan, SpanKind};
 
     fn create_test_vulnerability(
         crypto_type: CryptoType,
@@ -319,15 +374,25 @@ mod tests {
         key_size: Option<u32>,
     ) -> Vulnerability {
         Vulnerability {
+            category: crypto_type.category(),
             crypto_type,
             severity: Severity::High,
             risk_score: 80,
             line: 42,
             column: 10,
+            span: SourceSpan::default(),
             context: context.to_string(),
+            scope: EnclosingScope::default(),
             message: "Test vulnerability".to_string(),
             recommendation: "Test recommendation".to_string(),
             key_size,
+            security_level: None,
+            span_kind: SpanKind::Code,
+            rule_id: String::new(),
+            cwe: None,
+            confidence: 1.0,
+            explanation: Explanation::default(),
+            suppressed: Vec::new(),
         }
     }
 
@@ -399,6 +464,26 @@ mod tests {
         assert!(fix.confidence < 0.7);
     }
 
+    #[test]
+    fn test_remediate_dotnet() {
+        let mut vuln =
+            create_test_vulnerability(CryptoType::Sha1, "using var sha = new SHA1Managed();", None);
+        vuln.rule_id = "dotnet-sha1".to_string();
+        let fix = remediate_sha1(&vuln, "Hasher.cs").unwrap();
+        assert_eq!(fix.new_code, "using var sha = SHA256.Create();");
+
+        vuln.context = "MD5 md5 = MD5.Create();".to_string();
+        vuln.rule_id = "dotnet-md5".to_string();
+        let fix = remediate_md5(&vuln, "Hasher.cs").unwrap();
+        assert_eq!(fix.new_code, "SHA256 md5 = SHA256.Create();");
+
+        vuln.context = "var tdes = new TripleDESCryptoServiceProvider();".to_string();
+        vuln.rule_id = "dotnet-3des".to_string();
+        vuln.crypto_type = CryptoType::TripleDes;
+        let fix = remediate_des_3des(&vuln, "Cipher.cs").unwrap();
+        assert_eq!(fix.new_code, "var tdes = Aes.Create();");
+    }
+
     #[test]
     fn test_remediate_des() {
         let vuln =
@@ -459,6 +544,24 @@ mod tests {
         assert!(remediation.summary.average_confidence > 0.0);
     }
 
+    #[test]
+    fn test_one_fix_per_line_and_algorithm() {
+        let mut audit_result = AuditResult::new(Language::Python, 1);
+        for column in [0, 20] {
+            let mut vuln = create_test_vulnerability(
+                CryptoType::Md5,
+                "a = hashlib.md5(x); b = hashlib.md5(y)",
+                None,
+            );
+            vuln.column = column;
+            audit_result.add_vulnerability(vuln);
+        }
+
+        let result = generate_remediations(&audit_result, "test.py");
+        assert_eq!(result.fixes.len(), 1);
+        assert_eq!(result.summary.total_vulnerabilities, 2);
+    }
+
     #[test]
     fn test_generate_remediations_unsupported() {
         let mut audit_result = AuditResult::new(Language::Python, 100);
@@ -0,0 +1,449 @@
+//! Declarative Rule Packs
+//!
+//! Detection rules are described in JSON rule packs and compiled into
+//! `CryptoPattern`s for the detector. The core pack (`data/rules/core.json`) is
+//! embedded at compile time and checked by `build.rs`; additional packs are
+//! loaded from user files through `ScannerConfig` and validated here.
+
+use crate::detector::{CryptoPattern, KeySizeTier};
+use crate::types::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Base confidence for rules that do not set one
+const DEFAULT_CONFIDENCE: f32 = 0.5;
+
+#[derive(Error, Debug)]
+pub enum RulePackError {
+    #[error("Failed to read rule pack {0}: {1}")]
+    Io(String, String),
+
+    #[error("Invalid rule pack JSON: {0}")]
+    InvalidJson(String),
+
+    #[error("Rule pack '{0}' contains no rules")]
+    EmptyPack(String),
+
+    #[error("Invalid rule '{rule}': {reason}")]
+    InvalidRule { rule: String, reason: String },
+
+    #[error("Duplicate rule id: {0}")]
+    DuplicateRuleId(String),
+
+    #[error("Rule '{rule}' supersedes unknown rule '{superseded}'")]
+    UnknownSupersededRule { rule: String, superseded: String },
+}
+
+/// Rule pack metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulePackMetadata {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Key-size extraction for a rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeySizeDefinition {
+    /// Regex whose first capture group is the key size in bits
+    pub pattern: String,
+    /// Severity overrides, ordered by ascending `below`
+    #[serde(default)]
+    pub tiers: Vec<KeySizeTier>,
+}
+
+/// A single detection rule as written in a rule pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleDefinition {
+    pub id: String,
+    /// Display name; defaults to the rule id
+    pub name: Option<String>,
+    /// Regex pattern (mutually exclusive with `tokens`)
+    pub pattern: Option<String>,
+    /// Literal API names, matched on identifier boundaries (e.g. `corp_crypto::legacy_sign`)
+    #[serde(default)]
+    pub tokens: Vec<String>,
+    /// Allow matches inside identifier segments (e.g. `rsa` in `parsable`)
+    #[serde(default)]
+    pub match_inside_identifiers: bool,
+    /// Also match inside every string literal (e.g. JOSE `"alg": "RS256"`), not
+    /// just algorithm names passed to a crypto API
+    #[serde(default)]
+    pub match_string_literals: bool,
+    /// Languages the rule applies to; empty means all
+    #[serde(default)]
+    pub languages: Vec<Language>,
+    pub crypto_type: CryptoType,
+    /// Overrides the category implied by `crypto_type`
+    pub category: Option<CryptoCategory>,
+    pub severity: Severity,
+    pub risk_score: u32,
+    pub message: String,
+    pub recommendation: String,
+    pub cwe: Option<String>,
+    /// Base confidence before context signals (0.0-1.0, default 0.5)
+    pub confidence: Option<f32>,
+    /// Rule ids (from any pack) this rule wins against when matches overlap
+    #[serde(default)]
+    pub supersedes: Vec<String>,
+    pub key_size: Option<KeySizeDefinition>,
+}
+
+/// A set of detection rules loaded from JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulePack {
+    pub metadata: RulePackMetadata,
+    pub rules: Vec<RuleDefinition>,
+}
+
+static CORE_RULE_PACK: Lazy<RulePack> = Lazy::new(|| {
+    let json_data = include_str!("../data/rules/core.json");
+    serde_json::from_str(json_data).expect("Failed to parse core rule pack")
+});
+
+/// The embedded core rule pack
+pub fn core_rule_pack() -> &'static RulePack {
+    &CORE_RULE_PACK
+}
+
+impl RulePack {
+    /// Parse and validate a rule pack from JSON
+    pub fn from_json(json: &str) -> Result<Self, RulePackError> {
+        let pack: RulePack =
+            serde_json::from_str(json).map_err(|e| RulePackError::InvalidJson(e.to_string()))?;
+        pack.validate()?;
+        Ok(pack)
+    }
+
+    /// Read, parse and validate a rule pack file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RulePackError> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .map_err(|e| RulePackError::Io(path.display().to_string(), e.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    /// Check every rule compiles, rule ids are unique within the pack and
+    /// every `supersedes` id names a rule in this pack or the core pack
+    pub fn validate(&self) -> Result<(), RulePackError> {
+        self.compile().map(|_| ())
+    }
+
+    /// Compile the pack into detector patterns, in rule order
+    pub fn compile(&self) -> Result<Vec<CryptoPattern>, RulePackError> {
+        let core = core_rule_pack().rules.iter().map(|r| r.id.as_str());
+        self.compile_with(&core.collect())
+    }
+
+    /// Compile the pack, allowing `supersedes` to name rules in `known` as
+    /// well as rules in this pack
+    pub(crate) fn compile_with(
+        &self,
+        known: &HashSet<&str>,
+    ) -> Result<Vec<CryptoPattern>, RulePackError> {
+        if self.rules.is_empty() {
+            return Err(RulePackError::EmptyPack(self.metadata.name.clone()));
+        }
+
+        let mut seen = HashSet::new();
+        let mut patterns = Vec::with_capacity(self.rules.len());
+        for rule in &self.rules {
+            if !seen.insert(rule.id.as_str()) {
+                return Err(RulePackError::DuplicateRuleId(rule.id.clone()));
+            }
+            patterns.push(rule.compile()?);
+        }
+
+        for rule in &self.rules {
+            let unknown = rule
+                .supersedes
+                .iter()
+                .find(|id| !seen.contains(id.as_str()) && !known.contains(id.as_str()));
+            if let Some(id) = unknown {
+                return Err(RulePackError::UnknownSupersededRule {
+                    rule: rule.id.clone(),
+                    superseded: id.clone(),
+                });
+            }
+        }
+        Ok(patterns)
+    }
+}
+
+impl RuleDefinition {
+    fn invalid(&self, reason: impl Into<String>) -> RulePackError {
+        RulePackError::InvalidRule {
+            rule: self.id.clone(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Validate this rule and build its detector pattern
+    pub fn compile(&self) -> Result<CryptoPattern, RulePackError> {
+        if self.id.trim().is_empty() || self.id.contains(char::is_whitespace) {
+            return Err(self.invalid("id must be non-empty and contain no whitespace"));
+        }
+
+        let source = match (&self.pattern, self.tokens.is_empty()) {
+            (Some(pattern), true) => pattern.clone(),
+            (None, false) => {
+                if self.tokens.iter().any(|t| t.trim().is_empty()) {
+                    return Err(self.invalid("tokens must not be empty strings"));
+                }
+                token_pattern(&self.tokens)
+            }
+            (Some(_), false) => {
+                return Err(self.invalid("specify either pattern or tokens, not both"));
+            }
+            (None, true) => return Err(self.invalid("missing pattern or tokens")),
+        };
+        let regex =
+            Regex::new(&source).map_err(|e| self.invalid(format!("invalid pattern: {}", e)))?;
+
+        check_risk_score(self.risk_score).map_err(|reason| self.invalid(reason))?;
+        if self.message.trim().is_empty() {
+            return Err(self.invalid("message must not be empty"));
+        }
+        if self.recommendation.trim().is_empty() {
+            return Err(self.invalid("recommendation must not be empty"));
+        }
+        if let Some(cwe) = &self.cwe {
+            let digits = cwe.strip_prefix("CWE-").unwrap_or_default();
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(self.invalid(format!("cwe must look like CWE-327, got '{}'", cwe)));
+            }
+        }
+
+        let confidence = self.confidence.unwrap_or(DEFAULT_CONFIDENCE);
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err(self.invalid(format!("confidence must be 0.0-1.0, got {}", confidence)));
+        }
+        if self.supersedes.contains(&self.id) {
+            return Err(self.invalid("a rule cannot supersede itself"));
+        }
+
+        let (key_size, key_size_tiers) = match &self.key_size {
+            Some(def) => {
+                let key_regex = Regex::new(&def.pattern)
+                    .map_err(|e| self.invalid(format!("invalid key_size pattern: {}", e)))?;
+                if key_regex.captures_len() < 2 {
+                    return Err(self.invalid("key_size pattern needs a capture group"));
+                }
+                self.check_tiers(&def.tiers)?;
+                (Some(key_regex), def.tiers.clone())
+            }
+            None => (None, Vec::new()),
+        };
+
+        Ok(CryptoPattern {
+            id: self.id.clone(),
+            name: self.name.clone().unwrap_or_else(|| self.id.clone()),
+            regex,
+            category: self.category.unwrap_or_else(|| self.crypto_type.category()),
+            crypto_type: self.crypto_type.clone(),
+            languages: self.languages.clone(),
+            severity: self.severity,
+            risk_score: self.risk_score,
+            message: self.message.clone(),
+            recommendation: self.recommendation.clone(),
+            cwe: self.cwe.clone(),
+            match_inside_identifiers: self.match_inside_identifiers,
+            match_string_literals: self.match_string_literals,
+            confidence,
+            supersedes: self.supersedes.clone(),
+            key_size,
+            key_size_tiers,
+        })
+    }
+
+    /// Tier bounds must strictly increase, with an open-ended tier only last
+    fn check_tiers(&self, tiers: &[KeySizeTier]) -> Result<(), RulePackError> {
+        let mut previous: Option<u32> = None;
+        for (idx, tier) in tiers.iter().enumerate() {
+            check_risk_score(tier.risk_score).map_err(|reason| self.invalid(reason))?;
+            match tier.below {
+                Some(below) if previous.is_some_and(|p| below <= p) => {
+                    return Err(self.invalid("key_size tiers must be in ascending order"));
+                }
+                Some(below) => previous = Some(below),
+                None if idx + 1 != tiers.len() => {
+                    return Err(self.invalid("only the last key_size tier may omit 'below'"));
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_risk_score(score: u32) -> Result<(), String> {
+    if score > 100 {
+        return Err(format!("risk_score must be 0-100, got {}", score));
+    }
+    Ok(())
+}
+
+/// Build a regex matching any of `tokens` literally, anchored on identifier boundaries
+fn token_pattern(tokens: &[String]) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    tokens
+        .iter()
+        .map(|token| {
+            let mut alt = String::new();
+            if token.starts_with(is_word) {
+                alt.push_str(r"\b");
+            }
+            alt.push_str(&regex::escape(token));
+            if token.ends_with(is_word) {
+                alt.push_str(r"\b");
+            }
+            alt
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack_json(rules: &str) -> String {
+        format!(
+            r#"{{"metadata": {{"name": "test", "version": "1.0.0"}}, "rules": [{}]}}"#,
+            rules
+        )
+    }
+
+    const LEGACY_SIGN: &str = r#"{
+        "id": "corp-legacy-sign",
+        "tokens": ["corp_crypto::legacy_sign"],
+        "languages": ["rust"],
+        "crypto_type": "RSA",
+        "severity": "high",
+        "risk_score": 85,
+        "message": "In-house legacy_sign wraps RSA-2048",
+        "recommendation": "Use corp_crypto::pq_sign",
+        "cwe": "CWE-327"
+    }"#;
+
+    #[test]
+    fn test_core_rule_pack_compiles() {
+        let patterns = core_rule_pack().compile().unwrap();
+        assert!(!patterns.is_empty());
+        assert!(patterns.iter().all(|p| p.cwe.is_some()));
+    }
+
+    #[test]
+    fn test_token_rule() {
+        let pack = RulePack::from_json(&pack_json(LEGACY_SIGN)).unwrap();
+        let pattern = &pack.compile().unwrap()[0];
+        assert_eq!(pattern.name, "corp-legacy-sign");
+        assert_eq!(pattern.category, CryptoCategory::AsymmetricEncryption);
+        assert!(pattern.applies_to(Language::Rust));
+        assert!(!pattern.applies_to(Language::Python));
+
+        let found = pattern.detect(
+            "let sig = corp_crypto::legacy_sign(&key, msg);",
+            1,
+            Language::Rust,
+        );
+        assert_eq!(found.len(), 1);
+        let vuln = &found[0];
+        assert_eq!(vuln.rule_id, "corp-legacy-sign");
+        assert_eq!(vuln.cwe.as_deref(), Some("CWE-327"));
+        assert_eq!(vuln.column, 10);
+        assert_eq!(vuln.span.byte_end, 34);
+        assert!(
+            pattern
+                .detect("corp_crypto::legacy_signer()", 1, Language::Rust)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_rejects_duplicate_ids() {
+        let json = pack_json(&format!("{},{}", LEGACY_SIGN, LEGACY_SIGN));
+        assert!(matches!(
+            RulePack::from_json(&json),
+            Err(RulePackError::DuplicateRuleId(id)) if id == "corp-legacy-sign"
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unknown_superseded_rule() {
+        let supersedes = |ids: &str| {
+            let rule = LEGACY_SIGN.replace(
+                r#""cwe": "CWE-327""#,
+                &format!(r#""cwe": "CWE-327", "supersedes": [{}]"#, ids),
+            );
+            RulePack::from_json(&pack_json(&rule))
+        };
+
+        assert!(matches!(
+            supersedes(r#""rsa-typo""#),
+            Err(RulePackError::UnknownSupersededRule { rule, superseded })
+                if rule == "corp-legacy-sign" && superseded == "rsa-typo"
+        ));
+        // Custom packs may supersede core rules
+        assert!(supersedes(r#""rsa""#).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_rules() {
+        let invalid = |from: &str, to: &str| {
+            let json = pack_json(&LEGACY_SIGN.replace(from, to));
+            RulePack::from_json(&json).unwrap_err()
+        };
+
+        assert!(matches!(
+            invalid(r#""risk_score": 85"#, r#""risk_score": 150"#),
+            RulePackError::InvalidRule { .. }
+        ));
+        assert!(matches!(
+            invalid(r#""CWE-327""#, r#""327""#),
+            RulePackError::InvalidRule { .. }
+        ));
+        assert!(matches!(
+            invalid(
+                r#""tokens": ["corp_crypto::legacy_sign"]"#,
+                r#""pattern": "legacy_sign(""#
+            ),
+            RulePackError::InvalidRule { .. }
+        ));
+        assert!(matches!(
+            invalid(r#""crypto_type": "RSA""#, r#""crypto_type": "ENIGMA""#),
+            RulePackError::InvalidJson(_)
+        ));
+        assert!(matches!(
+            invalid(r#""severity""#, r#""severty""#),
+            RulePackError::InvalidJson(_)
+        ));
+        assert!(matches!(
+            RulePack::from_json(&pack_json("")),
+            Err(RulePackError::EmptyPack(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unordered_key_size_tiers() {
+        let rule = LEGACY_SIGN.replace(
+            r#""cwe": "CWE-327""#,
+            r#""key_size": {"pattern": "(\\d+)", "tiers": [
+                {"below": 4096, "severity": "high", "risk_score": 85, "message": "{bits}"},
+                {"below": 2048, "severity": "critical", "risk_score": 100, "message": "{bits}"}
+            ]}"#,
+        );
+        assert!(matches!(
+            RulePack::from_json(&pack_json(&rule)),
+            Err(RulePackError::InvalidRule { .. })
+        ));
+    }
+}
@@ -0,0 +1,431 @@
+//! Enclosing Scopes
+//!
+//! Attributes a line to the function, class and module it sits in, so that a
+//! finding can be routed by symbol as well as by line number. Declarations
+//! come from `parser::parse_file`; how far each one reaches comes from brace
+//! matching, from indentation in Python and Makefiles, and from the matching
+//! `end` in Ruby and `@end` in Objective-C.
+
+use crate::lexer::LexedSource;
+use crate::types::*;
+
+/// Lines a signature may span before the `{` that opens its body
+const MAX_SIGNATURE_LINES: usize = 12;
+
+/// Words that continue a signature on the next line
+const SIGNATURE_CONTINUATIONS: &[&str] = &["throws", "where", "extends", "implements", "returns"];
+
+#[derive(Debug, Clone)]
+struct Scope {
+    kind: NodeType,
+    name: String,
+    /// First and last line, 1-based and inclusive
+    start: usize,
+    end: usize,
+}
+
+/// The functions, classes and modules declared in one file and the lines
+/// each spans
+#[derive(Debug, Clone, Default)]
+pub struct ScopeIndex {
+    scopes: Vec<Scope>,
+    /// Joins nested module names: `a::b`, `App\Keys`, `com.example`
+    separator: &'static str,
+}
+
+impl ScopeIndex {
+    pub fn new(lexed: &LexedSource, parsed: &ParsedSource) -> Self {
+        // Braces and keywords in comments and strings do not open or close scopes
+        let code = lexed.scan_text(|_| false);
+        let lines: Vec<&str> = code.lines().collect();
+        let language = parsed.language;
+
+        let declarations: Vec<&AstNode> = parsed
+            .ast_nodes
+            .iter()
+            .filter(|node| {
+                matches!(
+                    node.node_type,
+                    NodeType::FunctionDeclaration
+                        | NodeType::ClassDeclaration
+                        | NodeType::ModuleDeclaration
+                ) && (1..=lines.len()).contains(&node.line)
+            })
+            .collect();
+
+        let scopes = declarations
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let start = node.line - 1;
+                let later = |kind: Option<NodeType>| {
+                    declarations[i + 1..]
+                        .iter()
+                        .filter(|n| kind.is_none_or(|kind| n.node_type == kind))
+                        .map(|n| n.line - 1)
+                        .find(|&line| line > start)
+                };
+                let end =
+                    match (language, node.node_type) {
+                        (Language::Python, _) => indented_block_end(&lines, start),
+                        (Language::Ruby, _) => end_keyword_line(&lines, start, "end"),
+                        (Language::ObjectiveC, NodeType::ClassDeclaration) => {
+                            end_keyword_line(&lines, start, "@end")
+                        }
+                        _ => brace_block_end(&lines, start, later(None), language).unwrap_or_else(
+                            || match (language, node.node_type) {
+                                // Make targets own their tab-indented recipe
+                                (Language::Shell, _) => indented_block_end(&lines, start),
+                                // `package x;` and `namespace X;` cover the rest of the file
+                                (_, NodeType::ModuleDeclaration) => {
+                                    later(Some(NodeType::ModuleDeclaration))
+                                        .map_or(lines.len() - 1, |next| next - 1)
+                                }
+                                _ => start,
+                            },
+                        ),
+                    };
+                Scope {
+                    kind: node.node_type,
+                    name: node.content.clone(),
+                    start: node.line,
+                    end: end + 1,
+                }
+            })
+            .collect();
+
+        Self {
+            scopes,
+            separator: match language {
+                Language::Rust | Language::Cpp | Language::Ruby => "::",
+                Language::Php => "\\",
+                _ => ".",
+            },
+        }
+    }
+
+    /// Scopes for `source`, empty if the language has no parser
+    pub fn for_source(source: &str, language: Language) -> Self {
+        crate::parser::parse_file(source, &language.to_string())
+            .map(|parsed| Self::new(&LexedSource::new(source, language), &parsed))
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    /// First and last line of the innermost function around `line`
+    pub fn function_lines(&self, line: usize) -> Option<(usize, usize)> {
+        self.scopes
+            .iter()
+            .filter(|scope| {
+                scope.kind == NodeType::FunctionDeclaration
+                    && scope.start <= line
+                    && line <= scope.end
+            })
+            .max_by_key(|scope| scope.start)
+            .map(|scope| (scope.start, scope.end))
+    }
+
+    /// Innermost function and class around `line`, and the modules it is nested in
+    pub fn enclosing(&self, line: usize) -> EnclosingScope {
+        let containing: Vec<&Scope> = self
+            .scopes
+            .iter()
+            .filter(|scope| scope.start <= line && line <= scope.end)
+            .collect();
+        let innermost = |kind: NodeType| {
+            containing
+                .iter()
+                .filter(|scope| scope.kind == kind)
+                .max_by_key(|scope| scope.start)
+                .map(|scope| scope.name.as_str())
+        };
+
+        // `func (k *KeyStore) Rotate` and `void Signer::sign` name their type
+        let (owner, function) = match innermost(NodeType::FunctionDeclaration) {
+            Some(name) => match ["::", "."].iter().find_map(|sep| name.rsplit_once(sep)) {
+                Some((owner, method)) => (Some(owner), Some(method)),
+                None => (None, Some(name)),
+            },
+            None => (None, None),
+        };
+
+        let modules: Vec<&str> = containing
+            .iter()
+            .filter(|scope| scope.kind == NodeType::ModuleDeclaration)
+            .map(|scope| scope.name.as_str())
+            .collect();
+
+        EnclosingScope {
+            module: (!modules.is_empty()).then(|| modules.join(self.separator)),
+            class: innermost(NodeType::ClassDeclaration)
+                .or(owner)
+                .map(str::to_string),
+            function: function.map(str::to_string),
+        }
+    }
+}
+
+/// Last line of the `{ ... }` body opened by the declaration on line `start`
+/// (0-based), or `None` if the declaration has no braced body: `fn f();`,
+/// `package x`, `data class Key(val bits: Int)` or `fun f() = expr`
+fn brace_block_end(
+    lines: &[&str],
+    start: usize,
+    next_declaration: Option<usize>,
+    language: Language,
+) -> Option<usize> {
+    let expression_bodies = matches!(language, Language::Kotlin | Language::Scala);
+    let mut depth = 0i32;
+    let mut open = None;
+
+    'signature: for (idx, line) in lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(MAX_SIGNATURE_LINES)
+    {
+        if idx > start && next_declaration.is_some_and(|next| idx >= next) {
+            return None;
+        }
+        for (col, c) in line.char_indices() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                '{' if depth <= 0 => {
+                    open = Some((idx, col));
+                    break 'signature;
+                }
+                ';' | '}' if depth <= 0 => return None,
+                '=' if expression_bodies && depth <= 0 => {
+                    let rest = line[col + 1..].trim_start();
+                    if !(rest.is_empty() || rest.starts_with('{')) {
+                        return None;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if depth <= 0 && !signature_continues(line, lines.get(idx + 1).copied()) {
+            return None;
+        }
+    }
+
+    let (first, col) = open?;
+    let mut depth = 0usize;
+    for (idx, line) in lines.iter().enumerate().skip(first) {
+        let text = if idx == first { &line[col..] } else { line };
+        for c in text.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Some(idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Some(lines.len() - 1)
+}
+
+/// Whether a signature ending `line` goes on to `next`, as in a `{` on its own
+/// line or a `throws` clause
+fn signature_continues(line: &str, next: Option<&str>) -> bool {
+    let line = line.trim_end();
+    let next = next.unwrap_or_default().trim_start();
+    line.ends_with([',', '(', ':', '=', '>', '|', '&'])
+        || next.starts_with(['{', ':', ',', ')', '.'])
+        || SIGNATURE_CONTINUATIONS.iter().any(|word| {
+            next.strip_prefix(word)
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+        })
+}
+
+/// Last line of a block that ends where indentation returns to the level of
+/// the declaration on line `start`, after any signature continuation lines
+fn indented_block_end(lines: &[&str], start: usize) -> usize {
+    let indent = indentation(lines[start]);
+
+    let mut depth = 0i32;
+    let mut header = start;
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        for c in line.chars() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        header = idx;
+        if depth <= 0 && !line.trim_end().ends_with('\\') {
+            break;
+        }
+    }
+
+    let mut end = header;
+    for (idx, line) in lines.iter().enumerate().skip(header + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indentation(line) <= indent {
+            break;
+        }
+        end = idx;
+    }
+    end
+}
+
+/// Line of the `keyword` (`end`, `@end`) at the same indentation as the
+/// declaration on line `start`; one-liners such as `def f; x; end` end where
+/// they start
+fn end_keyword_line(lines: &[&str], start: usize, keyword: &str) -> usize {
+    let is_end = |text: &str| {
+        text.strip_prefix(keyword)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+    };
+    let header = lines[start].trim_end();
+    if header
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_' || c == '@'))
+        .next()
+        .is_some_and(|last| last == keyword)
+    {
+        return start;
+    }
+
+    let indent = indentation(lines[start]);
+    lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, line)| indentation(line) == indent && is_end(line.trim_start()))
+        .map_or(lines.len() - 1, |(idx, _)| idx)
+}
+
+/// Width of a line's leading whitespace, counting a tab as one column
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope_at(source: &str, language: Language, line: usize) -> EnclosingScope {
+        ScopeIndex::for_source(source, language).enclosing(line)
+    }
+
+    #[test]
+    fn test_java_package_class_and_method() {
+        let source = r#"package com.example.crypto;
+
+public class KeyService {
+    private static final int BITS = 2048;
+
+    public KeyPair generate(int bits)
+            throws GeneralSecurityException
+    {
+        KeyPairGenerator kpg = KeyPairGenerator.getInstance("RSA");
+        return kpg.generateKeyPair();
+    }
+}
+"#;
+        let scope = scope_at(source, Language::Java, 9);
+        assert_eq!(scope.module.as_deref(), Some("com.example.crypto"));
+        assert_eq!(scope.class.as_deref(), Some("KeyService"));
+        assert_eq!(scope.function.as_deref(), Some("generate"));
+        assert_eq!(
+            scope.to_string(),
+            "KeyService.generate (com.example.crypto)"
+        );
+
+        let field = scope_at(source, Language::Java, 4);
+        assert_eq!(field.class.as_deref(), Some("KeyService"));
+        assert_eq!(field.function, None);
+    }
+
+    #[test]
+    fn test_python_indentation() {
+        let source = r#"import hashlib
+
+class Legacy:
+    def digest(
+        self,
+        data,
+    ):
+        """Old { style } hashing"""
+        return hashlib.md5(data)
+
+    # trailing comment
+def top():
+    return hashlib.sha1()
+"#;
+        let scope = scope_at(source, Language::Python, 9);
+        assert_eq!(scope.class.as_deref(), Some("Legacy"));
+        assert_eq!(scope.function.as_deref(), Some("digest"));
+
+        let scope = scope_at(source, Language::Python, 13);
+        assert_eq!(scope.class, None);
+        assert_eq!(scope.function.as_deref(), Some("top"));
+        assert!(scope_at(source, Language::Python, 1).is_empty());
+    }
+
+    #[test]
+    fn test_go_receiver_and_rust_impl() {
+        let source = "package keys\n\nfunc (k *KeyStore) Rotate() error {\n\trsa.GenerateKey(rand.Reader, 1024)\n}\n";
+        let scope = scope_at(source, Language::Go, 4);
+        assert_eq!(scope.module.as_deref(), Some("keys"));
+        assert_eq!(scope.class.as_deref(), Some("KeyStore"));
+        assert_eq!(scope.function.as_deref(), Some("Rotate"));
+
+        let source = r#"mod signing {
+    impl<T> Signer for KeyPair<T> {
+        fn sign(&self) {
+            let key = RsaPrivateKey::new(&mut rng, 1024);
+        }
+    }
+}
+fn main() {}
+"#;
+        let scope = scope_at(source, Language::Rust, 4);
+        assert_eq!(scope.module.as_deref(), Some("signing"));
+        assert_eq!(scope.class.as_deref(), Some("KeyPair"));
+        assert_eq!(scope.function.as_deref(), Some("sign"));
+        assert_eq!(
+            scope_at(source, Language::Rust, 8).function.as_deref(),
+            Some("main")
+        );
+    }
+
+    #[test]
+    fn test_ruby_end_and_makefile_recipe() {
+        let source = r#"module App
+  module Keys
+    class Rotator
+      def rotate
+        OpenSSL::PKey::RSA.new(1024)
+      end
+    end
+  end
+end
+"#;
+        let scope = scope_at(source, Language::Ruby, 5);
+        assert_eq!(scope.module.as_deref(), Some("App::Keys"));
+        assert_eq!(scope.class.as_deref(), Some("Rotator"));
+        assert_eq!(scope.function.as_deref(), Some("rotate"));
+
+        let source = "keys:\n\topenssl genrsa -out key.pem 1024\n\nclean:\n\trm -f key.pem\n";
+        assert_eq!(
+            scope_at(source, Language::Shell, 2).function.as_deref(),
+            Some("keys")
+        );
+        assert_eq!(
+            scope_at(source, Language::Shell, 5).function.as_deref(),
+            Some("clean")
+        );
+    }
+}
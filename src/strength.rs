@@ -0,0 +1,170 @@
+//! Security Strength
+//!
+//! Estimates the bits of work the best known attacks need against an
+//! algorithm at a given key size. Classically, symmetric ciphers offer their
+//! effective key length, hashes half their output size against collisions, and
+//! elliptic curves half the curve size; RSA, DSA and finite-field
+//! Diffie-Hellman use the NFS estimate from NIST SP 800-56B Appendix D.
+//! Against a quantum attacker, Grover's algorithm halves key search, the
+//! Brassard-Hoyer-Tapp algorithm brings collisions down to a third of the
+//! output size, and Shor's algorithm leaves nothing of RSA, DSA,
+//! Diffie-Hellman or elliptic curves.
+
+use crate::types::{CryptoType, SecurityLevel};
+
+/// Post-quantum security a CNSA 2.0-style policy requires: AES-256 for
+/// encryption and SHA-384 or SHA-512 for hashing
+pub const POLICY_POST_QUANTUM_BITS: u32 = 128;
+
+/// Best known MD5 collision attack (Stevens, 2^18 compressions)
+const MD5_COLLISION_BITS: u32 = 18;
+
+/// Best known SHA-1 collision attack (SHAttered, 2^63.1 compressions)
+const SHA1_COLLISION_BITS: u32 = 63;
+
+/// Classical and quantum limit of a 64-bit block cipher under one key: the
+/// birthday bound of 2^32 blocks, after which ciphertext collisions leak
+/// plaintext whatever the key length (Sweet32)
+const BLOCK_64_BIRTHDAY_BITS: u32 = 32;
+
+/// Security of `crypto_type` with a `key_size`-bit key, curve or output, or
+/// `None` when it depends on a key size that was not found, or when the
+/// finding is not an algorithm with a security level. RC4's keystream biases,
+/// ECB mode and static IVs leak plaintext at any key length, so there is no
+/// work factor to report; broken post-quantum candidates have none either.
+pub fn security_level(crypto_type: &CryptoType, key_size: Option<u32>) -> Option<SecurityLevel> {
+    match crypto_type {
+        CryptoType::Rsa | CryptoType::Dsa | CryptoType::DiffieHellman => {
+            key_size.map(|bits| broken_by_shor(nfs_strength(bits)))
+        }
+        CryptoType::Ecdsa | CryptoType::Eddsa | CryptoType::Ecdh => {
+            key_size.map(|bits| broken_by_shor(bits / 2))
+        }
+        CryptoType::Aes => key_size.map(key_search),
+        CryptoType::Blowfish | CryptoType::Rc2 => Some(block_64(key_size.map(key_search))),
+        CryptoType::Des => Some(block_64(Some(key_search(56)))),
+        // Meet-in-the-middle limits three-key 3DES to 112 bits
+        CryptoType::TripleDes => Some(block_64(Some(key_search(112)))),
+        CryptoType::Md5 => Some(collision(128, Some(MD5_COLLISION_BITS))),
+        CryptoType::Sha1 => Some(collision(160, Some(SHA1_COLLISION_BITS))),
+        CryptoType::Sha224 => Some(collision(224, None)),
+        CryptoType::Rc4
+        | CryptoType::EcbMode
+        | CryptoType::StaticIv
+        | CryptoType::Sike
+        | CryptoType::Rainbow
+        | CryptoType::Kyber
+        | CryptoType::Dilithium
+        | CryptoType::SphincsPlus
+        | CryptoType::NonHybridKem
+        | CryptoType::WeakRandom
+        | CryptoType::UnsignedJwt
+        | CryptoType::WeakJwtSecret => None,
+    }
+}
+
+/// Whether `level` meets `POLICY_POST_QUANTUM_BITS`
+pub fn meets_policy(level: &SecurityLevel) -> bool {
+    level.post_quantum_bits >= POLICY_POST_QUANTUM_BITS
+}
+
+/// Exhaustive key search, which Grover's algorithm speeds up quadratically
+fn key_search(key_bits: u32) -> SecurityLevel {
+    SecurityLevel {
+        classical_bits: key_bits,
+        post_quantum_bits: key_bits / 2,
+    }
+}
+
+/// Security of a 64-bit block cipher: its key search, if the key size is
+/// known, capped by `BLOCK_64_BIRTHDAY_BITS`
+fn block_64(key: Option<SecurityLevel>) -> SecurityLevel {
+    SecurityLevel {
+        classical_bits: key.map_or(BLOCK_64_BIRTHDAY_BITS, |k| {
+            k.classical_bits.min(BLOCK_64_BIRTHDAY_BITS)
+        }),
+        post_quantum_bits: key.map_or(BLOCK_64_BIRTHDAY_BITS, |k| {
+            k.post_quantum_bits.min(BLOCK_64_BIRTHDAY_BITS)
+        }),
+    }
+}
+
+/// Collision search on an `output_bits` hash: the birthday bound classically
+/// and Brassard-Hoyer-Tapp on a quantum computer, both capped by any known
+/// cryptanalytic attack
+fn collision(output_bits: u32, attack_bits: Option<u32>) -> SecurityLevel {
+    let cap = attack_bits.unwrap_or(u32::MAX);
+    SecurityLevel {
+        classical_bits: (output_bits / 2).min(cap),
+        post_quantum_bits: (output_bits / 3).min(cap),
+    }
+}
+
+fn broken_by_shor(classical_bits: u32) -> SecurityLevel {
+    SecurityLevel {
+        classical_bits,
+        post_quantum_bits: 0,
+    }
+}
+
+/// Work factor of the general number field sieve against a `modulus_bits`
+/// modulus, rounded to a multiple of 8 as in SP 800-56B (2048 gives 112)
+fn nfs_strength(modulus_bits: u32) -> u32 {
+    let ln_n = f64::from(modulus_bits) * std::f64::consts::LN_2;
+    let work = 1.923 * ln_n.cbrt() * ln_n.ln().powi(2).cbrt() - 4.69;
+    let bits = work / std::f64::consts::LN_2;
+    ((bits / 8.0).round() * 8.0).max(0.0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(crypto_type: CryptoType, key_size: Option<u32>) -> (u32, u32) {
+        let level = security_level(&crypto_type, key_size).unwrap();
+        (level.classical_bits, level.post_quantum_bits)
+    }
+
+    #[test]
+    fn test_symmetric_levels_halved_by_grover() {
+        assert_eq!(level(CryptoType::Aes, Some(128)), (128, 64));
+        assert_eq!(level(CryptoType::Aes, Some(256)), (256, 128));
+        assert!(security_level(&CryptoType::Aes, None).is_none());
+        assert!(security_level(&CryptoType::EcbMode, None).is_none());
+    }
+
+    #[test]
+    fn test_64_bit_block_birthday_bound() {
+        assert_eq!(level(CryptoType::Blowfish, None), (32, 32));
+        assert_eq!(level(CryptoType::Blowfish, Some(128)), (32, 32));
+        assert_eq!(level(CryptoType::Blowfish, Some(40)), (32, 20));
+        assert_eq!(level(CryptoType::Des, None), (32, 28));
+        assert_eq!(level(CryptoType::TripleDes, None), (32, 32));
+        assert_eq!(level(CryptoType::Rc2, None), (32, 32));
+        assert_eq!(level(CryptoType::Rc2, Some(40)), (32, 20));
+    }
+
+    #[test]
+    fn test_hash_collision_levels() {
+        assert_eq!(level(CryptoType::Sha224, None), (112, 74));
+        assert_eq!(level(CryptoType::Sha1, None), (63, 53));
+        assert_eq!(level(CryptoType::Md5, None), (18, 18));
+    }
+
+    #[test]
+    fn test_public_key_levels() {
+        assert_eq!(level(CryptoType::Rsa, Some(1024)), (80, 0));
+        assert_eq!(level(CryptoType::Rsa, Some(2048)), (112, 0));
+        assert_eq!(level(CryptoType::DiffieHellman, Some(3072)), (128, 0));
+        assert_eq!(level(CryptoType::Ecdsa, Some(256)), (128, 0));
+        assert!(security_level(&CryptoType::Rsa, None).is_none());
+    }
+
+    #[test]
+    fn test_policy_requires_aes_256() {
+        let aes_128 = security_level(&CryptoType::Aes, Some(128)).unwrap();
+        let aes_256 = security_level(&CryptoType::Aes, Some(256)).unwrap();
+        assert!(!meets_policy(&aes_128));
+        assert!(meets_policy(&aes_256));
+    }
+}
@@ -0,0 +1,341 @@
+//! Per-file Symbol Table
+//!
+//! Resolves identifiers to the literal constants they were assigned, following
+//! simple `a = b` chains, so that `const BITS = 1024` can supply the key size
+//! for `modulusLength: BITS` and `alg = "MD5"` the algorithm for
+//! `hashlib.new(alg)`. Built from the assignments `parser::parse_file` records.
+//!
+//! A `ProjectIndex` collects the exported constants of every file in a project
+//! so that names a file does not define itself (`CryptoConstants.KEY_SIZE`,
+//! `config.DefaultAlg`) resolve through the file that does.
+
+use crate::parser;
+use crate::types::*;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Longest `a = b` chain followed before giving up
+const MAX_RESOLUTION_DEPTH: usize = 8;
+
+/// Assignments in one file, keyed by name
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    bindings: HashMap<String, Vec<Assignment>>,
+    /// Consulted for names this file does not assign
+    project: Option<Arc<ProjectIndex>>,
+}
+
+impl SymbolTable {
+    pub fn from_parsed(parsed: &ParsedSource) -> Self {
+        let mut table = Self::default();
+        for assignment in &parsed.assignments {
+            table
+                .bindings
+                .entry(assignment.name.clone())
+                .or_default()
+                .push(assignment.clone());
+        }
+        table
+    }
+
+    /// Symbol table for `source`, empty if the language has no parser
+    pub fn for_source(source: &str, language: Language) -> Self {
+        parser::parse_file(source, &language.to_string())
+            .map(|parsed| Self::from_parsed(&parsed))
+            .unwrap_or_default()
+    }
+
+    /// Fall back to constants exported by other files in `project`
+    pub fn with_project(mut self, project: Arc<ProjectIndex>) -> Self {
+        self.project = Some(project);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty() && self.project.as_ref().is_none_or(|p| p.is_empty())
+    }
+
+    /// Resolve `name` as used on `line` to a literal constant
+    pub fn resolve(&self, name: &str, line: usize) -> Option<Resolution> {
+        let mut chain = Vec::new();
+        let mut visited = Vec::new();
+        let mut table = self;
+        let mut file: Option<&str> = None;
+        let mut current = name;
+        let mut at = line;
+
+        while chain.len() < MAX_RESOLUTION_DEPTH {
+            let assignment = match table.binding_at(current, at) {
+                Some(assignment) => assignment,
+                None => {
+                    let (indexed, export) = self.project.as_deref()?.lookup(current)?;
+                    table = &indexed.symbols;
+                    file = Some(&indexed.path);
+                    table.binding_at(&export.name, export.line)?
+                }
+            };
+            // `x = x` style self-references and longer cycles never resolve
+            let key = (file, assignment.line, assignment.name.as_str());
+            if visited.contains(&key) {
+                return None;
+            }
+            visited.push(key);
+            chain.push(ResolutionStep {
+                name: current.to_string(),
+                line: assignment.line,
+                file: file.map(str::to_string),
+            });
+
+            match &assignment.value {
+                AssignedValue::Literal(value) => {
+                    return Some(Resolution {
+                        symbol: name.to_string(),
+                        value: value.clone(),
+                        chain,
+                    });
+                }
+                AssignedValue::Identifier(next) => {
+                    current = next;
+                    at = assignment.line;
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The last assignment to `name` at or before `line`, or failing that the
+    /// first one after it (e.g. a constant declared at the bottom of a file).
+    /// `Class.NAME` finds a `NAME` this file declares inside `Class`.
+    fn binding_at(&self, name: &str, line: usize) -> Option<&Assignment> {
+        let (assignments, qualifier) = match self.bindings.get(name) {
+            Some(assignments) => (assignments, None),
+            None => {
+                let (qualifier, bare) = name.rsplit_once('.')?;
+                (self.bindings.get(bare)?, Some(qualifier))
+            }
+        };
+        let in_scope = |a: &&Assignment| qualifier.is_none() || a.qualifier.as_deref() == qualifier;
+        assignments
+            .iter()
+            .rev()
+            .filter(in_scope)
+            .find(|a| a.line <= line)
+            .or_else(|| assignments.iter().find(in_scope))
+    }
+}
+
+/// Exported constants from every file of a project, keyed by the names other
+/// files use for them: the bare name plus `Class.NAME` (Java, C#), `package.Name`
+/// (Go) or `module.NAME` (Python)
+#[derive(Debug, Default)]
+pub struct ProjectIndex {
+    files: Vec<IndexedFile>,
+    exports: HashMap<String, Vec<ExportedSymbol>>,
+}
+
+#[derive(Debug)]
+struct IndexedFile {
+    path: String,
+    symbols: SymbolTable,
+}
+
+#[derive(Debug)]
+struct ExportedSymbol {
+    file: usize,
+    name: String,
+    line: usize,
+}
+
+impl ProjectIndex {
+    /// Index the exported constants of one file; files the parser does not
+    /// support are skipped
+    pub fn add_file(&mut self, path: &str, source: &str, language: Language) {
+        let Ok(parsed) = parser::parse_file(source, &language.to_string()) else {
+            return;
+        };
+        let module = Path::new(path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned());
+
+        let file = self.files.len();
+        for assignment in parsed.assignments.iter().filter(|a| a.exported) {
+            let qualifier = match language {
+                Language::Python => module.as_ref(),
+                _ => assignment.qualifier.as_ref(),
+            };
+            let mut keys = vec![assignment.name.clone()];
+            keys.extend(qualifier.map(|q| format!("{}.{}", q, assignment.name)));
+
+            for key in keys {
+                self.exports.entry(key).or_default().push(ExportedSymbol {
+                    file,
+                    name: assignment.name.clone(),
+                    line: assignment.line,
+                });
+            }
+        }
+
+        self.files.push(IndexedFile {
+            path: path.to_string(),
+            symbols: SymbolTable::from_parsed(&parsed),
+        });
+    }
+
+    /// Number of names exported across the project
+    pub fn len(&self) -> usize {
+        self.exports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exports.is_empty()
+    }
+
+    /// The file exporting `name`, unless several files export it. A file that
+    /// reassigns the name resolves to its last assignment.
+    fn lookup(&self, name: &str) -> Option<(&IndexedFile, &ExportedSymbol)> {
+        let exports = self.exports.get(name)?;
+        let last = exports.last()?;
+        if exports.iter().any(|e| e.file != last.file) {
+            return None;
+        }
+        Some((&self.files[last.file], last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(source: &str, language: Language) -> SymbolTable {
+        SymbolTable::for_source(source, language)
+    }
+
+    #[test]
+    fn test_resolves_literals_and_chains() {
+        let symbols = table(
+            "const BITS = 1024;\nconst SIZE = BITS;\nlet alg = 'sha1';\n",
+            Language::JavaScript,
+        );
+
+        let size = symbols.resolve("SIZE", 4).unwrap();
+        assert_eq!(size.value, ConstantValue::Integer(1024));
+        assert_eq!(
+            size.chain
+                .iter()
+                .map(|s| (s.name.as_str(), s.line))
+                .collect::<Vec<_>>(),
+            vec![("SIZE", 2), ("BITS", 1)]
+        );
+        assert_eq!(size.to_string(), "SIZE (line 2) = BITS (line 1) = 1024");
+
+        assert_eq!(
+            symbols.resolve("alg", 4).unwrap().value,
+            ConstantValue::String("sha1".to_string())
+        );
+        assert!(symbols.resolve("missing", 4).is_none());
+    }
+
+    #[test]
+    fn test_latest_assignment_before_use_wins() {
+        let symbols = table(
+            "alg = \"MD5\"\nh1 = hashlib.new(alg)\nalg = \"sha256\"  # upgraded\n",
+            Language::Python,
+        );
+        assert_eq!(
+            symbols.resolve("alg", 2).unwrap().value,
+            ConstantValue::String("MD5".to_string())
+        );
+        assert_eq!(
+            symbols.resolve("alg", 4).unwrap().value,
+            ConstantValue::String("sha256".to_string())
+        );
+    }
+
+    #[test]
+    fn test_language_declaration_forms() {
+        let rust = table("pub const RSA_BITS: usize = 2_048;", Language::Rust);
+        assert_eq!(
+            rust.resolve("RSA_BITS", 5).unwrap().value,
+            ConstantValue::Integer(2048)
+        );
+
+        let java = table("private static final int KEY_SIZE = 1024;", Language::Java);
+        assert_eq!(
+            java.resolve("KEY_SIZE", 5).unwrap().value,
+            ConstantValue::Integer(1024)
+        );
+
+        let go = table("const (\n\tDefaultAlg = \"RSA\"\n)\n", Language::Go);
+        assert_eq!(
+            go.resolve("DefaultAlg", 5).unwrap().value,
+            ConstantValue::String("RSA".to_string())
+        );
+
+        // Computed values are not constants
+        let js = table("const size = base * 2;", Language::JavaScript);
+        assert!(js.is_empty());
+    }
+
+    #[test]
+    fn test_qualified_name_resolves_within_file() {
+        let symbols = table(
+            "object CryptoConfig {\n    const val CIPHER = \"DES/ECB/PKCS5Padding\"\n}\n",
+            Language::Kotlin,
+        );
+        assert_eq!(
+            symbols.resolve("CryptoConfig.CIPHER", 5).unwrap().value,
+            ConstantValue::String("DES/ECB/PKCS5Padding".to_string())
+        );
+        assert!(symbols.resolve("OtherConfig.CIPHER", 5).is_none());
+    }
+
+    #[test]
+    fn test_project_index_resolves_across_files() {
+        let mut index = ProjectIndex::default();
+        index.add_file(
+            "src/CryptoConstants.java",
+            "public final class CryptoConstants {\n    public static final int KEY_SIZE = DEFAULT_SIZE;\n    static final int DEFAULT_SIZE = 1024;\n    private static final String SECRET = \"x\";\n}\n",
+            Language::Java,
+        );
+        index.add_file(
+            "config/config.go",
+            "package config\n\nconst (\n\tDefaultAlg = \"RSA\"\n\tinternalAlg = \"MD5\"\n)\n",
+            Language::Go,
+        );
+        let index = Arc::new(index);
+
+        let java = table("int bits = CryptoConstants.KEY_SIZE;", Language::Java)
+            .with_project(Arc::clone(&index));
+        let bits = java.resolve("bits", 2).unwrap();
+        assert_eq!(bits.value, ConstantValue::Integer(1024));
+        assert_eq!(
+            bits.to_string(),
+            "bits (line 1) = CryptoConstants.KEY_SIZE (src/CryptoConstants.java:2) = DEFAULT_SIZE (src/CryptoConstants.java:3) = 1024"
+        );
+        assert!(java.resolve("CryptoConstants.SECRET", 2).is_none());
+
+        let go = SymbolTable::default().with_project(Arc::clone(&index));
+        assert_eq!(
+            go.resolve("config.DefaultAlg", 1).unwrap().value,
+            ConstantValue::String("RSA".to_string())
+        );
+        assert!(go.resolve("config.internalAlg", 1).is_none());
+    }
+
+    #[test]
+    fn test_ambiguous_project_exports_do_not_resolve() {
+        let mut index = ProjectIndex::default();
+        index.add_file("a.js", "export const ALG = 'md5';", Language::JavaScript);
+        index.add_file("b.js", "export const ALG = 'sha256';", Language::JavaScript);
+        let symbols = SymbolTable::default().with_project(Arc::new(index));
+        assert!(symbols.resolve("ALG", 1).is_none());
+    }
+
+    #[test]
+    fn test_cycles_do_not_resolve() {
+        let symbols = table("a = b\nb = a\n", Language::Python);
+        assert!(symbols.resolve("a", 3).is_none());
+    }
+}
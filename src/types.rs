@@ -13,6 +13,19 @@ pub enum Language {
     Go,
     Cpp,
     Csharp,
+    Kotlin,
+    Scala,
+    Php,
+    Ruby,
+    Swift,
+    #[serde(rename = "objc")]
+    ObjectiveC,
+    /// Shell scripts, Dockerfiles and Makefiles
+    Shell,
+    Solidity,
+    /// JSON Web Keys and Key Sets (`jwks.json`, `.jwk`, `.jwks`) and OpenID
+    /// discovery documents
+    Json,
 }
 
 impl Language {
@@ -24,11 +37,27 @@ impl Language {
             "python" | "py" => Some(Language::Python),
             "java" => Some(Language::Java),
             "go" | "golang" => Some(Language::Go),
-            "cpp" | "c++" | "cxx" => Some(Language::Cpp),
+            "cpp" | "c++" | "cxx" | "cc" | "c" | "h" | "hpp" => Some(Language::Cpp),
             "csharp" | "cs" | "c#" => Some(Language::Csharp),
+            "kotlin" | "kt" | "kts" => Some(Language::Kotlin),
+            "scala" | "sc" => Some(Language::Scala),
+            "php" => Some(Language::Php),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "swift" => Some(Language::Swift),
+            "objc" | "objective-c" | "objectivec" | "m" | "mm" => Some(Language::ObjectiveC),
+            "shell" | "sh" | "bash" | "zsh" | "dockerfile" | "makefile" | "make" => {
+                Some(Language::Shell)
+            }
+            "solidity" | "sol" => Some(Language::Solidity),
+            "json" | "jwk" | "jwks" => Some(Language::Json),
             _ => None,
         }
     }
+
+    /// Languages that run on the JVM and share the JCA provider APIs
+    pub fn is_jvm(self) -> bool {
+        matches!(self, Language::Java | Language::Kotlin | Language::Scala)
+    }
 }
 
 impl fmt::Display for Language {
@@ -42,10 +71,44 @@ impl fmt::Display for Language {
             Language::Go => write!(f, "go"),
             Language::Cpp => write!(f, "cpp"),
             Language::Csharp => write!(f, "csharp"),
+            Language::Kotlin => write!(f, "kotlin"),
+            Language::Scala => write!(f, "scala"),
+            Language::Php => write!(f, "php"),
+            Language::Ruby => write!(f, "ruby"),
+            Language::Swift => write!(f, "swift"),
+            Language::ObjectiveC => write!(f, "objc"),
+            Language::Shell => write!(f, "shell"),
+            Language::Solidity => write!(f, "solidity"),
+            Language::Json => write!(f, "json"),
         }
     }
 }
 
+/// How `Language::detect` chose a language, most reliable first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetectionMethod {
+    /// Vim or Emacs modeline naming the file type
+    Modeline,
+    /// Well-known file name such as `Dockerfile` or `Rakefile`
+    Filename,
+    /// File extension
+    Extension,
+    /// `#!` interpreter line
+    Shebang,
+    /// Syntax characteristic of the language
+    Content,
+}
+
+/// Language detected for a file, with the heuristic that decided it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LanguageDetection {
+    pub language: Language,
+    /// How reliable the detection is (0.0-1.0)
+    pub confidence: f32,
+    pub method: DetectionMethod,
+}
+
 /// Severity levels for vulnerabilities
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
@@ -56,12 +119,37 @@ pub enum Severity {
     Critical,
 }
 
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Critical => write!(f, "CRITICAL"),
+            Severity::High => write!(f, "HIGH"),
+            Severity::Medium => write!(f, "MEDIUM"),
+            Severity::Low => write!(f, "LOW"),
+        }
+    }
+}
+
+/// Category of cryptographic algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CryptoCategory {
+    AsymmetricEncryption,
+    KeyExchange,
+    DigitalSignature,
+    Hash,
+    SymmetricEncryption,
+    Random,
+}
+
 /// Types of cryptographic algorithms detected
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum CryptoType {
     Rsa,
     Ecdsa,
+    /// Edwards-curve signatures (Ed25519, Ed448)
+    Eddsa,
     Ecdh,
     Dsa,
     DiffieHellman,
@@ -70,6 +158,32 @@ pub enum CryptoType {
     Des,
     TripleDes,
     Rc4,
+    Aes,
+    Blowfish,
+    Rc2,
+    Sha224,
+    /// Electronic codebook mode, with any block cipher
+    EcbMode,
+    /// Hard-coded or all-zero initialization vector or nonce
+    StaticIv,
+    /// SIKE/SIDH isogeny KEM, broken by the Castryck-Decru attack
+    Sike,
+    /// Rainbow multivariate signatures, broken by Beullens' attack
+    Rainbow,
+    /// Round-3 CRYSTALS-Kyber, superseded by ML-KEM (FIPS 203)
+    Kyber,
+    /// Round-3 CRYSTALS-Dilithium, superseded by ML-DSA (FIPS 204)
+    Dilithium,
+    /// Round-3 SPHINCS+, superseded by SLH-DSA (FIPS 205)
+    SphincsPlus,
+    /// Post-quantum KEM configured on its own where a hybrid is required
+    NonHybridKem,
+    /// Non-cryptographic random number generator or predictable seed
+    WeakRandom,
+    /// JWT accepted or issued with `alg: none`, or without signature verification
+    UnsignedJwt,
+    /// Hard-coded or short HMAC secret signing JWTs
+    WeakJwtSecret,
 }
 
 impl fmt::Display for CryptoType {
@@ -77,6 +191,7 @@ impl fmt::Display for CryptoType {
         match self {
             CryptoType::Rsa => write!(f, "RSA"),
             CryptoType::Ecdsa => write!(f, "ECDSA"),
+            CryptoType::Eddsa => write!(f, "EdDSA"),
             CryptoType::Ecdh => write!(f, "ECDH"),
             CryptoType::Dsa => write!(f, "DSA"),
             CryptoType::DiffieHellman => write!(f, "Diffie-Hellman"),
@@ -85,16 +200,424 @@ impl fmt::Display for CryptoType {
             CryptoType::Des => write!(f, "DES"),
             CryptoType::TripleDes => write!(f, "3DES"),
             CryptoType::Rc4 => write!(f, "RC4"),
+            CryptoType::Aes => write!(f, "AES"),
+            CryptoType::Blowfish => write!(f, "Blowfish"),
+            CryptoType::Rc2 => write!(f, "RC2"),
+            CryptoType::Sha224 => write!(f, "SHA-224"),
+            CryptoType::EcbMode => write!(f, "ECB mode"),
+            CryptoType::StaticIv => write!(f, "Static IV"),
+            CryptoType::Sike => write!(f, "SIKE"),
+            CryptoType::Rainbow => write!(f, "Rainbow"),
+            CryptoType::Kyber => write!(f, "Kyber"),
+            CryptoType::Dilithium => write!(f, "Dilithium"),
+            CryptoType::SphincsPlus => write!(f, "SPHINCS+"),
+            CryptoType::NonHybridKem => write!(f, "Non-hybrid ML-KEM"),
+            CryptoType::WeakRandom => write!(f, "Weak random"),
+            CryptoType::UnsignedJwt => write!(f, "Unsigned JWT"),
+            CryptoType::WeakJwtSecret => write!(f, "Weak JWT secret"),
+        }
+    }
+}
+
+impl CryptoType {
+    /// Default category for findings of this algorithm
+    pub fn category(&self) -> CryptoCategory {
+        match self {
+            CryptoType::Rsa => CryptoCategory::AsymmetricEncryption,
+            CryptoType::Ecdsa
+            | CryptoType::Eddsa
+            | CryptoType::Dsa
+            | CryptoType::Rainbow
+            | CryptoType::Dilithium
+            | CryptoType::SphincsPlus
+            | CryptoType::UnsignedJwt
+            | CryptoType::WeakJwtSecret => CryptoCategory::DigitalSignature,
+            CryptoType::Ecdh
+            | CryptoType::DiffieHellman
+            | CryptoType::Sike
+            | CryptoType::Kyber
+            | CryptoType::NonHybridKem => CryptoCategory::KeyExchange,
+            CryptoType::Sha1 | CryptoType::Md5 | CryptoType::Sha224 => CryptoCategory::Hash,
+            CryptoType::Des
+            | CryptoType::TripleDes
+            | CryptoType::Rc4
+            | CryptoType::Aes
+            | CryptoType::Blowfish
+            | CryptoType::Rc2
+            | CryptoType::EcbMode
+            | CryptoType::StaticIv => CryptoCategory::SymmetricEncryption,
+            CryptoType::WeakRandom => CryptoCategory::Random,
+        }
+    }
+
+    /// Whether Shor's algorithm breaks this algorithm at any key size
+    pub fn is_quantum_vulnerable(&self) -> bool {
+        matches!(
+            self,
+            CryptoType::Rsa
+                | CryptoType::Ecdsa
+                | CryptoType::Eddsa
+                | CryptoType::Ecdh
+                | CryptoType::Dsa
+                | CryptoType::DiffieHellman
+        )
+    }
+
+    /// Post-quantum candidates that classical cryptanalysis has broken
+    pub fn is_broken_pqc(&self) -> bool {
+        matches!(self, CryptoType::Sike | CryptoType::Rainbow)
+    }
+
+    /// Draft versions of the NIST post-quantum standards, and the standard
+    /// each one became
+    pub fn standard_successor(&self) -> Option<PqcFamily> {
+        match self {
+            CryptoType::Kyber => Some(PqcFamily::MlKem),
+            CryptoType::Dilithium => Some(PqcFamily::MlDsa),
+            CryptoType::SphincsPlus => Some(PqcFamily::SlhDsa),
+            _ => None,
+        }
+    }
+
+    /// False for findings about how an algorithm is used (ECB mode, static
+    /// IVs, a KEM without its hybrid, unsigned tokens, weak JWT secrets) rather
+    /// than which algorithm it is
+    pub fn is_algorithm(&self) -> bool {
+        !matches!(
+            self,
+            CryptoType::EcbMode
+                | CryptoType::StaticIv
+                | CryptoType::NonHybridKem
+                | CryptoType::UnsignedJwt
+                | CryptoType::WeakJwtSecret
+        )
+    }
+}
+
+/// Estimated strength of an algorithm instance, in bits of work for the best
+/// known classical and quantum attacks (see `strength`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityLevel {
+    pub classical_bits: u32,
+    pub post_quantum_bits: u32,
+}
+
+impl fmt::Display for SecurityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-bit classical, {}-bit post-quantum",
+            self.classical_bits, self.post_quantum_bits
+        )
+    }
+}
+
+/// NIST post-quantum standard an algorithm belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PqcFamily {
+    /// Module-lattice key encapsulation (FIPS 203)
+    #[serde(rename = "ML-KEM")]
+    MlKem,
+    /// Module-lattice signatures (FIPS 204)
+    #[serde(rename = "ML-DSA")]
+    MlDsa,
+    /// Stateless hash-based signatures (FIPS 205)
+    #[serde(rename = "SLH-DSA")]
+    SlhDsa,
+}
+
+impl fmt::Display for PqcFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PqcFamily::MlKem => write!(f, "ML-KEM"),
+            PqcFamily::MlDsa => write!(f, "ML-DSA"),
+            PqcFamily::SlhDsa => write!(f, "SLH-DSA"),
+        }
+    }
+}
+
+impl PqcFamily {
+    /// FIPS publication that standardizes the family
+    pub fn standard(&self) -> &'static str {
+        match self {
+            PqcFamily::MlKem => "FIPS 203",
+            PqcFamily::MlDsa => "FIPS 204",
+            PqcFamily::SlhDsa => "FIPS 205",
+        }
+    }
+
+    pub fn category(&self) -> CryptoCategory {
+        match self {
+            PqcFamily::MlKem => CryptoCategory::KeyExchange,
+            PqcFamily::MlDsa | PqcFamily::SlhDsa => CryptoCategory::DigitalSignature,
+        }
+    }
+}
+
+/// A use of a standardized post-quantum algorithm: part of the quantum-safe
+/// inventory rather than a vulnerability
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantumSafeFinding {
+    pub family: PqcFamily,
+
+    /// Parameter set, e.g. `ML-KEM-768` or `SLH-DSA-SHA2-128s`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter_set: Option<String>,
+
+    /// NIST security category (1-5) of the parameter set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_category: Option<u8>,
+
+    /// Classical algorithm paired with it in a hybrid key exchange or composite
+    /// signature, e.g. `X25519` in `X25519MLKEM768`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid_with: Option<String>,
+
+    pub line: usize,
+    pub column: usize,
+    pub span: SourceSpan,
+    pub context: String,
+
+    #[serde(default, skip_serializing_if = "EnclosingScope::is_empty")]
+    pub scope: EnclosingScope,
+}
+
+impl fmt::Display for QuantumSafeFinding {
+    /// `ML-KEM-768`, or `X25519 + ML-KEM-768` for a hybrid
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(classical) = &self.hybrid_with {
+            write!(f, "{} + ", classical)?;
+        }
+        match &self.parameter_set {
+            Some(parameter_set) => write!(f, "{}", parameter_set),
+            None => write!(f, "{}", self.family),
+        }
+    }
+}
+
+/// Where in the source a match was found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanKind {
+    #[default]
+    Code,
+    Comment,
+    StringLiteral,
+    DocString,
+}
+
+impl fmt::Display for SpanKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpanKind::Code => write!(f, "code"),
+            SpanKind::Comment => write!(f, "comment"),
+            SpanKind::StringLiteral => write!(f, "string literal"),
+            SpanKind::DocString => write!(f, "docstring"),
+        }
+    }
+}
+
+/// Exact extent of a match in the source; offsets are end-exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub byte_start: usize,
+    pub byte_end: usize,
+    /// Offsets in Unicode scalar values, for editors that index by character
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+impl SourceSpan {
+    /// Span of bytes `start..end` of `text`, with character offsets counted within `text`
+    pub fn from_byte_range(text: &str, start: usize, end: usize) -> Self {
+        // Count characters that begin before each offset, so offsets that fall
+        // inside a multi-byte character do not panic
+        let chars_before =
+            |offset: usize| text.char_indices().take_while(|(i, _)| *i < offset).count();
+        Self {
+            byte_start: start,
+            byte_end: end,
+            char_start: chars_before(start),
+            char_end: chars_before(end),
+        }
+    }
+
+    /// Move the span by the given byte and character offsets
+    pub fn shifted(self, bytes: usize, chars: usize) -> Self {
+        Self {
+            byte_start: self.byte_start + bytes,
+            byte_end: self.byte_end + bytes,
+            char_start: self.char_start + chars,
+            char_end: self.char_end + chars,
+        }
+    }
+}
+
+/// Function, class and module a finding sits in; each is absent at top level
+/// or when the language parser does not declare it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnclosingScope {
+    /// Package, namespace or module: `com.example.crypto`, `App::Keys`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Class, struct, trait, contract or `impl` target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    /// Innermost named function or method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+}
+
+impl EnclosingScope {
+    pub fn is_empty(&self) -> bool {
+        self.module.is_none() && self.class.is_none() && self.function.is_none()
+    }
+
+    /// Class and function joined as they would be referenced, e.g.
+    /// `KeyService.generate`
+    pub fn symbol(&self) -> Option<String> {
+        match (&self.class, &self.function) {
+            (Some(class), Some(function)) => Some(format!("{}.{}", class, function)),
+            (class, function) => class.clone().or_else(|| function.clone()),
+        }
+    }
+}
+
+impl fmt::Display for EnclosingScope {
+    /// `Class.function (module)`, or whichever parts are known
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.symbol(), &self.module) {
+            (Some(symbol), Some(module)) => write!(f, "{} ({})", symbol, module),
+            (Some(symbol), None) => write!(f, "{}", symbol),
+            (None, Some(module)) => write!(f, "{}", module),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Evidence that raised or lowered a finding's confidence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalKind {
+    /// The match is part of a function or constructor call
+    ApiCall,
+    /// The match is an algorithm name passed to a crypto API
+    AlgorithmArgument,
+    /// The file imports a cryptography library
+    CryptoImport,
+    /// A key size was extracted for the match
+    KeySize,
+    /// The match is only a fragment of a larger identifier (e.g. `rsa_total`)
+    IdentifierFragment,
+    /// The match is an argument to a crypto command-line tool (`openssl`, `ssh-keygen`)
+    CommandArgument,
+}
+
+/// One signal in a finding's explanation trail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceSignal {
+    pub kind: SignalKind,
+    /// Amount added to (or subtracted from) the rule's base confidence
+    pub adjustment: f32,
+    pub detail: String,
+}
+
+/// Why a finding was reported and how its confidence was reached
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Explanation {
+    pub rule_id: String,
+    pub matched_text: String,
+    /// Confidence of the rule before any signals were applied
+    pub base_confidence: f32,
+    pub signals: Vec<ConfidenceSignal>,
+    /// Constant the algorithm name was resolved from, for findings on an identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm_resolution: Option<Resolution>,
+    /// Constant the key size was resolved from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_size_resolution: Option<Resolution>,
+    /// JCA transformation the matched string names, e.g. `DES/ECB/PKCS5Padding`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transformation: Option<Transformation>,
+    /// Command-line tool invocation the finding was parsed from, e.g. `openssl genrsa`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Key, IV, nonce or token a weak random value flows into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flows_into: Option<ResolutionStep>,
+}
+
+/// A JCA `Cipher.getInstance` transformation split into its parts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transformation {
+    pub algorithm: String,
+    pub mode: Option<String>,
+    pub padding: Option<String>,
+}
+
+impl fmt::Display for Transformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.algorithm)?;
+        if let Some(mode) = &self.mode {
+            write!(f, " in {} mode", mode)?;
         }
+        if let Some(padding) = &self.padding {
+            write!(f, " with {}", padding)?;
+        }
+        Ok(())
     }
 }
 
+/// One assignment followed while resolving an identifier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionStep {
+    pub name: String,
+    pub line: usize,
+    /// File the assignment is in, when it is not the file being analyzed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// How an identifier in the matched code was resolved to a literal constant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resolution {
+    /// Identifier as written at the finding
+    pub symbol: String,
+    pub value: ConstantValue,
+    /// Assignments followed, from `symbol` to the literal
+    pub chain: Vec<ResolutionStep>,
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.chain {
+            match &step.file {
+                Some(file) => write!(f, "{} ({}:{}) = ", step.name, file, step.line)?,
+                None => write!(f, "{} (line {}) = ", step.name, step.line)?,
+            }
+        }
+        write!(f, "{}", self.value)
+    }
+}
+
+/// A candidate match discarded in favour of an overlapping, more specific finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuppressedMatch {
+    pub rule_id: String,
+    pub crypto_type: CryptoType,
+    pub span: SourceSpan,
+}
+
 /// Individual vulnerability finding
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct Vulnerability {
     /// Type of crypto algorithm found
     pub crypto_type: CryptoType,
 
+    /// Category of the algorithm (signature, key exchange, hash, ...)
+    pub category: CryptoCategory,
+
     /// Severity level
     pub severity: Severity,
 
@@ -104,12 +627,20 @@ pub struct Vulnerability {
     /// Line number in source code
     pub line: usize,
 
-    /// Column number in source code
+    /// Column number in source code (byte offset within the line)
     pub column: usize,
 
+    /// Exact match span within the whole source
+    #[serde(default)]
+    pub span: SourceSpan,
+
     /// Context snippet from source
     pub context: String,
 
+    /// Function, class and module the finding sits in
+    #[serde(default, skip_serializing_if = "EnclosingScope::is_empty")]
+    pub scope: EnclosingScope,
+
     /// Detailed description
     pub message: String,
 
@@ -118,6 +649,62 @@ pub struct Vulnerability {
 
     /// Key size detected (if applicable)
     pub key_size: Option<u32>,
+
+    /// Classical and post-quantum security of the algorithm at `key_size`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_level: Option<SecurityLevel>,
+
+    /// Source region the match was found in (code or string literal)
+    #[serde(default)]
+    pub span_kind: SpanKind,
+
+    /// ID of the rule that produced this finding
+    #[serde(default)]
+    pub rule_id: String,
+
+    /// CWE identifier for the weakness (e.g. "CWE-327")
+    #[serde(default)]
+    pub cwe: Option<String>,
+
+    /// Certainty that this is a real use of the algorithm (0.0-1.0)
+    #[serde(default)]
+    pub confidence: f32,
+
+    /// Rule, matched text and signals behind `confidence`
+    #[serde(default)]
+    pub explanation: Explanation,
+
+    /// Overlapping candidates from other rules that lost to this finding (debug metadata)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<SuppressedMatch>,
+}
+
+impl Serialize for Vulnerability {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Vulnerability::serialize(self, serializer)
+    }
+}
+
+/// Reports written before findings carried a category read back with the
+/// category of their `crypto_type`.
+impl<'de> Deserialize<'de> for Vulnerability {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        if let Some(finding) = value.as_object_mut() {
+            if !finding.contains_key("category") {
+                let category = finding
+                    .get("crypto_type")
+                    .and_then(|crypto_type| CryptoType::deserialize(crypto_type).ok())
+                    .map(|crypto_type| crypto_type.category());
+                if let Some(category) = category {
+                    let category =
+                        serde_json::to_value(category).map_err(serde::de::Error::custom)?;
+                    finding.insert("category".to_string(), category);
+                }
+            }
+        }
+        Vulnerability::deserialize(value).map_err(serde::de::Error::custom)
+    }
 }
 
 /// Complete audit result
@@ -126,6 +713,10 @@ pub struct AuditResult {
     /// List of vulnerabilities found
     pub vulnerabilities: Vec<Vulnerability>,
 
+    /// Standardized post-quantum algorithms in use
+    #[serde(default)]
+    pub quantum_safe: Vec<QuantumSafeFinding>,
+
     /// Overall risk score (0-100)
     pub risk_score: u32,
 
@@ -148,12 +739,15 @@ pub struct AuditStats {
     pub medium_count: usize,
     pub low_count: usize,
     pub lines_scanned: usize,
+    #[serde(default)]
+    pub quantum_safe_count: usize,
 }
 
 impl AuditResult {
     pub fn new(language: Language, lines_scanned: usize) -> Self {
         Self {
             vulnerabilities: Vec::new(),
+            quantum_safe: Vec::new(),
             risk_score: 0,
             language,
             recommendations: Vec::new(),
@@ -164,6 +758,7 @@ impl AuditResult {
                 medium_count: 0,
                 low_count: 0,
                 lines_scanned,
+                quantum_safe_count: 0,
             },
         }
     }
@@ -179,6 +774,38 @@ impl AuditResult {
         self.vulnerabilities.push(vuln);
     }
 
+    pub fn add_quantum_safe(&mut self, finding: QuantumSafeFinding) {
+        self.stats.quantum_safe_count += 1;
+        self.quantum_safe.push(finding);
+    }
+
+    /// Percentage of public-key algorithm uses that are post-quantum: quantum-safe
+    /// findings out of those plus quantum-vulnerable vulnerabilities. `None`
+    /// when there are neither.
+    pub fn migration_progress(&self) -> Option<u32> {
+        let migrated = self.quantum_safe.len();
+        let remaining = self
+            .vulnerabilities
+            .iter()
+            .filter(|v| v.crypto_type.is_quantum_vulnerable())
+            .count();
+        let total = migrated + remaining;
+        (total > 0).then(|| (migrated * 100 / total) as u32)
+    }
+
+    /// Distinct post-quantum algorithms in use, as `ML-KEM-768` or
+    /// `X25519 + ML-KEM-768`, in order of first use
+    pub fn quantum_safe_algorithms(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for finding in &self.quantum_safe {
+            let name = finding.to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
     pub fn calculate_risk_score(&mut self) {
         if self.vulnerabilities.is_empty() {
             self.risk_score = 0;
@@ -236,6 +863,36 @@ impl AuditResult {
             );
         }
 
+        if self
+            .vulnerabilities
+            .iter()
+            .any(|v| v.crypto_type.is_broken_pqc())
+        {
+            self.recommendations.push(
+                "CRITICAL: Remove broken post-quantum candidates (SIKE/SIDH, Rainbow) and use ML-KEM-768 (FIPS 203) or ML-DSA-65 (FIPS 204)".to_string()
+            );
+        }
+
+        if self
+            .vulnerabilities
+            .iter()
+            .any(|v| v.crypto_type.standard_successor().is_some())
+        {
+            self.recommendations.push(
+                "Move pre-standard post-quantum drafts to their FIPS parameter sets: Kyber768 to ML-KEM-768, Dilithium3 to ML-DSA-65, SPHINCS+-SHA2-128s to SLH-DSA-SHA2-128s".to_string()
+            );
+        }
+
+        if self
+            .vulnerabilities
+            .iter()
+            .any(|v| v.crypto_type == CryptoType::UnsignedJwt)
+        {
+            self.recommendations.push(
+                "CRITICAL: Reject JWTs with `alg: none` and always verify signatures against an explicit algorithm allow-list".to_string()
+            );
+        }
+
         self.recommendations.push(
             "Follow NIST Post-Quantum Cryptography Standardization guidelines: https://csrc.nist.gov/projects/post-quantum-cryptography".to_string()
         );
@@ -243,12 +900,14 @@ impl AuditResult {
 }
 
 // Parser types
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeType {
     Import,
     FunctionDeclaration,
     ClassDeclaration,
     VariableDeclaration,
+    /// Package, namespace or module
+    ModuleDeclaration,
 }
 
 #[derive(Debug, Clone)]
@@ -261,10 +920,104 @@ pub struct AstNode {
 
 #[derive(Debug, Clone)]
 pub struct FunctionCall {
+    /// Callee as written, including its receiver: `crypto.createHash`,
+    /// `OpenSSL::PKey::RSA.new`
     pub name: String,
+    /// Object, module or type the callee is reached through: `crypto`,
+    /// `OpenSSL::PKey::RSA`
+    pub receiver: Option<String>,
     pub line: usize,
     pub column: usize,
-    pub args: Vec<String>,
+    pub args: Vec<CallArgument>,
+}
+
+impl FunctionCall {
+    /// Callee without its receiver, e.g. `createHash`
+    pub fn method(&self) -> &str {
+        match &self.receiver {
+            Some(receiver) => self.name[receiver.len()..].trim_start_matches(['.', ':', '-', '>']),
+            None => &self.name,
+        }
+    }
+
+    /// The string literal passed as the `index`th argument, if any
+    pub fn string_argument(&self, index: usize) -> Option<&str> {
+        match &self.args.get(index)?.value {
+            ArgumentValue::Literal(ConstantValue::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// One argument in a call's argument list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallArgument {
+    /// Keyword or label of a named argument: `key_size=`, `keySize:`
+    pub name: Option<String>,
+    pub value: ArgumentValue,
+    /// The argument as written, without its name
+    pub text: String,
+    /// Byte offset of `text` in the source
+    pub offset: usize,
+}
+
+/// What an argument is, as far as it can be told from the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgumentValue {
+    /// String, numeric, boolean or null literal
+    Literal(ConstantValue),
+    /// A (possibly qualified) name, e.g. `ALG` or `Config.ALG`
+    Identifier(String),
+    /// Any other expression
+    Expression,
+}
+
+/// A literal constant value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConstantValue {
+    Integer(u64),
+    String(String),
+    /// Negative or floating-point number, e.g. `-1` or `0.5`
+    Number(f64),
+    Boolean(bool),
+    /// `null`, `nil`, `None`, `NULL`, `nullptr` or `undefined`
+    Null,
+}
+
+// Numbers come from parsed literals, which are never NaN
+impl Eq for ConstantValue {}
+
+impl fmt::Display for ConstantValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstantValue::Integer(n) => write!(f, "{}", n),
+            ConstantValue::String(s) => write!(f, "\"{}\"", s),
+            ConstantValue::Number(n) => write!(f, "{}", n),
+            ConstantValue::Boolean(b) => write!(f, "{}", b),
+            ConstantValue::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// Right-hand side of a simple assignment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignedValue {
+    Literal(ConstantValue),
+    /// Another (possibly dotted) identifier, e.g. `ALG` or `Config.ALG`
+    Identifier(String),
+}
+
+/// A constant declaration or simple assignment, e.g. `const BITS = 1024`
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub name: String,
+    pub value: AssignedValue,
+    pub line: usize,
+    /// Visible to other files (`export const`, `pub const`, `static final`, ...)
+    pub exported: bool,
+    /// Enclosing class (Java) or package (Go) other files qualify the name with
+    pub qualifier: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -273,6 +1026,7 @@ pub struct ParsedSource {
     pub ast_nodes: Vec<AstNode>,
     pub imports: Vec<String>,
     pub function_calls: Vec<FunctionCall>,
+    pub assignments: Vec<Assignment>,
 }
 
 impl ParsedSource {
@@ -282,6 +1036,7 @@ impl ParsedSource {
             ast_nodes: Vec::new(),
             imports: Vec::new(),
             function_calls: Vec::new(),
+            assignments: Vec::new(),
         }
     }
 }
@@ -377,6 +1132,8 @@ pub struct SourceLocation {
     pub line: usize,
     pub column: usize,
     pub snippet: String,
+    #[serde(default, skip_serializing_if = "EnclosingScope::is_empty")]
+    pub scope: EnclosingScope,
 }
 
 /// NIST 800-53 SC-13 Assessment Report
@@ -463,6 +1220,18 @@ pub struct AssessmentSummary {
     /// Weak key sizes detected
     pub weak_key_sizes: Vec<String>,
 
+    /// Uses below the CNSA 2.0-style post-quantum security policy, and insecure
+    /// cipher modes or IVs
+    #[serde(default)]
+    pub policy_violations: Vec<String>,
+
+    /// Standardized post-quantum algorithms in use, e.g. `X25519 + ML-KEM-768`
+    #[serde(default)]
+    pub quantum_safe_algorithms: Vec<String>,
+
+    /// Share of public-key algorithm uses already migrated to post-quantum (0-100)
+    pub migration_progress: Option<u32>,
+
     /// Compliance score (0-100)
     pub compliance_score: u32,
 
@@ -604,6 +1373,17 @@ pub struct Observation {
     pub collected: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relevant_evidence: Option<Vec<RelevantEvidence>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub props: Option<Vec<Property>>,
+}
+
+/// OSCAL name/value property; scanner-specific names carry the scanner's namespace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Property {
+    pub name: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ns: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -839,6 +1619,9 @@ pub struct CanadianAssessmentSummary {
     pub quantum_vulnerable_algorithms: Vec<String>,
     pub deprecated_algorithms: Vec<String>,
     pub weak_key_sizes: Vec<String>,
+    #[serde(default)]
+    pub quantum_safe_algorithms: Vec<String>,
+    pub migration_progress: Option<u32>,
     pub compliance_score: u32,
     pub risk_score: u32,
 
@@ -151,6 +151,12 @@ fn test_unsupported_language() {
     assert!(matches!(result, Err(AuditError::UnsupportedLanguage(_))));
 }
 
+#[test]
+fn test_undetectable_language() {
+    let result = analyze("some prose without code", "auto");
+    assert!(matches!(result, Err(AuditError::LanguageNotDetected)));
+}
+
 #[test]
 fn test_parser_unsupported_language() {
     let result = parse_file("some code", "invalid_lang");
@@ -175,6 +181,33 @@ fn test_edge_case_max_lines() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_large_file_analysis_time() {
+    // Guards against rules being run one by one on statements that match none of them
+    let block = r#"
+fn handle(request: &Request) -> Response {
+    // Look up the session and render the page
+    let session = sessions.get(&request.id).unwrap_or_default();
+    let body = format!("Hello, {}", session.user);
+    Response::new(200, body)
+}
+"#;
+    let mut source = block.repeat(14_000);
+    source.push_str("let key = Rsa::generate(1024).unwrap();\n");
+
+    let start = std::time::Instant::now();
+    let result = analyze(&source, "rust").unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(result.vulnerabilities.len(), 1);
+    assert!(
+        elapsed < std::time::Duration::from_secs(10),
+        "analyzing {} lines took {:?}",
+        result.stats.lines_scanned,
+        elapsed
+    );
+}
+
 #[test]
 fn test_unicode_in_source() {
     let unicode_source = r#"
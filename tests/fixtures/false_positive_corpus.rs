@@ -0,0 +1,440 @@
+// Per-rule false-positive corpus.
+// Code under each `// rule: <id>` marker contains identifiers or constants that
+// embed the rule's algorithm name without using it; that rule must not fire.
+
+fn corpus() {
+    // rule: rsa
+    let parsable = is_parsable(input);
+    let universal = conversation.versatile();
+
+    // rule: ecdsa
+    let specced = rebecca.specced();
+    const SALT: u64 = 0x7ecc3a91d2b4;
+
+    // rule: ecdh
+    let vecdh_len = vecdh.len();
+
+    // rule: dsa
+    let signer = EcdsaSigner::new(ECDSA_P384);
+
+    // rule: diffie-hellman
+    let adherent = ADHERENCE_LEVEL + adhesive;
+
+    // rule: sha1
+    let pasha1 = pasha1_count();
+
+    // rule: md5
+    let cmd5_args = cmd5.args();
+
+    // rule: des
+    let codes_ok = node.description.len() < MAX_DESCRIPTION_LEN;
+    let total = NODES.len();
+
+    // rule: 3des
+    let item3description = item3desc();
+
+    // rule: rc4
+    let src4 = sources[4];
+
+    // rule: aes
+    let caesar128 = shift(caesar, 128);
+    let aes_key_len = AES_BLOCK_SIZE * 2;
+
+    // rule: blowfish
+    let bf_count = bf_cache.len();
+    let BF_SIZE = 8;
+
+    // rule: python-blowfish
+    let blowfish_new = new_blowfish(key);
+    let fish = Blowfish.new;
+
+    // rule: rc2
+    let src2 = sources[2];
+    let rc2_count = rc2_builds();
+
+    // rule: python-rc2
+    let arc2_new = ARC2_new(key);
+    let rc2 = arc2.new(key);
+
+    // rule: sha224
+    let sha2240_len = 0;
+    let hash224 = digest224(input);
+
+    // rule: ecb-mode
+    let decb = specb + 1;
+    let ecb_rates = fetch_ecb_rates();
+
+    // rule: static-iv
+    let iv = cipher.generate_iv();
+    let nonce: [u8; 12] = rng.random();
+    let derived_iv = derive(ikm, salt);
+
+    // rule: sike
+    let likes = count_sikes(user);
+    let consider_it = true;
+
+    // rule: rainbow
+    let rainbow_table = precompute(hashes);
+    let rainbow_colors = palette::rainbow(7);
+
+    // rule: kyber
+    let kyber_crate_version = "0.4";
+    let cyber768 = 768;
+
+    // rule: dilithium
+    let dilithium_version = 3;
+    let dilithium_keys = 5;
+
+    // rule: sphincs-plus
+    let sphincs_docs_url = docs();
+
+    // rule: non-hybrid-ml-kem
+    let groups = vec![X25519MLKEM768, SecP256r1MLKEM768];
+    let curves = [X25519MLKEM768];
+
+    // rule: fixed-random-seed
+    random.seed();
+    let rng = new Random(seedFromConfig);
+    srand(entropy ^ getpid());
+
+    // rule: js-math-random
+    crypto.getRandomValues(buf);
+    let jitter = myMath.randomize();
+
+    // rule: python-random
+    let word = secrets.choice(words);
+    let rng = random.SystemRandom();
+
+    // rule: java-util-random
+    let rng = new SecureRandom();
+    secureRandom.nextBytes(iv);
+
+    // rule: go-math-rand
+    rand.Read(buf);
+    let n = rand.Int(rand.Reader, max);
+
+    // rule: c-rand
+    let n = arc4random_uniform(10);
+    let v = rng->rand();
+
+    // rule: php-rand
+    let n = random_int(0, 9);
+    let b = random_bytes(16);
+
+    // rule: ruby-rand
+    let n = SecureRandom.rand(10);
+    let operand = brand_name;
+
+    // rule: dotnet-random
+    RandomNumberGenerator.GetBytes(32);
+    let g = new RandomGenerator();
+
+    // rule: jose-rsa
+    let codes = ["RS2560", "GPS256"];
+    let part = "CARS256";
+
+    // rule: jose-rsa1-5
+    let version = "RSA1_50";
+
+    // rule: jose-ecdsa
+    let codes = ["RES256", "ES2560"];
+
+    // rule: jose-ecdh
+    let name = "ECDH-ESP";
+
+    // rule: jwt-none
+    let cfg = json!({"alg": "nonexistent", "verify_signature": True});
+    let algorithms = ["none_of_these"];
+
+    // rule: jwt-weak-hmac-secret
+    let token = jwt.sign(payload, process.env.JWT_SECRET);
+    let token = jwt.sign(payload, "a-random-secret-of-at-least-32-bytes");
+
+    // rule: openssl-rsa
+    let my_RSA_generate_key_ex_calls = 0;
+    let mock_EVP_RSA_gen = stub();
+
+    // rule: openssl-ec
+    let wrapped_EC_KEY_generate_key_count = 2;
+
+    // rule: openssl-dh
+    let legacy_DH_generate_key_ok = false;
+
+    // rule: openssl-dsa
+    let fake_DSA_generate_key_seen = true;
+
+    // rule: openssl-des
+    let skip_EVP_des_cbc_tests = true;
+
+    // rule: openssl-3des
+    let no_DES_ede3_cbc_encrypt = true;
+
+    // rule: openssl-cli-rsa
+    openssl rsa -in key.pem -pubout -out pub.pem
+
+    // rule: openssl-cli-ec
+    openssl ec -in ec.pem -text -noout
+
+    // rule: openssl-cli-eddsa
+    openssl pkey -in ed25519.pem -pubout
+
+    // rule: openssl-cli-x25519
+    openssl genpkey -algorithm ML-KEM-768 -out x25519_replacement.pem
+
+    // rule: openssl-cli-dsa
+    openssl dsa -in dsa.pem -pubout
+
+    // rule: openssl-cli-dh
+    openssl x509 -in dh_server.pem -noout -dates
+
+    // rule: openssl-cli-des
+    openssl enc -aes-256-cbc -pbkdf2 -in design.txt -out design.enc
+
+    // rule: openssl-cli-3des
+    openssl pkcs12 -export -keypbe AES-256-CBC -in des3_notes.pem -out notes.p12
+
+    // rule: openssl-cli-rc4
+    openssl enc -chacha20 -in rc4_migration.txt
+
+    // rule: openssl-cli-rc2
+    openssl pkcs12 -export -in rc2.pem -inkey key.pem -out bundle.p12
+
+    // rule: openssl-cli-blowfish
+    openssl enc -aes-256-cbc -pbkdf2 -in bf_backup.tar
+
+    // rule: openssl-cli-aes
+    openssl enc -aes-256-ctr -pbkdf2 -in aes128_notes.txt
+
+    // rule: openssl-cli-ecb
+    openssl enc -aes-256-cbc -pbkdf2 -in ecb_demo.bin
+
+    // rule: openssl-cli-static-iv
+    openssl enc -aes-256-cbc -K "$KEY" -iv "$IV" -in data.bin
+
+    // rule: openssl-cli-md5
+    openssl dgst -sha256 md5sums.txt
+
+    // rule: openssl-cli-sha1
+    openssl dgst -sha256 -sign key.pem sha1_manifest.txt
+
+    // rule: openssl-cli-sha224
+    openssl dgst -sha384 sha224_vectors.txt
+
+    // rule: ssh-keygen-rsa
+    ssh-keygen -y -f id_rsa > id_rsa.pub
+
+    // rule: ssh-keygen-ecdsa
+    ssh-keygen -l -f id_ecdsa.pub
+
+    // rule: ssh-keygen-ed25519
+    ssh-keygen -y -f id_ed25519 > id_ed25519.pub
+
+    // rule: ssh-keygen-dsa
+    ssh-keygen -R dsa.example.com
+
+    // rule: libgcrypt-rsa
+    let GCRY_PK_RSA_MISSING = 1;
+
+    // rule: libgcrypt-ecc
+    let GCRY_PK_ECC_DISABLED = 1;
+
+    // rule: mbedtls-rsa
+    let has_mbedtls_rsa_c = cfg.get("have_mbedtls_rsa_c");
+
+    // rule: mbedtls-ecdsa
+    let has_mbedtls_ecdsa_c = cfg.get("have_mbedtls_ecdsa_c");
+
+    // rule: mbedtls-ecdh
+    let use_mbedtls_ecdh_c = false;
+
+    // rule: mbedtls-dhm
+    let use_mbedtls_dhm_c = false;
+
+    // rule: mbedtls-des
+    let no_mbedtls_des_c = true;
+
+    // rule: mbedtls-3des
+    let no_mbedtls_des3_c = true;
+
+    // rule: dotnet-rsa
+    let mockRSACng = stub();
+    let noRSACryptoServiceProvider = true;
+
+    // rule: dotnet-dsa
+    let useDSAOpenSsl = false;
+
+    // rule: dotnet-ecdsa
+    let skipECDsaCng = true;
+
+    // rule: dotnet-ecdh
+    let noECDiffieHellmanCng = true;
+
+    // rule: dotnet-md5
+    let isMD5CryptoServiceProvider = false;
+    let xHMACMD5 = 1;
+
+    // rule: dotnet-sha1
+    let hasSHA1Managed = false;
+
+    // rule: dotnet-des
+    let oldDESCryptoServiceProvider = 0;
+
+    // rule: dotnet-3des
+    let stubTripleDESCng = 0;
+
+    // rule: dotnet-x509-rsa
+    let canGetRSAPublicKey = false;
+
+    // rule: dotnet-x509-ecdsa
+    let canGetECDsaPrivateKey = false;
+
+    // rule: dotnet-x509-dsa
+    let canGetDSAPublicKey = false;
+
+    // rule: jca-rsa
+    let kpg = KeyPairGenerator.getInstance("RSASSA");
+    let sig = Signature.getInstance("Ed25519");
+
+    // rule: jca-ecdsa
+    let kf = KeyFactory.getInstance("ECDSA_LEGACY");
+
+    // rule: jca-ecdh
+    let ka = KeyAgreement.getInstance("ML-KEM");
+
+    // rule: jca-dsa
+    let sig = Signature.getInstance("ML-DSA");
+
+    // rule: jca-dh
+    let kpg = KeyPairGenerator.getInstance("DHKEM");
+
+    // rule: jca-des
+    let cipher = Cipher.getInstance("DESX_UNSUPPORTED");
+
+    // rule: jca-3des
+    let cipher = Cipher.getInstance("AES/CBC/PKCS5Padding");
+
+    // rule: jca-rc4
+    let cipher = Cipher.getInstance("RC45");
+
+    // rule: jca-md5
+    let md = MessageDigest.getInstance("MD5X");
+
+    // rule: jca-sha1
+    let md = MessageDigest.getInstance("SHA-128");
+
+    // rule: php-openssl-rsa
+    let keys = my_openssl_pkey_new_wrapper();
+
+    // rule: php-openssl-ec
+    let kind = OPENSSL_KEYTYPE_EC_NAME;
+
+    // rule: php-openssl-dsa
+    let kind = OPENSSL_KEYTYPE_DSA_NAME;
+
+    // rule: php-openssl-dh
+    let kind = OPENSSL_KEYTYPE_DH_NAME;
+
+    // rule: php-md5
+    let column = $row->md5_checksum;
+    let stored = legacy_md5($x);
+
+    // rule: php-sha1
+    let stored = is_sha1($x);
+
+    // rule: php-mcrypt-des
+    let mode = MCRYPT_DESX_UNSUPPORTED;
+
+    // rule: php-mcrypt-3des
+    let mode = MCRYPT_3DES_NAME;
+
+    // rule: php-mcrypt-rc4
+    let mode = MCRYPT_ARCFOUR_NAME;
+
+    // rule: php-openssl-des
+    let out = openssl_encrypt($data, 'aes-256-gcm', $key, 0, $iv, $tag, 'des');
+
+    // rule: php-openssl-3des
+    let out = openssl_encrypt($data, 'aes-128-cbc', $key);
+
+    // rule: ruby-rsa
+    let key = OpenSSL::PKey::RSAKeyLoader.new(pem);
+
+    // rule: ruby-dsa
+    let key = OpenSSL::PKey::DSAKeyLoader.new(pem);
+
+    // rule: ruby-ec
+    let key = OpenSSL::PKey::ECKeyLoader.new(pem);
+
+    // rule: ruby-dh
+    let params = OpenSSL::PKey::DHParams.new(pem);
+
+    // rule: ruby-md5
+    let digest = Digest::MD5Stub;
+
+    // rule: ruby-sha1
+    let digest = Digest::SHA1Stub;
+
+    // rule: ruby-des
+    let cipher = OpenSSL::Cipher.new('desx-cbc');
+
+    // rule: ruby-3des
+    let cipher = OpenSSL::Cipher.new('aes-256-gcm');
+
+    // rule: seckey-rsa
+    let kind = kSecAttrKeyTypeRSAOAEP;
+
+    // rule: seckey-ec
+    let kind = kSecAttrKeyTypeECSECPrimeRandomPKA;
+
+    // rule: commoncrypto-md5
+    let n = CC_MD5_BLOCK_BYTES;
+
+    // rule: commoncrypto-sha1
+    let digest = CC_SHA256(data, len, out);
+    let mac = kCCHmacAlgSHA256;
+
+    // rule: commoncrypto-des
+    let alg = kCCAlgorithmAES128;
+
+    // rule: commoncrypto-3des
+    let alg = kCCAlgorithmAES;
+
+    // rule: commoncrypto-rc4
+    let alg = kCCAlgorithmRC2;
+
+    // rule: cryptokit-ecdsa
+    let key = MLDSA65.PrivateKey();
+    let suite = HPKE.Ciphersuite.P256_SHA256_AES_GCM_256;
+
+    // rule: cryptokit-ecdh
+    let kem = MLKEM768.PrivateKey();
+
+    // rule: cryptokit-md5
+    let digest = SHA256.hash(data: data);
+
+    // rule: cryptokit-sha1
+    let digest = SHA512.hash(data: data);
+
+    // rule: secp256k1
+    let curve = Curve::Secp256r1;
+    let nsecp256k1_count = registry.len();
+
+    // rule: solidity-ecrecover
+    address signer = recoverSigner(digest, proof);
+
+    // rule: openzeppelin-ecdsa
+    bytes32 digest = MessageHashUtils.toEthSignedMessageHash(hash);
+
+    // rule: eth-address-derivation
+    address owner = address(uint160(uint256(slot)));
+    let book = computeAddressBook(entries);
+
+    // rule: ethers-signing
+    const walletAddress = await provider.getAddress(walletName);
+
+    // rule: web3py-signing
+    balance = w3.eth.get_balance(account)
+
+    // rule: go-secp256k1
+    sum := crypto.Keccak256(data)
+}
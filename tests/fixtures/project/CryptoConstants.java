@@ -0,0 +1,8 @@
+package com.example.security;
+
+public final class CryptoConstants {
+    public static final String SIGNATURE_ALGORITHM = "RSA";
+    public static final int KEY_SIZE = 1024;
+
+    private CryptoConstants() {}
+}
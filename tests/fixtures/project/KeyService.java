@@ -0,0 +1,11 @@
+package com.example.security;
+
+import java.security.KeyPairGenerator;
+
+public class KeyService {
+    public KeyPair generate() throws Exception {
+        KeyPairGenerator kpg = KeyPairGenerator.getInstance(CryptoConstants.SIGNATURE_ALGORITHM);
+        kpg.initialize(CryptoConstants.KEY_SIZE);
+        return kpg.generateKeyPair();
+    }
+}
@@ -0,0 +1,6 @@
+package config
+
+const (
+	DefaultAlg = "RSA"
+	KeyBits    = 1024
+)
@@ -0,0 +1,12 @@
+package main
+
+import (
+	"crypto/rand"
+	"crypto/rsa"
+
+	"example.com/app/config"
+)
+
+func newKey() (*rsa.PrivateKey, error) {
+	return rsa.GenerateKey(rand.Reader, config.KeyBits)
+}
@@ -0,0 +1,22 @@
+#include <openssl/rsa.h>
+#include <openssl/ec.h>
+#include <openssl/des.h>
+#include <gcrypt.h>
+#include <mbedtls/rsa.h>
+
+#define LEGACY_RSA_BITS 1024
+
+int make_keys(void) {
+    RSA *rsa = RSA_new();
+    RSA_generate_key_ex(rsa, LEGACY_RSA_BITS, e, NULL);
+
+    EC_KEY *ec = EC_KEY_new_by_curve_name(NID_X9_62_prime256v1);
+
+    DES_ecb_encrypt(&input, &output, &schedule, DES_ENCRYPT);
+
+    gcry_sexp_build(&params, NULL, "(genkey (rsa (nbits 4:2048)))");
+
+    mbedtls_rsa_gen_key(&ctx, mbedtls_ctr_drbg_random, &drbg,
+                        4096, 65537);
+    return 0;
+}
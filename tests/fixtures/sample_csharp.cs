@@ -0,0 +1,33 @@
+using System;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+
+namespace Legacy.Crypto
+{
+    public static class KeyMaterial
+    {
+        public const int RsaKeySize = 1024;
+
+        public static RSA LegacyRsa() => new RSACryptoServiceProvider(RsaKeySize);
+
+        public static RSA ModernRsa() => RSA.Create(2048);
+
+        public static ECDsa Signer() => ECDsa.Create(ECCurve.NamedCurves.nistP256);
+
+        public static byte[] Fingerprint(byte[] data)
+        {
+            using var md5 = MD5.Create();
+            return md5.ComputeHash(data);
+        }
+
+        public static byte[] LegacyDigest(byte[] data)
+        {
+            using var sha = new SHA1Managed();
+            return sha.ComputeHash(data);
+        }
+
+        public static SymmetricAlgorithm Cipher() => new TripleDESCryptoServiceProvider();
+
+        public static RSA CertificateKey(X509Certificate2 cert) => cert.GetRSAPublicKey();
+    }
+}
@@ -0,0 +1,19 @@
+package com.example.crypto
+
+import java.security.KeyPairGenerator
+import java.security.MessageDigest
+import javax.crypto.Cipher
+
+object CryptoConfig {
+    const val LEGACY_CIPHER = "DES/ECB/PKCS5Padding"
+}
+
+class LegacyCrypto {
+    fun keyPair() = KeyPairGenerator.getInstance("RSA").apply { initialize(1024) }.generateKeyPair()
+
+    fun cipher(): Cipher = Cipher.getInstance(CryptoConfig.LEGACY_CIPHER)
+
+    fun digest(data: ByteArray): ByteArray = MessageDigest.getInstance("SHA-1").digest(data)
+
+    fun modern(): Cipher = Cipher.getInstance("AES/GCM/NoPadding")
+}
@@ -0,0 +1,31 @@
+#import <Foundation/Foundation.h>
+#import <CommonCrypto/CommonCrypto.h>
+#import <Security/Security.h>
+
+static const int kLegacyKeyBits = 1024;
+
+@implementation LegacyCrypto
+
+- (SecKeyRef)makeRSAKey {
+    NSDictionary *attributes = @{
+        (id)kSecAttrKeyType: (id)kSecAttrKeyTypeRSA,
+        (id)kSecAttrKeySizeInBits: @(kLegacyKeyBits),
+    };
+    return SecKeyCreateRandomKey((__bridge CFDictionaryRef)attributes, NULL);
+}
+
+- (NSData *)sha1:(NSData *)data {
+    unsigned char digest[CC_SHA1_DIGEST_LENGTH];
+    CC_SHA1(data.bytes, (CC_LONG)data.length, digest);
+    return [NSData dataWithBytes:digest length:CC_SHA1_DIGEST_LENGTH];
+}
+
+- (NSData *)encrypt:(NSData *)data key:(NSData *)key {
+    size_t moved = 0;
+    NSMutableData *out = [NSMutableData dataWithLength:data.length + kCCBlockSizeDES];
+    CCCrypt(kCCEncrypt, kCCAlgorithmDES, kCCOptionPKCS7Padding, key.bytes, kCCKeySizeDES,
+            NULL, data.bytes, data.length, out.mutableBytes, out.length, &moved);
+    return out;
+}
+
+@end
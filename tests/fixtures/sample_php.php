@@ -0,0 +1,41 @@
+<?php
+
+namespace App\Legacy;
+
+use phpseclib3\Crypt\RSA;
+
+define('KEY_BITS', 1024);
+
+class TokenService
+{
+    const CIPHER = 'des-ede3-cbc';
+
+    public function keys()
+    {
+        return openssl_pkey_new([
+            'private_key_bits' => 1024,
+            'private_key_type' => OPENSSL_KEYTYPE_RSA,
+        ]);
+    }
+
+    public function ecKeys()
+    {
+        return openssl_pkey_new(['curve_name' => 'prime256v1', 'private_key_type' => OPENSSL_KEYTYPE_EC]);
+    }
+
+    public function fingerprint($data)
+    {
+        return md5($data) . sha1($data);
+    }
+
+    public function encrypt($data, $key)
+    {
+        $iv = mcrypt_create_iv(8);
+        return mcrypt_encrypt(MCRYPT_3DES, $key, $data, MCRYPT_MODE_CBC, $iv);
+    }
+
+    public function seal($data, $key, $iv)
+    {
+        return openssl_encrypt($data, 'des-ede3-cbc', $key, 0, $iv);
+    }
+}
@@ -0,0 +1,28 @@
+require 'openssl'
+require 'digest'
+
+module Legacy
+  KEY_BITS = 1024
+
+  class Signer
+    def key
+      OpenSSL::PKey::RSA.new(KEY_BITS)
+    end
+
+    def ec_key
+      OpenSSL::PKey::EC.generate('prime256v1')
+    end
+
+    def fingerprint(data)
+      Digest::MD5.hexdigest(data)
+    end
+
+    def legacy_fingerprint(data)
+      OpenSSL::Digest::SHA1.new.hexdigest(data)
+    end
+
+    def cipher
+      OpenSSL::Cipher.new('des-ede3-cbc')
+    end
+  end
+end
@@ -0,0 +1,17 @@
+package com.example.crypto
+
+import java.security.{KeyPairGenerator, Signature}
+import javax.crypto.Cipher
+
+object LegacyCrypto {
+  val Transformation = "DESede/CBC/PKCS5Padding"
+
+  def cipher(): Cipher = Cipher.getInstance(Transformation)
+
+  def signer(): Signature = Signature.getInstance("SHA1withRSA")
+
+  def ecKeys() = {
+    val kpg = KeyPairGenerator.getInstance("EC")
+    kpg.generateKeyPair()
+  }
+}
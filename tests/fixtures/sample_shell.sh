@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# Provision development certificates and deploy keys
+set -euo pipefail
+
+KEY_BITS=1024
+OUT_DIR="./certs"
+
+mkdir -p "$OUT_DIR"
+
+# Legacy CA key for the test environment
+openssl genrsa -out "$OUT_DIR/ca.key" $KEY_BITS
+
+openssl req -new -x509 -days 365 \
+    -newkey rsa:2048 -nodes \
+    -keyout "$OUT_DIR/server.key" -out "$OUT_DIR/server.crt" \
+    -subj "/CN=localhost"
+
+openssl enc -des3 -salt -in secrets.txt -out secrets.enc -pass env:SECRET
+openssl dgst -sha1 "$OUT_DIR/server.crt"
+
+ssh-keygen -t dsa -f "$HOME/.ssh/deploy_key" -N ""
+
+# Mode flags do not generate keys
+ssh-keygen -l -f "$HOME/.ssh/deploy_key.pub"
+echo "run: openssl genrsa 512"
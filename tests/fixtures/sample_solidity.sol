@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import {ECDSA} from "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+
+/// @notice Relays meta-transactions signed by the account owner
+contract Forwarder {
+    using ECDSA for bytes32;
+
+    mapping(address => uint256) public nonces;
+
+    function verify(address from, bytes32 digest, uint8 v, bytes32 r, bytes32 s) public view returns (bool) {
+        // ecrecover returns the zero address on malformed signatures
+        address signer = ecrecover(digest, v, r, s);
+        return signer == from && signer != address(0);
+    }
+
+    function verifyPacked(address from, bytes32 digest, bytes calldata signature) external pure returns (bool) {
+        return ECDSA.recover(digest, signature) == from;
+    }
+
+    function ownerOf(bytes memory publicKey) public pure returns (address) {
+        return address(uint160(uint256(keccak256(publicKey))));
+    }
+}
@@ -0,0 +1,41 @@
+import CryptoKit
+import CommonCrypto
+import Security
+
+enum KeyConfig {
+    static let rsaBits = 1024
+}
+
+final class LegacyCrypto {
+    func makeRSAKey() throws -> SecKey? {
+        let attributes: [String: Any] = [
+            kSecAttrKeyType as String: kSecAttrKeyTypeRSA,
+            kSecAttrKeySizeInBits as String: KeyConfig.rsaBits,
+        ]
+        return SecKeyCreateRandomKey(attributes as CFDictionary, nil)
+    }
+
+    func makeECKey() -> SecKey? {
+        let attributes: [String: Any] = [kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom, kSecAttrKeySizeInBits as String: 256]
+        return SecKeyCreateRandomKey(attributes as CFDictionary, nil)
+    }
+
+    func sign(_ data: Data) throws -> Data {
+        let key = P384.Signing.PrivateKey()
+        return try key.signature(for: data).derRepresentation
+    }
+
+    func agree() -> Curve25519.KeyAgreement.PrivateKey {
+        Curve25519.KeyAgreement.PrivateKey()
+    }
+
+    func fingerprint(_ data: Data) -> [UInt8] {
+        var digest = [UInt8](repeating: 0, count: Int(CC_MD5_DIGEST_LENGTH))
+        _ = data.withUnsafeBytes { CC_MD5($0.baseAddress, CC_LONG(data.count), &digest) }
+        return digest
+    }
+
+    func legacyDigest(_ data: Data) -> Insecure.SHA1.Digest {
+        Insecure.SHA1.hash(data: data)
+    }
+}
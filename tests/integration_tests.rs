@@ -1,5 +1,11 @@
 // Integration Tests for Quantum-Safe Crypto Auditor
-use pqc_scanner::{CryptoType, Severity, analyze};
+use pqc_scanner::{
+    CryptoType, DetectionMethod, Language, PqcFamily, ProjectIndex, Scanner, ScannerConfig,
+    SecurityClassification, Severity, analyze, detector, generate_itsg33_report,
+    generate_oscal_json, generate_sc13_report,
+};
+use std::fs;
+use std::path::Path;
 
 #[test]
 fn test_end_to_end_rust_audit() {
@@ -137,3 +143,1247 @@ fn test_language_support() {
         assert!(result.is_ok(), "Failed for language: {}", lang);
     }
 }
+
+#[test]
+fn test_false_positive_fixture() {
+    let source = include_str!("fixtures/false_positives.rs");
+
+    let result = analyze(source, "rust").unwrap();
+    assert!(
+        result.vulnerabilities.is_empty(),
+        "comments and log strings should not be reported: {:?}",
+        result.vulnerabilities
+    );
+}
+
+#[test]
+fn test_false_positive_corpus_per_rule() {
+    let source = include_str!("fixtures/false_positive_corpus.rs");
+
+    // Rule whose section each line belongs to
+    let mut section = None;
+    let sections: Vec<Option<&str>> = source
+        .lines()
+        .map(|line| {
+            if let Some(id) = line.trim().strip_prefix("// rule: ") {
+                section = Some(id);
+            }
+            section
+        })
+        .collect();
+
+    for pattern in detector::builtin_patterns() {
+        assert!(
+            sections.contains(&Some(pattern.id.as_str())),
+            "false-positive corpus has no section for rule '{}'",
+            pattern.id
+        );
+    }
+
+    // Language-specific rules only run on their own languages
+    let mut languages = vec![Language::Rust];
+    for pattern in detector::builtin_patterns() {
+        for &language in &pattern.languages {
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+    }
+
+    // Other languages only check the rules written for them, since the corpus
+    // markers are not comments in every language
+    let checked_on = |rule_id: &str, language: Language| {
+        language == Language::Rust
+            || detector::builtin_patterns()
+                .iter()
+                .any(|p| p.id == rule_id && p.languages.contains(&language))
+    };
+
+    for language in languages {
+        let result = analyze(source, &language.to_string()).unwrap();
+        for vuln in &result.vulnerabilities {
+            if !checked_on(&vuln.rule_id, language) {
+                continue;
+            }
+            assert_ne!(
+                sections[vuln.line - 1],
+                Some(vuln.rule_id.as_str()),
+                "rule '{}' fired on its false-positive corpus as {}: {}",
+                vuln.rule_id,
+                language,
+                vuln.context
+            );
+        }
+    }
+}
+
+#[test]
+fn test_scanner_loads_user_rule_pack_file() {
+    let config = ScannerConfig {
+        rule_pack_paths: vec!["tests/fixtures/corp_rules.json".into()],
+        ..ScannerConfig::default()
+    };
+    let scanner = Scanner::new(&config).unwrap();
+
+    let source = "sig = corp_crypto.legacy_sign(key, payload)\ndigest = hashlib.md5(payload)\n";
+    let result = scanner.analyze(source, "python").unwrap();
+
+    let corp = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.rule_id == "corp-legacy-sign")
+        .expect("custom rule should fire");
+    assert_eq!(corp.crypto_type, CryptoType::Rsa);
+    assert_eq!(corp.cwe.as_deref(), Some("CWE-327"));
+    assert!(
+        result
+            .vulnerabilities
+            .iter()
+            .any(|v| v.crypto_type == CryptoType::Md5)
+    );
+}
+
+#[test]
+fn test_scanner_rejects_missing_rule_pack_file() {
+    let config = ScannerConfig {
+        rule_pack_paths: vec!["tests/fixtures/does_not_exist.json".into()],
+        ..ScannerConfig::default()
+    };
+    assert!(Scanner::new(&config).is_err());
+}
+
+fn project_index(dir: &str) -> ProjectIndex {
+    let mut index = ProjectIndex::default();
+    for path in [
+        "CryptoConstants.java",
+        "KeyService.java",
+        "config/config.go",
+        "main.go",
+    ] {
+        let path = format!("{}/{}", dir, path);
+        let source = fs::read_to_string(&path).unwrap();
+        let language = if path.ends_with(".java") {
+            Language::Java
+        } else {
+            Language::Go
+        };
+        index.add_file(&path, &source, language);
+    }
+    index
+}
+
+#[test]
+fn test_project_index_resolves_constants_across_files() {
+    let dir = "tests/fixtures/project";
+    let scanner = Scanner::default().with_project_index(project_index(dir));
+
+    // Java: the algorithm comes from CryptoConstants.SIGNATURE_ALGORITHM
+    let source = fs::read_to_string(format!("{}/KeyService.java", dir)).unwrap();
+    let result = scanner.analyze(&source, "java").unwrap();
+    let rsa = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.crypto_type == CryptoType::Rsa)
+        .expect("algorithm should resolve through the project index");
+    let resolution = rsa.explanation.algorithm_resolution.as_ref().unwrap();
+    assert_eq!(resolution.symbol, "CryptoConstants.SIGNATURE_ALGORITHM");
+    assert_eq!(
+        resolution.chain[0].file.as_deref(),
+        Some("tests/fixtures/project/CryptoConstants.java")
+    );
+
+    // ...and the key size from CryptoConstants.KEY_SIZE passed to initialize()
+    assert_eq!(rsa.key_size, Some(1024));
+    assert_eq!(rsa.severity, Severity::Critical);
+    let resolution = rsa.explanation.key_size_resolution.as_ref().unwrap();
+    assert_eq!(resolution.symbol, "CryptoConstants.KEY_SIZE");
+
+    // Go: the key size comes from config.KeyBits
+    let source = fs::read_to_string(format!("{}/main.go", dir)).unwrap();
+    let result = scanner.analyze(&source, "go").unwrap();
+    let keygen = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.line == 11)
+        .unwrap();
+    assert_eq!(keygen.key_size, Some(1024));
+    assert_eq!(keygen.severity, Severity::Critical);
+
+    // Without the index neither constant resolves
+    let result = analyze(&source, "go").unwrap();
+    assert!(result.vulnerabilities.iter().all(|v| v.key_size.is_none()));
+}
+
+#[test]
+fn test_c_crypto_library_rules() {
+    let source = include_str!("fixtures/sample_c.c");
+    let result = analyze(source, "c").unwrap();
+
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    // Key size from a #define passed as the call argument
+    let rsa = finding("openssl-rsa");
+    assert_eq!(rsa.line, 11);
+    assert_eq!(rsa.key_size, Some(1024));
+    assert_eq!(rsa.severity, Severity::Critical);
+
+    // The curve NID gives the key size and the generic ECDSA match is folded in
+    let ec = finding("openssl-ec");
+    assert_eq!(ec.key_size, Some(256));
+    assert!(ec.suppressed.iter().any(|s| s.rule_id == "ecdsa"));
+    assert!(
+        !result
+            .vulnerabilities
+            .iter()
+            .any(|v| v.line == 13 && v.rule_id == "ecdsa")
+    );
+
+    assert_eq!(finding("openssl-des").crypto_type, CryptoType::Des);
+    assert_eq!(finding("libgcrypt-rsa").key_size, Some(2048));
+
+    // Arguments spread over two lines
+    let mbedtls = finding("mbedtls-rsa");
+    assert_eq!(mbedtls.line, 19);
+    assert_eq!(mbedtls.key_size, Some(4096));
+}
+
+#[test]
+fn test_library_call_is_one_finding() {
+    let source = r#"#include <openssl/rsa.h>
+#include <openssl/des.h>
+#include <mbedtls/rsa.h>
+
+void legacy(void) {
+    RSA_generate_key_ex(rsa, 1024, e, NULL);
+    mbedtls_rsa_gen_key(&rsa, mbedtls_ctr_drbg_random, &drbg, 2048, 65537);
+    DES_ecb_encrypt(&input, &output, &schedule, DES_ENCRYPT);
+}
+"#;
+    let result = analyze(source, "c").unwrap();
+    let found: Vec<(usize, &str, Option<u32>)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.rule_id.as_str(), v.key_size))
+        .collect();
+    // Header names, argument names and flags are part of the call's finding
+    assert_eq!(
+        found,
+        vec![
+            (6, "openssl-rsa", Some(1024)),
+            (7, "mbedtls-rsa", Some(2048)),
+            (8, "openssl-des", None),
+        ]
+    );
+    assert!(
+        result.vulnerabilities[0]
+            .suppressed
+            .iter()
+            .any(|s| s.rule_id == "rsa")
+    );
+}
+
+#[test]
+fn test_csharp_dotnet_rules() {
+    let source = include_str!("fixtures/sample_csharp.cs");
+    let result = analyze(source, "csharp").unwrap();
+
+    let finding = |rule_id: &str, line: usize| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id && v.line == line)
+            .unwrap_or_else(|| panic!("expected a '{}' finding on line {}", rule_id, line))
+    };
+
+    // Key size from a `const` passed to the constructor
+    let legacy = finding("dotnet-rsa", 11);
+    assert_eq!(legacy.key_size, Some(1024));
+    assert_eq!(legacy.severity, Severity::Critical);
+    assert!(legacy.recommendation.contains("MLDsa"));
+
+    assert_eq!(finding("dotnet-rsa", 13).key_size, Some(2048));
+
+    // The curve name gives the key size and the generic matches are folded in
+    let ecdsa = finding("dotnet-ecdsa", 15);
+    assert_eq!(ecdsa.key_size, Some(256));
+    assert!(ecdsa.suppressed.iter().any(|s| s.rule_id == "ecdsa"));
+
+    let md5 = finding("dotnet-md5", 19);
+    assert!(md5.recommendation.contains("SHA256.HashData"));
+    assert!(md5.suppressed.iter().any(|s| s.rule_id == "md5"));
+
+    finding("dotnet-sha1", 25);
+    assert_eq!(
+        finding("dotnet-3des", 29).crypto_type,
+        CryptoType::TripleDes
+    );
+    finding("dotnet-x509-rsa", 31);
+}
+
+#[test]
+fn test_jca_rules_shared_across_jvm_languages() {
+    let finding = |result: &pqc_scanner::AuditResult, rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+            .clone()
+    };
+
+    let kotlin = analyze(include_str!("fixtures/sample_kotlin.kt"), "kotlin").unwrap();
+    let rsa = finding(&kotlin, "jca-rsa");
+    assert_eq!(rsa.line, 12);
+    assert_eq!(rsa.key_size, Some(1024));
+
+    // The transformation comes through an object constant and is split up
+    let des = finding(&kotlin, "jca-des");
+    assert_eq!(des.line, 14);
+    let transformation = des.explanation.transformation.unwrap();
+    assert_eq!(transformation.algorithm, "DES");
+    assert_eq!(transformation.mode.as_deref(), Some("ECB"));
+    assert_eq!(transformation.padding.as_deref(), Some("PKCS5Padding"));
+
+    assert_eq!(finding(&kotlin, "jca-sha1").line, 16);
+    assert!(!kotlin.vulnerabilities.iter().any(|v| v.line == 18));
+
+    let scala = analyze(include_str!("fixtures/sample_scala.scala"), "scala").unwrap();
+    let tdes = finding(&scala, "jca-3des");
+    assert_eq!(tdes.crypto_type, CryptoType::TripleDes);
+    assert_eq!(
+        tdes.explanation.transformation.unwrap().mode.as_deref(),
+        Some("CBC")
+    );
+
+    // `SHA1withRSA` is both a SHA-1 digest and an RSA signature
+    let sha1 = finding(&scala, "jca-sha1");
+    let rsa = finding(&scala, "jca-rsa");
+    assert_eq!((sha1.line, rsa.line), (11, 11));
+    assert_eq!(finding(&scala, "jca-ecdsa").line, 14);
+
+    // The same rules fire on Java
+    let java = analyze(
+        r#"Cipher c = Cipher.getInstance("DES/CBC/NoPadding");"#,
+        "java",
+    )
+    .unwrap();
+    assert_eq!(java.vulnerabilities[0].rule_id, "jca-des");
+}
+
+#[test]
+fn test_php_crypto_rules() {
+    let result = analyze(include_str!("fixtures/sample_php.php"), "php").unwrap();
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    // Key size from the options array spread over several lines
+    let rsa = finding("php-openssl-rsa");
+    assert_eq!(rsa.line, 15);
+    assert_eq!(rsa.key_size, Some(1024));
+    assert_eq!(rsa.severity, Severity::Critical);
+
+    // An explicit key type replaces the default RSA finding
+    let ec = finding("php-openssl-ec");
+    assert_eq!(ec.key_size, Some(256));
+    assert!(ec.suppressed.iter().any(|s| s.rule_id == "php-openssl-rsa"));
+
+    assert_eq!(finding("php-md5").line, 28);
+    assert_eq!(finding("php-sha1").line, 28);
+    assert_eq!(finding("php-mcrypt-3des").line, 34);
+    assert_eq!(finding("php-openssl-3des").line, 39);
+}
+
+#[test]
+fn test_ruby_crypto_rules() {
+    let result = analyze(include_str!("fixtures/sample_ruby.rb"), "ruby").unwrap();
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    // Key size resolved from a module constant
+    let rsa = finding("ruby-rsa");
+    assert_eq!(rsa.line, 9);
+    assert_eq!(rsa.key_size, Some(1024));
+    assert!(rsa.explanation.key_size_resolution.is_some());
+
+    let ec = finding("ruby-ec");
+    assert_eq!(ec.key_size, Some(256));
+    assert!(!result.vulnerabilities.iter().any(|v| v.rule_id == "ecdsa"));
+
+    assert_eq!(finding("ruby-md5").line, 17);
+    assert_eq!(finding("ruby-sha1").line, 21);
+    assert_eq!(finding("ruby-3des").crypto_type, CryptoType::TripleDes);
+}
+
+#[test]
+fn test_swift_apple_crypto_rules() {
+    let result = analyze(include_str!("fixtures/sample_swift.swift"), "swift").unwrap();
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    // Key size from the attributes dictionary, through a static constant
+    let rsa = finding("seckey-rsa");
+    assert_eq!(rsa.line, 12);
+    assert_eq!(rsa.key_size, Some(1024));
+    assert_eq!(rsa.severity, Severity::Critical);
+
+    let ec = finding("seckey-ec");
+    assert_eq!(ec.key_size, Some(256));
+    assert!(ec.message.contains("NIST P-256"));
+
+    let signing = finding("cryptokit-ecdsa");
+    assert_eq!(signing.crypto_type, CryptoType::Ecdsa);
+    assert_eq!(signing.key_size, Some(384));
+    assert!(signing.message.contains("NIST P-384"));
+
+    assert_eq!(finding("cryptokit-ecdh").crypto_type, CryptoType::Ecdh);
+    assert_eq!(finding("commoncrypto-md5").line, 34);
+    assert_eq!(finding("cryptokit-sha1").crypto_type, CryptoType::Sha1);
+}
+
+#[test]
+fn test_objc_apple_crypto_rules() {
+    let result = analyze(include_str!("fixtures/sample_objc.m"), "objc").unwrap();
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    // `@{...}` dictionary literals are joined into one statement
+    let rsa = finding("seckey-rsa");
+    assert_eq!(rsa.line, 11);
+    assert_eq!(rsa.key_size, Some(1024));
+    assert!(rsa.explanation.key_size_resolution.is_some());
+
+    assert_eq!(finding("commoncrypto-sha1").line, 19);
+    assert_eq!(finding("commoncrypto-des").line, 26);
+}
+
+#[test]
+fn test_shell_command_invocations() {
+    let result = analyze(include_str!("fixtures/sample_shell.sh"), "shell").unwrap();
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    // Key size through a shell variable
+    let genrsa = finding("openssl-cli-rsa");
+    assert_eq!(genrsa.line, 11);
+    assert_eq!(genrsa.crypto_type, CryptoType::Rsa);
+    assert_eq!(genrsa.key_size, Some(1024));
+    assert_eq!(genrsa.severity, Severity::Critical);
+    assert_eq!(
+        genrsa.explanation.command.as_deref(),
+        Some("openssl genrsa")
+    );
+    assert!(genrsa.explanation.key_size_resolution.is_some());
+
+    // `\` continuations are joined into one command
+    let req = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.explanation.command.as_deref() == Some("openssl req"))
+        .expect("expected an 'openssl req' finding");
+    assert_eq!(req.rule_id, "openssl-cli-rsa");
+    assert_eq!(req.line, 14);
+    assert_eq!(req.key_size, Some(2048));
+
+    assert_eq!(finding("openssl-cli-3des").line, 18);
+    assert_eq!(finding("openssl-cli-sha1").line, 19);
+    let keygen = finding("ssh-keygen-dsa");
+    assert_eq!(keygen.crypto_type, CryptoType::Dsa);
+    assert!(keygen.recommendation.contains("mlkem768x25519-sha256"));
+
+    // Fingerprinting a key and echoing a command are not key generation
+    assert!(result.vulnerabilities.iter().all(|v| v.line < 23));
+}
+
+#[test]
+fn test_dockerfile_and_makefile_invocations() {
+    let result = analyze(include_str!("fixtures/Dockerfile.sample"), "dockerfile").unwrap();
+    let ecparam = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.rule_id == "openssl-cli-ec")
+        .expect("expected an 'openssl-cli-ec' finding");
+    assert_eq!(ecparam.line, 7);
+    assert_eq!(ecparam.key_size, Some(256));
+
+    // ssh-keygen defaults to RSA without `-t`
+    let keygen = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.rule_id == "ssh-keygen-rsa")
+        .expect("expected an 'ssh-keygen-rsa' finding");
+    assert_eq!(keygen.crypto_type, CryptoType::Rsa);
+    assert_eq!(keygen.line, 8);
+
+    let result = analyze(include_str!("fixtures/Makefile.sample"), "makefile").unwrap();
+    let dhparam = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.rule_id == "openssl-cli-dh")
+        .expect("expected an 'openssl-cli-dh' finding");
+    assert_eq!(dhparam.line, 7);
+    assert_eq!(dhparam.crypto_type, CryptoType::DiffieHellman);
+    assert_eq!(dhparam.key_size, Some(1024));
+}
+
+#[test]
+fn test_ssh_keygen_rules_follow_key_type() {
+    let source = "ssh-keygen -t ed25519 -C deploy@example.com -f id_ed25519 -N ''
+ssh-keygen -t ecdsa -b 521 -f id_ecdsa
+ssh-keygen -t rsa -b 3072 -f id_rsa
+ssh-keygen -t dsa -f id_dsa
+";
+    let result = analyze(source, "shell").unwrap();
+    let found: Vec<(usize, &str, Option<u32>)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.rule_id.as_str(), v.key_size))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, "ssh-keygen-ed25519", None),
+            (2, "ssh-keygen-ecdsa", Some(521)),
+            (3, "ssh-keygen-rsa", Some(3072)),
+            (4, "ssh-keygen-dsa", None),
+        ]
+    );
+
+    let ed25519 = &result.vulnerabilities[0];
+    assert_eq!(ed25519.crypto_type, CryptoType::Eddsa);
+    assert!(!ed25519.message.contains("ECDSA"));
+    assert!(!ed25519.recommendation.contains("use ed25519"));
+    assert!(ed25519.recommendation.contains("mlkem768x25519-sha256"));
+}
+
+#[test]
+fn test_solidity_signature_rules() {
+    let result = analyze(include_str!("fixtures/sample_solidity.sol"), "solidity").unwrap();
+    let finding = |rule_id: &str| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.rule_id == rule_id)
+            .unwrap_or_else(|| panic!("expected a '{}' finding", rule_id))
+    };
+
+    let ecrecover = finding("solidity-ecrecover");
+    assert_eq!(ecrecover.line, 14);
+    assert_eq!(ecrecover.crypto_type, CryptoType::Ecdsa);
+    assert!(ecrecover.recommendation.contains("ERC-1271"));
+
+    assert_eq!(finding("openzeppelin-ecdsa").line, 19);
+    assert_eq!(finding("eth-address-derivation").line, 23);
+
+    // The comment mentioning ecrecover is not a finding
+    assert!(result.vulnerabilities.iter().all(|v| v.line != 13));
+}
+
+#[test]
+fn test_secp256k1_wallet_libraries() {
+    let rule_ids = |source: &str, language: &str| -> Vec<String> {
+        analyze(source, language)
+            .unwrap()
+            .vulnerabilities
+            .into_iter()
+            .map(|v| v.rule_id)
+            .collect()
+    };
+
+    let js = "import { ethers } from 'ethers';\nconst wallet = ethers.Wallet.createRandom();\nconst sig = await wallet.signMessage(message);\n";
+    assert_eq!(
+        rule_ids(js, "javascript"),
+        vec!["ethers-signing", "ethers-signing"]
+    );
+
+    let py = "from eth_account import Account\nsigned = Account.sign_message(message, private_key=key)\n";
+    assert_eq!(rule_ids(py, "python"), vec!["web3py-signing"]);
+
+    let go = "import \"github.com/ethereum/go-ethereum/crypto\"\n\nfunc sign(h []byte) ([]byte, error) {\n\treturn crypto.Sign(h, key)\n}\n";
+    assert_eq!(rule_ids(go, "go"), vec!["go-secp256k1"]);
+
+    // The curve name alone supersedes the generic ECDSA rule
+    let c = "secp256k1_context *ctx = secp256k1_context_create(SECP256K1_CONTEXT_SIGN);\n";
+    let found = analyze(c, "cpp").unwrap().vulnerabilities;
+    assert!(!found.is_empty());
+    assert!(found.iter().all(|v| v.rule_id == "secp256k1"));
+    assert!(found[0].recommendation.contains("post-quantum signatures"));
+    assert_eq!(found[0].key_size, Some(256));
+
+    // A key agreement on the curve is ECDH, not a blockchain signature
+    let js =
+        "const crypto = require('crypto');\nconst agreement = crypto.createECDH(\"secp256k1\");\n";
+    let found = analyze(js, "javascript").unwrap().vulnerabilities;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].rule_id, "ecdh");
+    assert_eq!(found[0].crypto_type, CryptoType::Ecdh);
+    assert_eq!(found[0].key_size, Some(256));
+    assert!(found[0].suppressed.iter().any(|s| s.rule_id == "secp256k1"));
+}
+
+#[test]
+fn test_auto_language_detection() {
+    // Extensionless script recognized by its `#!` line
+    let script = include_str!("fixtures/bin/rotate-keys");
+    let detection = Language::detect(Path::new("tests/fixtures/bin/rotate-keys"), script).unwrap();
+    assert_eq!(detection.language, Language::Python);
+    assert_eq!(detection.method, DetectionMethod::Shebang);
+
+    let result = analyze(script, "auto").unwrap();
+    assert_eq!(result.language, Language::Python);
+    let rsa = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.line == 5)
+        .expect("expected a finding for the key generation");
+    assert_eq!(rsa.crypto_type, CryptoType::Rsa);
+    assert_eq!(rsa.key_size, Some(1024));
+
+    let result = analyze(include_str!("fixtures/sample_solidity.sol"), "auto").unwrap();
+    assert_eq!(result.language, Language::Solidity);
+}
+
+#[test]
+fn test_findings_attributed_to_enclosing_scope() {
+    let result = analyze(include_str!("fixtures/sample_java.java"), "java").unwrap();
+    let rsa = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.crypto_type == CryptoType::Rsa)
+        .unwrap();
+    assert_eq!(rsa.scope.class.as_deref(), Some("CryptoExample"));
+    assert_eq!(rsa.scope.function.as_deref(), Some("main"));
+
+    let source = r#"package com.example.keys;
+
+public class Rotator {
+    public KeyPair rotate() throws Exception {
+        return KeyPairGenerator.getInstance("RSA").generateKeyPair();
+    }
+}
+"#;
+    let result = analyze(source, "java").unwrap();
+    let report = generate_sc13_report(&result, Some("src/Rotator.java"));
+    let evidence = &report.findings[0].evidence[0];
+    let location = evidence.source_location.as_ref().unwrap();
+    assert_eq!(location.scope.module.as_deref(), Some("com.example.keys"));
+    assert!(
+        evidence
+            .description
+            .contains("in Rotator.rotate (com.example.keys)")
+    );
+
+    let oscal = generate_oscal_json(&report, Some("src/Rotator.java"));
+    let props = oscal.assessment_results.results[0].observations[0]
+        .props
+        .as_ref()
+        .unwrap();
+    let prop = |name: &str| {
+        props
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_str())
+    };
+    assert_eq!(prop("enclosing-module"), Some("com.example.keys"));
+    assert_eq!(prop("enclosing-class"), Some("Rotator"));
+    assert_eq!(prop("enclosing-function"), Some("rotate"));
+
+    // Module-level code has no enclosing function or class
+    let result = analyze("import hashlib\ndigest = hashlib.md5(b'x')\n", "python").unwrap();
+    assert!(result.vulnerabilities[0].scope.is_empty());
+}
+
+#[test]
+fn test_symmetric_strength_flagged_under_cnsa_policy() {
+    let source = r#"from Crypto.Cipher import AES
+from cryptography.hazmat.primitives.ciphers import Cipher, algorithms, modes
+
+def encrypt(key, data):
+    legacy = AES.new(key, AES.MODE_ECB)
+    cipher = Cipher(algorithms.AES128(key), modes.CBC(iv=b"\x00" * 16))
+    strong = Cipher(algorithms.AES256(key), modes.GCM(os.urandom(12)))
+    return hashlib.sha224(data).digest()
+"#;
+    let result = analyze(source, "python").unwrap();
+    let found = |crypto_type: CryptoType| {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.crypto_type == crypto_type)
+            .unwrap_or_else(|| panic!("no {} finding", crypto_type))
+    };
+
+    let aes = found(CryptoType::Aes);
+    assert_eq!(aes.key_size, Some(128));
+    let level = aes.security_level.unwrap();
+    assert_eq!((level.classical_bits, level.post_quantum_bits), (128, 64));
+    assert_eq!(aes.severity, Severity::Medium);
+
+    let sha224 = found(CryptoType::Sha224).security_level.unwrap();
+    assert_eq!((sha224.classical_bits, sha224.post_quantum_bits), (112, 74));
+    assert_eq!(found(CryptoType::EcbMode).line, 5);
+    assert_eq!(found(CryptoType::StaticIv).line, 6);
+    assert!(found(CryptoType::EcbMode).security_level.is_none());
+
+    // AES-256 meets the policy and is not reported
+    assert!(!result.vulnerabilities.iter().any(|v| v.line == 7));
+
+    let report = generate_sc13_report(&result, Some("crypto.py"));
+    assert_eq!(
+        report.summary.policy_violations,
+        vec![
+            "ECB mode",
+            "AES 128-bit: 64-bit post-quantum security, below the 128-bit policy",
+            "Static IV",
+            "SHA-224: 74-bit post-quantum security, below the 128-bit policy",
+        ]
+    );
+    assert!(report.summary.weak_key_sizes.is_empty());
+    assert!(
+        report
+            .recommendations
+            .iter()
+            .any(|r| r.starts_with("CNSA 2.0 POLICY"))
+    );
+}
+
+#[test]
+fn test_64_bit_block_ciphers_constructed_not_imported() {
+    let source = "from Crypto.Cipher import Blowfish, ARC2
+b = Blowfish.new(key, Blowfish.MODE_CBC)
+r = ARC2.new(key, ARC2.MODE_CBC, effective_keylen=40)
+";
+    let result = analyze(source, "python").unwrap();
+    let found: Vec<(usize, &str, (u32, u32))> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| {
+            let level = v.security_level.unwrap();
+            (
+                v.line,
+                v.rule_id.as_str(),
+                (level.classical_bits, level.post_quantum_bits),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (2, "python-blowfish", (32, 32)),
+            (3, "python-rc2", (32, 20)),
+        ]
+    );
+
+    let result = analyze(
+        "Cipher c = Cipher.getInstance(\"DESede/CBC/PKCS5Padding\");",
+        "java",
+    )
+    .unwrap();
+    let level = result.vulnerabilities[0].security_level.unwrap();
+    assert_eq!(result.vulnerabilities[0].crypto_type, CryptoType::TripleDes);
+    assert_eq!((level.classical_bits, level.post_quantum_bits), (32, 32));
+
+    let source = "CCCrypt(kCCEncrypt, kCCAlgorithmAES, kCCOptionECBMode, key, kCCKeySizeAES256, NULL, in, n, out, n, &moved);";
+    let result = analyze(source, "objc").unwrap();
+    let ecb = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.crypto_type == CryptoType::EcbMode)
+        .unwrap();
+    assert_eq!(ecb.explanation.matched_text, "kCCOptionECBMode");
+}
+
+#[test]
+fn test_blowfish_and_jca_aes_key_generator() {
+    let result = analyze("b = Blowfish.new(k, Blowfish.MODE_CBC)", "python").unwrap();
+    assert_eq!(result.vulnerabilities.len(), 1);
+    let level = result.vulnerabilities[0].security_level.unwrap();
+    assert_eq!((level.classical_bits, level.post_quantum_bits), (32, 32));
+
+    let source = r#"class Keys {
+    static final int WRAP_BITS = 192;
+
+    SecretKey session() throws Exception {
+        KeyGenerator kg = KeyGenerator.getInstance("AES");
+        kg.init(128);
+        return kg.generateKey();
+    }
+
+    SecretKey wrapping() throws Exception {
+        KeyGenerator kg = KeyGenerator.getInstance("AES");
+        kg.init(WRAP_BITS);
+        return kg.generateKey();
+    }
+
+    SecretKey strong() throws Exception {
+        KeyGenerator kg = KeyGenerator.getInstance("AES");
+        kg.init(256);
+        return kg.generateKey();
+    }
+}
+"#;
+    let result = analyze(source, "java").unwrap();
+    let found: Vec<(usize, CryptoType, Option<u32>, Severity)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.crypto_type.clone(), v.key_size, v.severity))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (5, CryptoType::Aes, Some(128), Severity::Medium),
+            (11, CryptoType::Aes, Some(192), Severity::Low),
+        ]
+    );
+    let resolution = result.vulnerabilities[1]
+        .explanation
+        .key_size_resolution
+        .as_ref()
+        .unwrap();
+    assert_eq!(resolution.symbol, "WRAP_BITS");
+}
+
+#[test]
+fn test_post_quantum_inventory_tracks_migration() {
+    let legacy = r#"package main
+
+import "crypto/rsa"
+
+func keys() {
+	priv, _ := rsa.GenerateKey(rand.Reader, 2048)
+}
+"#;
+    let migrated = r#"package main
+
+import (
+	"crypto/mlkem"
+	"crypto/rsa"
+	"crypto/tls"
+)
+
+func keys() {
+	priv, _ := rsa.GenerateKey(rand.Reader, 2048)
+	dk, _ := mlkem.GenerateKey768()
+	cfg := &tls.Config{CurvePreferences: []tls.CurveID{tls.X25519MLKEM768}}
+	signer := mldsa.NewMLDSA65()
+}
+"#;
+    let before = analyze(legacy, "go").unwrap();
+    let after = analyze(migrated, "go").unwrap();
+    assert!(before.quantum_safe.is_empty());
+    assert_eq!(before.migration_progress(), Some(0));
+
+    let names: Vec<String> = after.quantum_safe.iter().map(|f| f.to_string()).collect();
+    assert_eq!(names, ["ML-KEM-768", "X25519 + ML-KEM-768", "ML-DSA-65"]);
+    assert_eq!(after.stats.quantum_safe_count, 3);
+    let ml_dsa = &after.quantum_safe[2];
+    assert_eq!(ml_dsa.family, PqcFamily::MlDsa);
+    assert_eq!((ml_dsa.line, ml_dsa.column), (13, 20));
+    assert_eq!(ml_dsa.scope.function.as_deref(), Some("keys"));
+
+    // "ML-DSA" and "X25519MLKEM768" are not reported as DSA or X25519
+    assert!(
+        after
+            .vulnerabilities
+            .iter()
+            .all(|v| v.crypto_type == CryptoType::Rsa)
+    );
+    assert_eq!(after.migration_progress(), Some(75));
+
+    let (before_sc13, after_sc13) = (
+        generate_sc13_report(&before, None).summary,
+        generate_sc13_report(&after, None).summary,
+    );
+    assert_eq!(after_sc13.migration_progress, Some(75));
+    assert_eq!(after_sc13.quantum_safe_algorithms, names);
+    assert!(after_sc13.compliance_score > before_sc13.compliance_score);
+
+    let classification = SecurityClassification::Unclassified;
+    let (before_itsg, after_itsg) = (
+        generate_itsg33_report(&before, classification, None).summary,
+        generate_itsg33_report(&after, classification, None).summary,
+    );
+    assert_eq!(after_itsg.migration_progress, Some(75));
+    assert!(after_itsg.compliance_score > before_itsg.compliance_score);
+}
+
+#[test]
+fn test_broken_and_pre_standard_pqc_flagged() {
+    let source = r#"from pqcrypto.kem.kyber768 import generate_keypair
+from pqcrypto.sign.dilithium3 import sign
+import oqs
+
+def handshake():
+    legacy = oqs.KeyEncapsulation("SIKE-p434")
+    signer = oqs.Signature("Rainbow-I-Classic")
+    hashsig = oqs.Signature("SPHINCS+-SHA2-128s-simple")
+    kem = oqs.KeyEncapsulation("ML-KEM-768")
+"#;
+    let result = analyze(source, "python").unwrap();
+    let found: Vec<(usize, CryptoType, Severity)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.crypto_type.clone(), v.severity))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, CryptoType::Kyber, Severity::Medium),
+            (2, CryptoType::Dilithium, Severity::Medium),
+            (6, CryptoType::Sike, Severity::Critical),
+            (7, CryptoType::Rainbow, Severity::Critical),
+            (8, CryptoType::SphincsPlus, Severity::Medium),
+        ]
+    );
+    assert!(
+        result.vulnerabilities[0]
+            .recommendation
+            .contains("Kyber768 to ML-KEM-768")
+    );
+    assert!(
+        result.vulnerabilities[1]
+            .recommendation
+            .contains("Dilithium3 to ML-DSA-65")
+    );
+    assert_eq!(result.quantum_safe_algorithms(), ["ML-KEM-768"]);
+
+    let summary = generate_sc13_report(&result, None).summary;
+    assert_eq!(summary.deprecated_algorithms, ["SIKE", "Rainbow"]);
+    let itsg = generate_itsg33_report(&result, SecurityClassification::Unclassified, None).summary;
+    assert_eq!(itsg.cccs_prohibited_algorithms, ["SIKE", "Rainbow"]);
+}
+
+#[test]
+fn test_pure_ml_kem_group_needs_hybrid() {
+    let source = "openssl s_server -groups MLKEM768:X25519MLKEM768 -accept 4433\n\
+                  openssl s_server -groups X25519MLKEM768:X25519 -accept 4434\n";
+    let result = analyze(source, "shell").unwrap();
+    assert_eq!(result.vulnerabilities.len(), 1);
+    let pure = &result.vulnerabilities[0];
+    assert_eq!(pure.crypto_type, CryptoType::NonHybridKem);
+    assert_eq!((pure.line, pure.column), (1, 25));
+
+    // The pure group still counts as a post-quantum algorithm in use
+    assert_eq!(
+        result.quantum_safe_algorithms(),
+        ["ML-KEM-768", "X25519 + ML-KEM-768"]
+    );
+    let summary = generate_sc13_report(&result, None).summary;
+    assert!(summary.policy_violations.is_empty());
+}
+
+#[test]
+fn test_ml_kem_groups_in_string_literals() {
+    let python = "cfg = {\"groups\": [\"MLKEM768\"]}\n";
+    let result = analyze(python, "python").unwrap();
+    assert_eq!(result.quantum_safe_algorithms(), ["ML-KEM-768"]);
+    assert_eq!(result.vulnerabilities.len(), 1);
+    let pure = &result.vulnerabilities[0];
+    assert_eq!(pure.crypto_type, CryptoType::NonHybridKem);
+    assert_eq!((pure.line, pure.column), (1, 19));
+
+    let js = "const tls = { groups: ['X25519MLKEM768', 'MLKEM1024'] };\n";
+    let result = analyze(js, "javascript").unwrap();
+    assert_eq!(
+        result.quantum_safe_algorithms(),
+        ["X25519 + ML-KEM-768", "ML-KEM-1024"]
+    );
+    assert_eq!(result.vulnerabilities.len(), 1);
+    assert_eq!(
+        result.vulnerabilities[0].crypto_type,
+        CryptoType::NonHybridKem
+    );
+}
+
+#[test]
+fn test_weak_random_escalates_into_key_material() {
+    let java = r#"import java.util.Random;
+
+public class Tokens {
+    public byte[] newKey() {
+        Random rnd = new Random();
+        byte[] key = new byte[32];
+        rnd.nextBytes(key);
+        return key;
+    }
+
+    public int jitter() {
+        return new Random(42).nextInt(100);
+    }
+}
+"#;
+    let result = analyze(java, "java").unwrap();
+    let found: Vec<(usize, &str, Severity)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.rule_id.as_str(), v.severity))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (5, "java-util-random", Severity::High),
+            (12, "fixed-random-seed", Severity::High),
+        ]
+    );
+    let weak = &result.vulnerabilities[0];
+    assert_eq!(weak.crypto_type, CryptoType::WeakRandom);
+    let step = weak.explanation.flows_into.as_ref().unwrap();
+    assert_eq!((step.name.as_str(), step.line), ("key", 7));
+    assert!(result.vulnerabilities[1].explanation.flows_into.is_none());
+
+    let python = "import random\n\ndef reset():\n    token = random.getrandbits(128)\n    delay = random.uniform(0, 1)\n";
+    let result = analyze(python, "python").unwrap();
+    let severities: Vec<Severity> = result.vulnerabilities.iter().map(|v| v.severity).collect();
+    assert_eq!(severities, [Severity::High, Severity::Medium]);
+
+    let go = "func init() {\n\trand.Seed(time.Now().UnixNano())\n\tnonce := make([]byte, 12)\n\tcrand.Read(nonce)\n}\n";
+    let result = analyze(go, "go").unwrap();
+    assert_eq!(result.vulnerabilities.len(), 1);
+    assert_eq!(result.vulnerabilities[0].rule_id, "fixed-random-seed");
+    assert_eq!(result.vulnerabilities[0].severity, Severity::High);
+}
+
+#[test]
+fn test_jose_algorithms_in_jwt_libraries() {
+    let cases = [
+        (
+            "javascript",
+            "const token = jwt.sign(claims, privateKey, { algorithm: 'RS256' });",
+        ),
+        (
+            "python",
+            "claims = jwt.decode(token, key, algorithms=[\"ES256\"])",
+        ),
+        (
+            "go",
+            "token := jwt.NewWithClaims(jwt.SigningMethodPS384, claims)",
+        ),
+        (
+            "java",
+            "jws.setAlgorithmHeaderValue(AlgorithmIdentifiers.RSA_USING_SHA256);",
+        ),
+    ];
+    let found: Vec<(String, CryptoType)> = cases
+        .iter()
+        .map(|(language, source)| {
+            let result = analyze(source, language).unwrap();
+            assert_eq!(result.vulnerabilities.len(), 1, "{}", source);
+            let vuln = &result.vulnerabilities[0];
+            (vuln.rule_id.clone(), vuln.crypto_type.clone())
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("jose-rsa".to_string(), CryptoType::Rsa),
+            ("jose-ecdsa".to_string(), CryptoType::Ecdsa),
+            ("jose-rsa".to_string(), CryptoType::Rsa),
+            ("jose-rsa".to_string(), CryptoType::Rsa),
+        ]
+    );
+
+    // Prose about algorithms is still ignored
+    let result = analyze("// We sign with RS256 for now\nlet x = 1;\n", "javascript").unwrap();
+    assert!(result.vulnerabilities.is_empty());
+}
+
+#[test]
+fn test_unsigned_jwt_and_short_hmac_secret() {
+    let js = r#"const jwt = require('jsonwebtoken');
+const claims = jwt.verify(token, 'changeme', { algorithms: ['HS256', 'none'] });
+const ok = jwt.verify(token, process.env.JWT_SECRET, { algorithms: ['HS256'] });
+"#;
+    let result = analyze(js, "javascript").unwrap();
+    let found: Vec<(usize, &str, Severity)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.rule_id.as_str(), v.severity))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (2, "jwt-weak-hmac-secret", Severity::High),
+            (2, "jwt-none", Severity::Critical),
+        ]
+    );
+    assert_eq!(
+        result.vulnerabilities[1].crypto_type,
+        CryptoType::UnsignedJwt
+    );
+    assert!(
+        result
+            .recommendations
+            .iter()
+            .any(|r| r.contains("alg: none"))
+    );
+
+    let python = "claims = jwt.decode(token, options={\"verify_signature\": False})\n";
+    let result = analyze(python, "python").unwrap();
+    assert_eq!(result.vulnerabilities[0].rule_id, "jwt-none");
+
+    let go = "s, err := token.SignedString([]byte(\"secret\"))\n";
+    let result = analyze(go, "go").unwrap();
+    assert_eq!(
+        result.vulnerabilities[0].crypto_type,
+        CryptoType::WeakJwtSecret
+    );
+
+    // Token findings are not CNSA 2.0 symmetric-strength violations
+    let result = analyze("const options = { alg: 'none' };\n", "javascript").unwrap();
+    assert_eq!(
+        result.vulnerabilities[0].crypto_type,
+        CryptoType::UnsignedJwt
+    );
+    let report = generate_sc13_report(&result, None);
+    assert!(report.summary.policy_violations.is_empty());
+    assert!(
+        !report
+            .recommendations
+            .iter()
+            .any(|r| r.starts_with("CNSA 2.0 POLICY"))
+    );
+}
+
+#[test]
+fn test_jwks_key_types_and_sizes() {
+    // A 2048-bit modulus: 342 base64url characters with the top bit set
+    let modulus = format!("g{}", "A".repeat(341));
+    let jwks = format!(
+        r#"{{
+  "keys": [
+    {{
+      "kty": "RSA",
+      "use": "sig",
+      "alg": "RS256",
+      "kid": "2026-signing",
+      "n": "{modulus}",
+      "e": "AQAB"
+    }},
+    {{
+      "kty": "EC",
+      "crv": "P-256",
+      "kid": "ec-signing",
+      "x": "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+      "y": "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"
+    }},
+    {{ "kty": "RSA", "use": "enc", "alg": "RSA1_5", "n": "{modulus}", "e": "AQAB" }}
+  ]
+}}
+"#
+    );
+    let language = Language::detect(Path::new(".well-known/jwks.json"), &jwks).unwrap();
+    assert_eq!(language.language, Language::Json);
+
+    // A key set under any other `.json` name is recognized by its content
+    let renamed = Language::detect(Path::new("config/keys.json"), &jwks).unwrap();
+    assert_eq!(renamed.language, Language::Json);
+
+    let result = analyze(&jwks, &language.language.to_string()).unwrap();
+    let found: Vec<(usize, &str, Option<u32>, Severity)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.rule_id.as_str(), v.key_size, v.severity))
+        .collect();
+    // The signing key's own `RS256` is part of its finding; `RSA1_5` is worse
+    // than the key it is used with, so it is reported as well
+    assert_eq!(
+        found,
+        vec![
+            (4, "jwk-rsa", Some(2048), Severity::High),
+            (12, "jwk-ec", Some(256), Severity::High),
+            (18, "jwk-rsa", Some(2048), Severity::High),
+            (18, "jose-rsa1-5", None, Severity::Critical),
+        ]
+    );
+    let rsa = &result.vulnerabilities[0];
+    assert_eq!(rsa.column, 14);
+    assert_eq!(rsa.explanation.matched_text, "RSA");
+    assert!(rsa.recommendation.contains("ML-DSA"));
+    assert_eq!(result.vulnerabilities[1].crypto_type, CryptoType::Ecdsa);
+    assert_eq!(
+        result.vulnerabilities[1]
+            .security_level
+            .map(|l| l.classical_bits),
+        Some(128)
+    );
+    assert!(result.vulnerabilities[2].recommendation.contains("ML-KEM"));
+}
+
+#[test]
+fn test_openid_discovery_document_accepting_none() {
+    let source = include_str!("fixtures/openid-configuration");
+    let language = Language::detect(Path::new(".well-known/openid-configuration"), source).unwrap();
+    assert_eq!(language.language, Language::Json);
+
+    let result = analyze(source, &language.language.to_string()).unwrap();
+    let found: Vec<(usize, &str)> = result
+        .vulnerabilities
+        .iter()
+        .map(|v| (v.line, v.rule_id.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![(9, "jose-ecdsa"), (10, "jwt-none"), (12, "jwt-none")]
+    );
+    let none = &result.vulnerabilities[1];
+    assert_eq!(none.crypto_type, CryptoType::UnsignedJwt);
+    assert_eq!(none.explanation.matched_text, "\"none\"");
+}
+
+#[test]
+fn test_reading_reports_without_finding_category() {
+    let result = analyze("let key = RSA::generate(2048);", "rust").unwrap();
+    let mut json = serde_json::to_value(&result.vulnerabilities[0]).unwrap();
+    json.as_object_mut().unwrap().remove("category");
+
+    let vuln: pqc_scanner::Vulnerability = serde_json::from_value(json).unwrap();
+    assert_eq!(vuln.crypto_type, CryptoType::Rsa);
+    assert_eq!(
+        vuln.category,
+        pqc_scanner::CryptoCategory::AsymmetricEncryption
+    );
+
+    let result = analyze("digest = hashlib.md5(data)", "python").unwrap();
+    let hash = result
+        .vulnerabilities
+        .iter()
+        .find(|v| v.crypto_type == CryptoType::Md5)
+        .unwrap();
+    let mut json = serde_json::to_value(hash).unwrap();
+    json.as_object_mut().unwrap().remove("category");
+
+    let vuln: pqc_scanner::Vulnerability = serde_json::from_value(json).unwrap();
+    assert_eq!(vuln.crypto_type, CryptoType::Md5);
+    assert_eq!(vuln.category, pqc_scanner::CryptoCategory::Hash);
+    assert_eq!(vuln.rule_id, hash.rule_id);
+}